@@ -0,0 +1,2 @@
+-- Employee id from the HR system, used to match staff across roster syncs
+ALTER TABLE staff ADD COLUMN IF NOT EXISTS external_id VARCHAR(255) UNIQUE;
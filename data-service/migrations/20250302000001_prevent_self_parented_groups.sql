@@ -0,0 +1,4 @@
+-- A group cannot be its own parent. Longer cycles are rejected by the service,
+-- which serialises hierarchy changes with an advisory lock.
+ALTER TABLE staff_groups
+    ADD CONSTRAINT staff_groups_parent_not_self CHECK (parent_id IS NULL OR parent_id <> id);
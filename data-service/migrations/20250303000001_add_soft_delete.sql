@@ -0,0 +1,8 @@
+-- Soft delete: deleted staff and groups keep their rows, and their memberships,
+-- so rosters that reference them still resolve and they can be restored
+ALTER TABLE staff ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+ALTER TABLE staff_groups ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Listings only show rows that are not deleted
+CREATE INDEX IF NOT EXISTS idx_staff_live_created_at ON staff(created_at) WHERE deleted_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_staff_groups_live_parent_id ON staff_groups(parent_id) WHERE deleted_at IS NULL;
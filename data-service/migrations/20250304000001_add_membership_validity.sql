@@ -0,0 +1,23 @@
+-- Memberships may start and end on given dates, both inclusive, so a planned
+-- transfer between teams can be recorded ahead of time. NULL leaves that end
+-- of the period open.
+ALTER TABLE group_memberships ADD COLUMN IF NOT EXISTS valid_from DATE;
+ALTER TABLE group_memberships ADD COLUMN IF NOT EXISTS valid_to DATE;
+
+ALTER TABLE group_memberships
+    ADD CONSTRAINT group_memberships_valid_period CHECK (valid_from <= valid_to);
+
+-- Someone who leaves a group and rejoins later holds one membership per stay,
+-- so memberships of the same staff and group may repeat as long as their
+-- periods do not overlap.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+ALTER TABLE group_memberships
+    DROP CONSTRAINT IF EXISTS group_memberships_staff_id_group_id_key;
+
+ALTER TABLE group_memberships
+    ADD CONSTRAINT group_memberships_no_overlap EXCLUDE USING gist (
+        staff_id WITH =,
+        group_id WITH =,
+        daterange(valid_from, valid_to, '[]') WITH &&
+    );
@@ -0,0 +1,6 @@
+-- What a staff member does within a group. Existing memberships become
+-- ordinary members.
+CREATE TYPE membership_role AS ENUM ('LEAD', 'MEMBER', 'TRAINEE', 'BACKUP');
+
+ALTER TABLE group_memberships
+    ADD COLUMN IF NOT EXISTS role membership_role NOT NULL DEFAULT 'MEMBER';
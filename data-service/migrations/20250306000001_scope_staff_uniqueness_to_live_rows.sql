@@ -0,0 +1,7 @@
+-- Emails and external ids only have to be unique among staff that are not
+-- deleted, so someone who was soft deleted can be created or synced again
+ALTER TABLE staff DROP CONSTRAINT IF EXISTS staff_email_key;
+ALTER TABLE staff DROP CONSTRAINT IF EXISTS staff_external_id_key;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_staff_live_email ON staff(email) WHERE deleted_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_staff_live_external_id ON staff(external_id) WHERE deleted_at IS NULL;
@@ -0,0 +1,29 @@
+-- Sibling groups may not share a name, ignoring case, so a path from the root
+-- names exactly one group. Existing clashes are not renamed automatically: the
+-- migration stops and lists them so they can be renamed or deleted first.
+DO $$
+DECLARE
+    clashes TEXT;
+BEGIN
+    SELECT string_agg(
+        format('"%s" under %s (groups %s)', names.name, COALESCE(names.parent_id::text, 'the root'), names.ids),
+        '; '
+    )
+    INTO clashes
+    FROM (
+        SELECT parent_id, MIN(name) AS name, string_agg(id::text, ', ' ORDER BY created_at, id) AS ids
+        FROM staff_groups
+        WHERE deleted_at IS NULL
+        GROUP BY parent_id, LOWER(name)
+        HAVING COUNT(*) > 1
+    ) names;
+
+    IF clashes IS NOT NULL THEN
+        RAISE EXCEPTION 'Sibling groups share a name: %', clashes
+            USING HINT = 'Rename or delete all but one group of each name, then run the migrations again';
+    END IF;
+END $$;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_staff_groups_live_sibling_name
+    ON staff_groups (COALESCE(parent_id, '00000000-0000-0000-0000-000000000000'::uuid), LOWER(name))
+    WHERE deleted_at IS NULL;
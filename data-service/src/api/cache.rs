@@ -0,0 +1,77 @@
+//! Invalidation of cached resolved members.
+//!
+//! A group's resolved members cover its whole subtree, so a change to one
+//! group's members also stales the cached list of every ancestor. Look the
+//! affected groups up before a write that would hide them (deleting a group or
+//! its memberships) and invalidate after it.
+
+use shared::{cache_keys, invalidate_cache_keys, invalidate_cache_pattern};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::api::state::AppState;
+
+/// Groups whose cached resolved members are stale, or `None` when they could
+/// not be looked up and every cached list must go
+#[derive(Debug)]
+pub struct StaleResolvedMembers {
+    groups: Option<HashSet<Uuid>>,
+}
+
+impl StaleResolvedMembers {
+    /// The groups and all of their ancestors
+    pub async fn of_groups(state: &AppState, group_ids: &[Uuid]) -> Self {
+        match state.group_repo.find_ancestor_ids(group_ids).await {
+            Ok(ids) => Self {
+                groups: Some(ids.into_iter().chain(group_ids.iter().copied()).collect()),
+            },
+            Err(e) => {
+                tracing::warn!("Failed to look up ancestors for cache invalidation: {}", e);
+                Self { groups: None }
+            }
+        }
+    }
+
+    /// Every group the staff member appears under
+    pub async fn of_staff(state: &AppState, staff_id: Uuid) -> Self {
+        match state
+            .membership_repo
+            .find_group_ids_by_staff(staff_id)
+            .await
+        {
+            Ok(group_ids) => Self::of_groups(state, &group_ids).await,
+            Err(e) => {
+                tracing::warn!("Failed to look up groups for cache invalidation: {}", e);
+                Self { groups: None }
+            }
+        }
+    }
+
+    pub fn merge(self, other: Self) -> Self {
+        let groups = match (self.groups, other.groups) {
+            (Some(mut groups), Some(more)) => {
+                groups.extend(more);
+                Some(groups)
+            }
+            _ => None,
+        };
+        Self { groups }
+    }
+
+    pub async fn invalidate(self, state: &AppState) {
+        let mut redis_conn = state.redis_pool.clone();
+        match self.groups {
+            Some(groups) => {
+                let keys: Vec<String> = groups
+                    .into_iter()
+                    .map(cache_keys::resolved_members)
+                    .collect();
+                invalidate_cache_keys(&mut redis_conn, &keys).await;
+            }
+            None => {
+                invalidate_cache_pattern(&mut redis_conn, cache_keys::RESOLVED_MEMBERS_PATTERN)
+                    .await
+            }
+        }
+    }
+}
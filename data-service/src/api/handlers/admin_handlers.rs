@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use shared::DomainError;
+use uuid::Uuid;
+
+use crate::api::cache::StaleResolvedMembers;
+use crate::api::state::AppState;
+
+fn purge_error(e: DomainError) -> (StatusCode, String) {
+    match e {
+        DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        DomainError::InvalidInput(message) => (StatusCode::CONFLICT, message),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/staff/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Staff ID")
+    ),
+    responses(
+        (status = 204, description = "Staff and their memberships permanently deleted"),
+        (status = 404, description = "Staff not found"),
+        (status = 409, description = "Staff must be soft deleted first"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "admin"
+)]
+pub async fn purge_staff(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let stale = StaleResolvedMembers::of_staff(&state, id).await;
+
+    state.staff_repo.purge(id).await.map_err(purge_error)?;
+
+    stale.invalidate(&state).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/groups/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Group ID")
+    ),
+    responses(
+        (status = 204, description = "Group, its subgroups and their memberships permanently deleted"),
+        (status = 404, description = "Group not found"),
+        (status = 409, description = "Group must be soft deleted first"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "admin"
+)]
+pub async fn purge_group(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    // A soft-deleted group is already out of its ancestors' resolved members
+    state.group_repo.purge(id).await.map_err(purge_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,309 +1,681 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use futures::future::join_all;
-use serde::{Deserialize, Serialize};
-use shared::{cache_keys, invalidate_cache_pattern, ApiResponse};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use shared::{cache_keys, invalidate_cache_pattern, ApiErrorResponse, ApiResponse, StaffStatus};
+use std::collections::HashMap;
 use utoipa::ToSchema;
+use uuid::Uuid;
 
-use crate::api::requests::CreateGroupRequest;
-use crate::api::requests::CreateStaffRequest;
+use crate::api::import_payload::{ImportPayload, ImportRow};
+use crate::api::requests::{
+    check_validity, AddMemberRequest, BatchGroupRow, BatchImportQuery, BatchMembershipRow,
+    CreateGroupRequest, CreateStaffRequest, UpdateStaffRequest,
+};
 use crate::api::state::AppState;
+use crate::domain::entities::{Staff, StaffGroup};
+use crate::domain::group_tree::GroupTree;
+use crate::domain::repositories::Transaction;
 
-const STAFF_JSON: &str = include_str!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/../sample-data/staff.json"
-));
-const GROUPS_JSON: &str = include_str!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/../sample-data/groups.json"
-));
-const MEMBERSHIPS_JSON: &str = include_str!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/../sample-data/memberships.json"
-));
-
-#[derive(Debug, Deserialize)]
-struct BatchGroupEntry {
-    name: String,
-    parent_name: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct BatchMembershipEntry {
-    staff_email: String,
-    group_name: String,
+/// A row that could not be imported
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BatchRowError {
+    /// Line of the payload the row starts on (for CSV, the header is line 1)
+    pub line: u64,
+    pub message: String,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct BatchImportSerializer {
     pub success_count: usize,
     pub error_count: usize,
-    pub errors: Vec<String>,
+    pub errors: Vec<BatchRowError>,
+    /// Whether the successful rows were saved. False for dry runs and for
+    /// all-or-nothing imports that had errors.
+    pub committed: bool,
+}
+
+/// A staff member touched by a sync
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StaffChange {
+    pub id: Uuid,
+    pub email: String,
+    pub external_id: Option<String>,
+    /// Fields the sync changed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_fields: Vec<String>,
+}
+
+impl StaffChange {
+    fn new(staff: &Staff, changed_fields: Vec<String>) -> Self {
+        Self {
+            id: staff.id,
+            email: staff.email.clone(),
+            external_id: staff.external_id.clone(),
+            changed_fields,
+        }
+    }
+}
+
+/// What a staff sync created, updated, deactivated and left alone
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct StaffSyncSerializer {
+    pub created: Vec<StaffChange>,
+    pub updated: Vec<StaffChange>,
+    /// Active staff missing from the roster. Only filled when every row
+    /// was read successfully.
+    pub deactivated: Vec<StaffChange>,
+    pub unchanged: Vec<StaffChange>,
+    pub error_count: usize,
+    pub errors: Vec<BatchRowError>,
+    /// Whether the changes were saved
+    pub committed: bool,
+}
+
+#[derive(Default)]
+struct ImportReport {
+    success_count: usize,
+    errors: Vec<BatchRowError>,
+}
+
+impl ImportReport {
+    fn record(&mut self, line: u64, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.success_count += 1,
+            Err(message) => self.errors.push(BatchRowError { line, message }),
+        }
+    }
+}
+
+pub(crate) async fn begin(state: &AppState) -> Result<Box<dyn Transaction>, (StatusCode, String)> {
+    state
+        .unit_of_work
+        .begin()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// Commit unless this is a dry run or an all-or-nothing import with errors,
+/// and say whether the rows were saved
+pub(crate) async fn complete(
+    state: &AppState,
+    query: BatchImportQuery,
+    tx: Box<dyn Transaction>,
+    has_errors: bool,
+    invalidates_resolved_members: bool,
+) -> Result<bool, (StatusCode, String)> {
+    let committed = !query.dry_run && (!query.all_or_nothing || !has_errors);
+    if committed {
+        tx.commit()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if invalidates_resolved_members {
+            let mut redis_conn = state.redis_pool.clone();
+            invalidate_cache_pattern(&mut redis_conn, cache_keys::RESOLVED_MEMBERS_PATTERN).await;
+        }
+    }
+
+    Ok(committed)
+}
+
+/// Answer with the report. A rolled-back import answers 422 with the report
+/// as the details of an error response.
+pub(crate) fn outcome<T: Serialize>(
+    query: BatchImportQuery,
+    committed: bool,
+    operation: &str,
+    report: T,
+) -> Response {
+    if query.dry_run {
+        let message = format!("{} validated", operation);
+        (StatusCode::OK, Json(ApiResponse::success(message, report))).into_response()
+    } else if committed {
+        let message = format!("{} completed", operation);
+        (StatusCode::OK, Json(ApiResponse::success(message, report))).into_response()
+    } else {
+        let message = format!("{} rolled back", operation);
+        (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiErrorResponse::new(message, report)),
+        )
+            .into_response()
+    }
+}
+
+async fn finish(
+    state: &AppState,
+    query: BatchImportQuery,
+    tx: Box<dyn Transaction>,
+    report: ImportReport,
+    operation: &str,
+    invalidates_resolved_members: bool,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let committed = complete(
+        state,
+        query,
+        tx,
+        !report.errors.is_empty(),
+        invalidates_resolved_members,
+    )
+    .await?;
+    let data = BatchImportSerializer {
+        success_count: report.success_count,
+        error_count: report.errors.len(),
+        errors: report.errors,
+        committed,
+    };
+
+    Ok(outcome(query, committed, operation, data))
+}
+
+pub(crate) fn validate_staff(request: &CreateStaffRequest) -> Result<(), String> {
+    if request.name.trim().is_empty() {
+        return Err("name must not be empty".to_string());
+    }
+    if !request.email.contains('@') {
+        return Err(format!("'{}' is not a valid email", request.email));
+    }
+    if request.position.trim().is_empty() {
+        return Err("position must not be empty".to_string());
+    }
+    Ok(())
 }
 
 #[utoipa::path(
     post,
     path = "/api/v1/batch/staff",
+    params(BatchImportQuery),
+    request_body(
+        description = "Staff rows as a JSON array, a CSV file with `name,email,position,status` columns, or either one as the `file` field of a multipart upload",
+        content(
+            (Vec<CreateStaffRequest> = "application/json"),
+            ("text/csv"),
+            ("multipart/form-data")
+        )
+    ),
     responses(
-        (status = 200, description = "Batch import completed", body = ApiResponse<BatchImportSerializer>),
+        (status = 200, description = "Batch import completed or validated", body = ApiResponse<BatchImportSerializer>),
+        (status = 400, description = "Payload could not be read"),
+        (status = 415, description = "Unsupported payload type"),
+        (status = 422, description = "All-or-nothing import rolled back", body = ApiErrorResponse<BatchImportSerializer>),
         (status = 500, description = "Internal server error")
     ),
     tag = "batch"
 )]
 pub async fn batch_import_staff(
     State(state): State<AppState>,
+    Query(query): Query<BatchImportQuery>,
+    payload: ImportPayload,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    // ...existing code...
-    let staff_list: Vec<CreateStaffRequest> = serde_json::from_str(STAFF_JSON).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to parse JSON: {}", e),
-        )
-    })?;
+    let rows = payload
+        .rows::<CreateStaffRequest>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let mut tx = begin(&state).await?;
+    let mut report = ImportReport::default();
+
+    for ImportRow { line, row } in rows {
+        let result = match row.and_then(|request| validate_staff(&request).map(|_| request)) {
+            Ok(request) => tx
+                .create_staff(&request)
+                .await
+                .map(|_| ())
+                .map_err(|e| format!("Failed to create staff '{}': {}", request.email, e)),
+            Err(e) => Err(e),
+        };
+        report.record(line, result);
+    }
 
-    let create_futures: Vec<_> = staff_list
-        .into_iter()
-        .map(|staff_request| {
-            let repo = state.staff_repo.clone();
-            async move { repo.create(staff_request).await }
-        })
-        .collect();
+    finish(&state, query, tx, report, "Batch staff import", false).await
+}
 
-    let results = join_all(create_futures).await;
+/// Look a group up by path, or by name when no path is given. Returns the
+/// reference that was used, or `None` when neither is set.
+fn find_group<'a>(
+    tree: &'a GroupTree,
+    path: &'a Option<String>,
+    name: &'a Option<String>,
+) -> Option<(&'a str, Result<Option<&'a StaffGroup>, String>)> {
+    if let Some(path) = path.as_deref().filter(|path| !path.is_empty()) {
+        return Some((path, tree.find_by_path(path)));
+    }
+    name.as_deref()
+        .filter(|name| !name.is_empty())
+        .map(|name| (name, tree.find_by_name(name)))
+}
 
-    let mut success_count = 0;
-    let mut error_count = 0;
-    let mut errors = Vec::new();
+/// Changes a synced row would make to an existing staff member, and the
+/// names of the fields it changes. Staff listed in the feed are active
+/// unless the row says otherwise.
+pub(crate) fn staff_update(
+    current: &Staff,
+    row: &CreateStaffRequest,
+) -> (UpdateStaffRequest, Vec<String>) {
+    fn changed<T: PartialEq + Clone>(current: &T, new: &T) -> Option<T> {
+        (current != new).then(|| new.clone())
+    }
 
-    for result in results {
-        match result {
-            Ok(_) => success_count += 1,
-            Err(e) => {
-                error_count += 1;
-                errors.push(e.to_string());
+    let status = row.status.clone().unwrap_or(StaffStatus::Active);
+    let update = UpdateStaffRequest {
+        name: changed(&current.name, &row.name),
+        email: changed(&current.email, &row.email),
+        position: changed(&current.position, &row.position),
+        status: changed(&current.status, &status),
+        external_id: row
+            .external_id
+            .as_ref()
+            .filter(|id| current.external_id.as_ref() != Some(*id))
+            .cloned()
+            .map(Some),
+    };
+
+    let fields = [
+        ("name", update.name.is_some()),
+        ("email", update.email.is_some()),
+        ("position", update.position.is_some()),
+        ("status", update.status.is_some()),
+        ("external_id", update.external_id.is_some()),
+    ]
+    .into_iter()
+    .filter(|(_, is_changed)| *is_changed)
+    .map(|(field, _)| field.to_string())
+    .collect();
+
+    (update, fields)
+}
+
+/// Find the staff member a synced row refers to: by external id when the
+/// row has one that is already known, otherwise by email
+pub(crate) fn match_staff<'a>(
+    existing: &'a [Staff],
+    row: &CreateStaffRequest,
+) -> Result<Option<&'a Staff>, String> {
+    if let Some(external_id) = &row.external_id {
+        if let Some(staff) = existing
+            .iter()
+            .find(|s| s.external_id.as_ref() == Some(external_id))
+        {
+            return Ok(Some(staff));
+        }
+    }
+
+    match existing.iter().find(|s| s.email == row.email) {
+        Some(staff) => match (&staff.external_id, &row.external_id) {
+            (Some(current), Some(new)) if current != new => Err(format!(
+                "'{}' belongs to staff with external id '{}', not '{}'",
+                row.email, current, new
+            )),
+            _ => Ok(Some(staff)),
+        },
+        None => Ok(None),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/batch/staff/sync",
+    params(BatchImportQuery),
+    request_body(
+        description = "The complete staff roster from the HR system, in the same formats as the staff import. Rows may carry an `external_id`",
+        content(
+            (Vec<CreateStaffRequest> = "application/json"),
+            ("text/csv"),
+            ("multipart/form-data")
+        )
+    ),
+    responses(
+        (status = 200, description = "Sync completed or validated", body = ApiResponse<StaffSyncSerializer>),
+        (status = 400, description = "Payload could not be read"),
+        (status = 415, description = "Unsupported payload type"),
+        (status = 422, description = "All-or-nothing sync rolled back", body = ApiErrorResponse<StaffSyncSerializer>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "batch"
+)]
+pub async fn sync_staff(
+    State(state): State<AppState>,
+    Query(query): Query<BatchImportQuery>,
+    payload: ImportPayload,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let rows = payload
+        .rows::<CreateStaffRequest>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let mut tx = begin(&state).await?;
+    let existing = tx
+        .list_staff()
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut report = StaffSyncSerializer::default();
+    // Staff matched so far, with the line that matched them
+    let mut matched: HashMap<Uuid, u64> = HashMap::new();
+    let mut emails: HashMap<String, u64> = HashMap::new();
+
+    for ImportRow { line, row } in rows {
+        let row = match row.and_then(|row| validate_staff(&row).map(|_| row)) {
+            Ok(row) => row,
+            Err(message) => {
+                report.errors.push(BatchRowError { line, message });
+                continue;
             }
+        };
+        if let Some(first) = emails.get(&row.email) {
+            report.errors.push(BatchRowError {
+                line,
+                message: format!("'{}' is already listed on line {}", row.email, first),
+            });
+            continue;
+        }
+        emails.insert(row.email.clone(), line);
+
+        let current = match match_staff(&existing, &row) {
+            Ok(current) => current,
+            Err(message) => {
+                report.errors.push(BatchRowError { line, message });
+                continue;
+            }
+        };
+
+        let Some(current) = current else {
+            match tx.create_staff(&row).await {
+                Ok(staff) => {
+                    matched.insert(staff.id, line);
+                    report.created.push(StaffChange::new(&staff, Vec::new()));
+                }
+                Err(e) => report.errors.push(BatchRowError {
+                    line,
+                    message: format!("Failed to create staff '{}': {}", row.email, e),
+                }),
+            }
+            continue;
+        };
+
+        if let Some(first) = matched.get(&current.id) {
+            report.errors.push(BatchRowError {
+                line,
+                message: format!("'{}' is the same staff member as line {}", row.email, first),
+            });
+            continue;
+        }
+        matched.insert(current.id, line);
+
+        let (update, fields) = staff_update(current, &row);
+        if fields.is_empty() {
+            report.unchanged.push(StaffChange::new(current, Vec::new()));
+            continue;
+        }
+        match tx.update_staff(current.id, &update).await {
+            Ok(staff) => report.updated.push(StaffChange::new(&staff, fields)),
+            Err(e) => report.errors.push(BatchRowError {
+                line,
+                message: format!("Failed to update staff '{}': {}", row.email, e),
+            }),
         }
     }
 
-    let data = BatchImportSerializer {
-        success_count,
-        error_count,
-        errors,
-    };
+    // A rejected row may be someone who is still employed, so only a fully
+    // readable roster deactivates the staff missing from it
+    if report.errors.is_empty() {
+        let deactivate = UpdateStaffRequest {
+            name: None,
+            email: None,
+            position: None,
+            status: Some(StaffStatus::Inactive),
+            external_id: None,
+        };
+        for staff in existing
+            .iter()
+            .filter(|s| s.status == StaffStatus::Active && !matched.contains_key(&s.id))
+        {
+            let staff = tx
+                .update_staff(staff.id, &deactivate)
+                .await
+                .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            report
+                .deactivated
+                .push(StaffChange::new(&staff, vec!["status".to_string()]));
+        }
+    }
 
-    Ok((
-        StatusCode::OK,
-        Json(ApiResponse::success("Batch staff import completed", data)),
-    ))
+    report.errors.sort_by_key(|error| error.line);
+    report.error_count = report.errors.len();
+    report.committed = complete(&state, query, tx, report.error_count > 0, true).await?;
+    let committed = report.committed;
+    Ok(outcome(query, committed, "Staff sync", report))
 }
 
 #[utoipa::path(
     post,
     path = "/api/v1/batch/groups",
+    params(BatchImportQuery),
+    request_body(
+        description = "Group rows as a JSON array, a CSV file with `name,parent_path` (or `name,parent_name`) columns, or either one as the `file` field of a multipart upload. A parent may be an existing group or another row; a parent name must be unique",
+        content(
+            (Vec<BatchGroupRow> = "application/json"),
+            ("text/csv"),
+            ("multipart/form-data")
+        )
+    ),
     responses(
-        (status = 200, description = "Batch import completed", body = ApiResponse<BatchImportSerializer>),
+        (status = 200, description = "Batch import completed or validated", body = ApiResponse<BatchImportSerializer>),
+        (status = 400, description = "Payload could not be read"),
+        (status = 415, description = "Unsupported payload type"),
+        (status = 422, description = "All-or-nothing import rolled back", body = ApiErrorResponse<BatchImportSerializer>),
         (status = 500, description = "Internal server error")
     ),
     tag = "batch"
 )]
 pub async fn batch_import_groups(
     State(state): State<AppState>,
+    Query(query): Query<BatchImportQuery>,
+    payload: ImportPayload,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let entries: Vec<BatchGroupEntry> = serde_json::from_str(GROUPS_JSON).map_err(|e| {
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            format!("Failed to parse JSON: {}", e),
-        )
-    })?;
-
-    let mut success_count = 0;
-    let mut error_count = 0;
-    let mut errors = Vec::new();
-
-    // Phase 1: Create all groups in parallel (without parent relationships)
-    let create_futures: Vec<_> = entries
-        .iter()
-        .map(|entry| {
-            let repo = state.group_repo.clone();
-            let name = entry.name.clone();
-            async move {
-                let request = CreateGroupRequest {
-                    name: name.clone(),
-                    parent_id: None,
-                };
-                (name, repo.create(request).await)
+    let rows = payload
+        .rows::<BatchGroupRow>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let mut tx = begin(&state).await?;
+    let mut report = ImportReport::default();
+
+    // Phase 1: Create all groups (without parent relationships)
+    let mut created = Vec::new();
+    for ImportRow { line, row } in rows {
+        let entry = match row {
+            Ok(entry) if entry.name.trim().is_empty() => {
+                report.record(line, Err("name must not be empty".to_string()));
+                continue;
             }
-        })
-        .collect();
-
-    let create_results = join_all(create_futures).await;
-
-    for (name, result) in create_results {
-        match result {
-            Ok(_) => success_count += 1,
+            Ok(entry) => entry,
             Err(e) => {
-                error_count += 1;
-                errors.push(format!("Failed to create group '{}': {}", name, e));
+                report.record(line, Err(e));
+                continue;
             }
+        };
+
+        let request = CreateGroupRequest {
+            name: entry.name.clone(),
+            parent_id: None,
+        };
+        match tx.create_group(&request).await {
+            Ok(group) => created.push((line, group, entry)),
+            Err(e) => report.record(
+                line,
+                Err(format!("Failed to create group '{}': {}", entry.name, e)),
+            ),
         }
     }
 
-    // Phase 2: Set parent relationships (need to be after all groups are created)
-    for entry in &entries {
-        if let Some(parent_name) = &entry.parent_name {
-            let parent_future = state.group_repo.find_by_name(parent_name);
-            let child_future = state.group_repo.find_by_name(&entry.name);
-
-            let (parent_result, child_result) = futures::join!(parent_future, child_future);
+    // Phase 2: Set parent relationships, now that every group of the batch exists
+    let mut tree = GroupTree::new(
+        tx.list_groups()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+    );
+    for (line, group, entry) in created {
+        let Some((reference, parent)) = find_group(&tree, &entry.parent_path, &entry.parent_name)
+        else {
+            report.record(line, Ok(()));
+            continue;
+        };
 
-            let parent = match parent_result {
-                Ok(Some(p)) => p,
-                Ok(None) => {
-                    error_count += 1;
-                    errors.push(format!(
+        let parent_id = match parent {
+            Ok(Some(parent)) => parent.id,
+            Ok(None) => {
+                report.record(
+                    line,
+                    Err(format!(
                         "Parent group '{}' not found for '{}'",
-                        parent_name, entry.name
-                    ));
-                    continue;
-                }
-                Err(e) => {
-                    error_count += 1;
-                    errors.push(format!("Error looking up parent '{}': {}", parent_name, e));
-                    continue;
-                }
-            };
-
-            let child = match child_result {
-                Ok(Some(c)) => c,
-                Ok(None) => continue,
-                Err(e) => {
-                    error_count += 1;
-                    errors.push(format!("Error looking up group '{}': {}", entry.name, e));
-                    continue;
-                }
-            };
-
-            let update_request = crate::api::requests::UpdateGroupRequest {
-                name: None,
-                parent_id: Some(parent.id),
-            };
-
-            if let Err(e) = state.group_repo.update(child.id, update_request).await {
-                error_count += 1;
-                errors.push(format!("Failed to set parent for '{}': {}", entry.name, e));
+                        reference, entry.name
+                    )),
+                );
+                continue;
+            }
+            Err(e) => {
+                report.record(line, Err(e));
+                continue;
             }
+        };
+
+        let result = tx
+            .set_group_parent(group.id, parent_id)
+            .await
+            .map_err(|e| format!("Failed to set parent for '{}': {}", entry.name, e));
+        if result.is_ok() {
+            tree.insert(StaffGroup {
+                parent_id: Some(parent_id),
+                ..group
+            });
         }
+        report.record(line, result);
     }
+    report.errors.sort_by_key(|error| error.line);
 
-    // Invalidate resolved members cache since group hierarchy changed
-    let mut redis_conn = state.redis_pool.clone();
-    invalidate_cache_pattern(&mut redis_conn, cache_keys::RESOLVED_MEMBERS_PATTERN).await;
-
-    let data = BatchImportSerializer {
-        success_count,
-        error_count,
-        errors,
-    };
-
-    Ok((
-        StatusCode::OK,
-        Json(ApiResponse::success("Batch groups import completed", data)),
-    ))
+    finish(&state, query, tx, report, "Batch groups import", true).await
 }
 
 #[utoipa::path(
     post,
     path = "/api/v1/batch/memberships",
+    params(BatchImportQuery),
+    request_body(
+        description = "Membership rows as a JSON array, a CSV file with `staff_email,group_path` (or `staff_email,group_name`) columns and optional `role`, `valid_from` and `valid_to` columns, or either one as the `file` field of a multipart upload",
+        content(
+            (Vec<BatchMembershipRow> = "application/json"),
+            ("text/csv"),
+            ("multipart/form-data")
+        )
+    ),
     responses(
-        (status = 200, description = "Batch import completed", body = ApiResponse<BatchImportSerializer>),
+        (status = 200, description = "Batch import completed or validated", body = ApiResponse<BatchImportSerializer>),
+        (status = 400, description = "Payload could not be read"),
+        (status = 415, description = "Unsupported payload type"),
+        (status = 422, description = "All-or-nothing import rolled back", body = ApiErrorResponse<BatchImportSerializer>),
         (status = 500, description = "Internal server error")
     ),
     tag = "batch"
 )]
 pub async fn batch_import_memberships(
     State(state): State<AppState>,
+    Query(query): Query<BatchImportQuery>,
+    payload: ImportPayload,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let entries: Vec<BatchMembershipEntry> =
-        serde_json::from_str(MEMBERSHIPS_JSON).map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to parse JSON: {}", e),
-            )
-        })?;
-
-    let mut success_count = 0;
-    let mut error_count = 0;
-    let mut errors = Vec::new();
-
-    for entry in &entries {
-        let staff_future = state.staff_repo.find_by_email(&entry.staff_email);
-        let group_future = state.group_repo.find_by_name(&entry.group_name);
-
-        let (staff_result, group_result) = futures::join!(staff_future, group_future);
-
-        let staff = match staff_result {
+    let rows = payload
+        .rows::<BatchMembershipRow>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let mut tx = begin(&state).await?;
+    let mut report = ImportReport::default();
+    let tree = GroupTree::new(
+        tx.list_groups()
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?,
+    );
+
+    for ImportRow { line, row } in rows {
+        let entry = match row {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.record(line, Err(e));
+                continue;
+            }
+        };
+        if let Err(e) = check_validity(entry.valid_from, entry.valid_to) {
+            report.record(line, Err(e));
+            continue;
+        }
+        let staff = match tx.find_staff_by_email(&entry.staff_email).await {
             Ok(Some(s)) => s,
             Ok(None) => {
-                error_count += 1;
-                errors.push(format!(
-                    "Staff with email '{}' not found",
-                    entry.staff_email
-                ));
+                report.record(
+                    line,
+                    Err(format!(
+                        "Staff with email '{}' not found",
+                        entry.staff_email
+                    )),
+                );
                 continue;
             }
             Err(e) => {
-                error_count += 1;
-                errors.push(format!(
-                    "Error looking up staff '{}': {}",
-                    entry.staff_email, e
-                ));
+                report.record(
+                    line,
+                    Err(format!(
+                        "Error looking up staff '{}': {}",
+                        entry.staff_email, e
+                    )),
+                );
                 continue;
             }
         };
 
-        let group = match group_result {
-            Ok(Some(g)) => g,
-            Ok(None) => {
-                error_count += 1;
-                errors.push(format!("Group '{}' not found", entry.group_name));
+        let group = match find_group(&tree, &entry.group_path, &entry.group_name) {
+            Some((_, Ok(Some(group)))) => group,
+            Some((reference, Ok(None))) => {
+                report.record(line, Err(format!("Group '{}' not found", reference)));
                 continue;
             }
-            Err(e) => {
-                error_count += 1;
-                errors.push(format!(
-                    "Error looking up group '{}': {}",
-                    entry.group_name, e
-                ));
+            Some((_, Err(e))) => {
+                report.record(line, Err(e));
+                continue;
+            }
+            None => {
+                report.record(
+                    line,
+                    Err("group_path or group_name is required".to_string()),
+                );
                 continue;
             }
         };
-
-        match state.membership_repo.add_member(staff.id, group.id).await {
-            Ok(_) => success_count += 1,
-            Err(e) => {
-                error_count += 1;
-                errors.push(format!(
+        let group_reference = entry
+            .group_path
+            .as_deref()
+            .or(entry.group_name.as_deref())
+            .unwrap_or_default();
+
+        let result = tx
+            .add_member(
+                group.id,
+                &AddMemberRequest {
+                    staff_id: staff.id,
+                    role: entry.role.unwrap_or_default(),
+                    valid_from: entry.valid_from,
+                    valid_to: entry.valid_to,
+                },
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                format!(
                     "Failed to add '{}' to '{}': {}",
-                    entry.staff_email, entry.group_name, e
-                ));
-            }
-        }
+                    entry.staff_email, group_reference, e
+                )
+            });
+        report.record(line, result);
     }
 
-    // Invalidate resolved members cache since memberships changed
-    let mut redis_conn = state.redis_pool.clone();
-    invalidate_cache_pattern(&mut redis_conn, cache_keys::RESOLVED_MEMBERS_PATTERN).await;
-
-    let data = BatchImportSerializer {
-        success_count,
-        error_count,
-        errors,
-    };
-
-    Ok((
-        StatusCode::OK,
-        Json(ApiResponse::success(
-            "Batch memberships import completed",
-            data,
-        )),
-    ))
+    finish(&state, query, tx, report, "Batch memberships import", true).await
 }
@@ -4,17 +4,24 @@ use axum::{
     response::IntoResponse,
     Json,
 };
+use chrono::Utc;
 use futures::future::try_join_all;
 use shared::{
-    cache_keys, cache_ttl, get_cached, invalidate_cache, set_cached, ApiResponse, DomainError,
+    cache_keys, cache_ttl, get_cached_field, set_cached_field, ApiResponse, DomainError,
     PaginationParams,
 };
 use uuid::Uuid;
 
-use crate::api::requests::{CreateGroupRequest, UpdateGroupRequest};
+use crate::api::cache::StaleResolvedMembers;
+use crate::api::requests::{
+    ArchiveQuery, CreateGroupRequest, GroupTreeQuery, MoveGroupRequest, ResolvedMembersQuery,
+    UpdateGroupRequest,
+};
 use crate::api::state::AppState;
 use crate::domain::entities::StaffGroup;
-use crate::presentation::{GroupSerializer, ResolvedGroupSerializer};
+use crate::presentation::{
+    BreadcrumbSerializer, GroupSerializer, GroupTreeNodeSerializer, ResolvedGroupSerializer,
+};
 
 async fn resolve_parent_name(
     state: &AppState,
@@ -23,7 +30,7 @@ async fn resolve_parent_name(
     if let Some(parent_id) = group.parent_id {
         let parent = state
             .group_repo
-            .find_by_id(parent_id)
+            .find_by_id_including_deleted(parent_id)
             .await
             .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
         Ok(parent.map(|p| p.name))
@@ -32,6 +39,36 @@ async fn resolve_parent_name(
     }
 }
 
+fn group_error(e: DomainError) -> (StatusCode, String) {
+    match e {
+        DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        DomainError::InvalidInput(message) => (StatusCode::BAD_REQUEST, message),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
+/// Update a group and invalidate the resolved members of its ancestors, both
+/// before and after a move
+async fn update_hierarchy(
+    state: &AppState,
+    id: Uuid,
+    request: UpdateGroupRequest,
+) -> Result<StaffGroup, (StatusCode, String)> {
+    let before = StaleResolvedMembers::of_groups(state, &[id]).await;
+    let group = state
+        .group_repo
+        .update(id, request)
+        .await
+        .map_err(group_error)?;
+
+    before
+        .merge(StaleResolvedMembers::of_groups(state, &[id]).await)
+        .invalidate(state)
+        .await;
+
+    Ok(group)
+}
+
 async fn to_group_serializer(
     state: &AppState,
     group: StaffGroup,
@@ -46,7 +83,7 @@ async fn to_group_serializer(
     request_body = CreateGroupRequest,
     responses(
         (status = 201, description = "Group created successfully", body = ApiResponse<GroupSerializer>),
-        (status = 400, description = "Bad request"),
+        (status = 400, description = "Bad request, or the parent does not exist or is deleted"),
         (status = 500, description = "Internal server error")
     ),
     tag = "groups"
@@ -59,7 +96,7 @@ pub async fn create_group(
         .group_repo
         .create(request)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(group_error)?;
 
     let serializer = to_group_serializer(&state, group).await?;
 
@@ -76,7 +113,8 @@ pub async fn create_group(
     get,
     path = "/api/v1/groups/{id}",
     params(
-        ("id" = Uuid, Path, description = "Group ID")
+        ("id" = Uuid, Path, description = "Group ID"),
+        ArchiveQuery
     ),
     responses(
         (status = 200, description = "Group found", body = ApiResponse<GroupSerializer>),
@@ -88,11 +126,14 @@ pub async fn create_group(
 pub async fn get_group_by_id(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(archive): Query<ArchiveQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let group = state
-        .group_repo
-        .find_by_id(id)
-        .await
+    let group = if archive.include_deleted {
+        state.group_repo.find_by_id_including_deleted(id).await
+    } else {
+        state.group_repo.find_by_id(id).await
+    };
+    let group = group
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or((StatusCode::NOT_FOUND, "Group not found".to_string()))?;
 
@@ -105,7 +146,7 @@ pub async fn get_group_by_id(
 #[utoipa::path(
     get,
     path = "/api/v1/groups",
-    params(PaginationParams),
+    params(PaginationParams, ArchiveQuery),
     responses(
         (status = 200, description = "Group list", body = ApiResponse<Vec<GroupSerializer>>),
         (status = 500, description = "Internal server error")
@@ -115,10 +156,11 @@ pub async fn get_group_by_id(
 pub async fn list_groups(
     State(state): State<AppState>,
     Query(params): Query<PaginationParams>,
+    Query(archive): Query<ArchiveQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     let (groups, total) = state
         .group_repo
-        .list(params.clone())
+        .list(params.clone(), archive.include_deleted)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -144,6 +186,7 @@ pub async fn list_groups(
     request_body = UpdateGroupRequest,
     responses(
         (status = 200, description = "Group updated successfully", body = ApiResponse<GroupSerializer>),
+        (status = 400, description = "The new parent or name breaks the hierarchy"),
         (status = 404, description = "Group not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -154,18 +197,7 @@ pub async fn update_group(
     Path(id): Path<Uuid>,
     Json(request): Json<UpdateGroupRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let group = state
-        .group_repo
-        .update(id, request)
-        .await
-        .map_err(|e| match e {
-            DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
-            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-        })?;
-
-    // Invalidate resolved members cache for this group
-    let mut redis_conn = state.redis_pool.clone();
-    invalidate_cache(&mut redis_conn, &cache_keys::resolved_members(id)).await;
+    let group = update_hierarchy(&state, id, request).await?;
 
     let serializer = to_group_serializer(&state, group).await?;
 
@@ -178,6 +210,40 @@ pub async fn update_group(
     ))
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/groups/{id}/move",
+    params(
+        ("id" = Uuid, Path, description = "Group ID")
+    ),
+    request_body = MoveGroupRequest,
+    responses(
+        (status = 200, description = "Group and its subgroups moved", body = ApiResponse<GroupSerializer>),
+        (status = 400, description = "The new parent does not exist, is the group itself or one of its subgroups, or already has a group with this name"),
+        (status = 404, description = "Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "groups"
+)]
+pub async fn move_group(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(request): Json<MoveGroupRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let update = UpdateGroupRequest {
+        name: None,
+        parent_id: Some(request.parent_id),
+    };
+    let group = update_hierarchy(&state, id, update).await?;
+
+    let serializer = to_group_serializer(&state, group).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success("Group moved successfully", serializer)),
+    ))
+}
+
 #[utoipa::path(
     delete,
     path = "/api/v1/groups/{id}",
@@ -185,7 +251,7 @@ pub async fn update_group(
         ("id" = Uuid, Path, description = "Group ID")
     ),
     responses(
-        (status = 204, description = "Group deleted successfully"),
+        (status = 204, description = "Group and its subgroups soft deleted"),
         (status = 404, description = "Group not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -195,26 +261,62 @@ pub async fn delete_group(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    state.group_repo.delete(id).await.map_err(|e| match e {
-        DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
-        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-    })?;
+    // The ancestors can no longer be found once the group is gone
+    let stale = StaleResolvedMembers::of_groups(&state, &[id]).await;
 
-    // Invalidate resolved members cache for this group
-    let mut redis_conn = state.redis_pool.clone();
-    invalidate_cache(&mut redis_conn, &cache_keys::resolved_members(id)).await;
+    state.group_repo.delete(id).await.map_err(group_error)?;
+
+    stale.invalidate(&state).await;
 
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/groups/{id}/restore",
+    params(
+        ("id" = Uuid, Path, description = "Group ID")
+    ),
+    responses(
+        (status = 200, description = "Group and the subgroups deleted with it restored", body = ApiResponse<GroupSerializer>),
+        (status = 400, description = "The parent is deleted, or already has a group with this name"),
+        (status = 404, description = "Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "groups"
+)]
+pub async fn restore_group(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let group = state.group_repo.restore(id).await.map_err(group_error)?;
+
+    StaleResolvedMembers::of_groups(&state, &[id])
+        .await
+        .invalidate(&state)
+        .await;
+
+    let serializer = to_group_serializer(&state, group).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Group restored successfully",
+            serializer,
+        )),
+    ))
+}
+
 #[utoipa::path(
     get,
     path = "/api/v1/groups/{id}/resolved-members",
     params(
-        ("id" = Uuid, Path, description = "Group ID")
+        ("id" = Uuid, Path, description = "Group ID"),
+        ResolvedMembersQuery
     ),
     responses(
-        (status = 200, description = "Resolved members grouped by subgroup", body = ApiResponse<Vec<ResolvedGroupSerializer>>),
+        (status = 200, description = "Resolved members grouped by subgroup, counting memberships in effect on any day of the period", body = ApiResponse<Vec<ResolvedGroupSerializer>>),
+        (status = 400, description = "Invalid period"),
         (status = 404, description = "Group not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -223,7 +325,12 @@ pub async fn delete_group(
 pub async fn get_resolved_members(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(query): Query<ResolvedMembersQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (from, to) = query
+        .period(Utc::now().date_naive())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
     state
         .group_repo
         .find_by_id(id)
@@ -234,19 +341,26 @@ pub async fn get_resolved_members(
             format!("Group with id {} not found", id),
         ))?;
 
+    // One hash per group, with a field per period, so invalidating the group
+    // drops every period at once
     let cache_key = cache_keys::resolved_members(id);
+    let cache_field = format!("{}:{}", from, to);
     let mut redis_conn = state.redis_pool.clone();
 
     // Check cache first
-    if let Some(response) =
-        get_cached::<ApiResponse<Vec<ResolvedGroupSerializer>>>(&mut redis_conn, &cache_key).await
+    if let Some(response) = get_cached_field::<ApiResponse<Vec<ResolvedGroupSerializer>>>(
+        &mut redis_conn,
+        &cache_key,
+        &cache_field,
+    )
+    .await
     {
         return Ok((StatusCode::OK, Json(response)));
     }
 
     let (groups_with_members, total_unique) = state
         .group_repo
-        .get_resolved_members(id)
+        .get_resolved_members(id, from, to)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
@@ -262,9 +376,10 @@ pub async fn get_resolved_members(
     );
 
     // Cache the result
-    set_cached(
+    set_cached_field(
         &mut redis_conn,
         &cache_key,
+        &cache_field,
         &response,
         cache_ttl::RESOLVED_MEMBERS,
     )
@@ -272,3 +387,106 @@ pub async fn get_resolved_members(
 
     Ok((StatusCode::OK, Json(response)))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/groups/{id}/ancestors",
+    params(
+        ("id" = Uuid, Path, description = "Group ID")
+    ),
+    responses(
+        (status = 200, description = "Breadcrumb from the root down to the group", body = ApiResponse<BreadcrumbSerializer>),
+        (status = 404, description = "Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "groups"
+)]
+pub async fn get_group_ancestors(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let groups = state
+        .group_repo
+        .get_ancestors(id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    if groups.is_empty() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("Group with id {} not found", id),
+        ));
+    }
+
+    let response = ApiResponse::success(
+        "Group ancestors retrieved successfully",
+        BreadcrumbSerializer::from(groups),
+    );
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/groups/{id}/tree",
+    params(
+        ("id" = Uuid, Path, description = "Group ID"),
+        GroupTreeQuery
+    ),
+    responses(
+        (status = 200, description = "The group with its subgroups nested below it", body = ApiResponse<GroupTreeNodeSerializer>),
+        (status = 404, description = "Group not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "groups"
+)]
+pub async fn get_group_tree(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<GroupTreeQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let nodes = state
+        .group_repo
+        .get_subtree(Some(id), query.max_depth, query.include_inactive)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let tree = GroupTreeNodeSerializer::build(nodes)
+        .into_iter()
+        .next()
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            format!("Group with id {} not found", id),
+        ))?;
+
+    let response = ApiResponse::success("Group tree retrieved successfully", tree);
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/groups/tree",
+    params(GroupTreeQuery),
+    responses(
+        (status = 200, description = "Every root group with its subgroups nested below it", body = ApiResponse<Vec<GroupTreeNodeSerializer>>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "groups"
+)]
+pub async fn get_group_forest(
+    State(state): State<AppState>,
+    Query(query): Query<GroupTreeQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let nodes = state
+        .group_repo
+        .get_subtree(None, query.max_depth, query.include_inactive)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let forest = GroupTreeNodeSerializer::build(nodes);
+    let total = forest.len() as u64;
+    let response = ApiResponse::with_total("Group forest retrieved successfully", forest, total);
+
+    Ok((StatusCode::OK, Json(response)))
+}
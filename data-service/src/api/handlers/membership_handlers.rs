@@ -1,17 +1,28 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
-use redis::AsyncCommands;
 use shared::{ApiResponse, DomainError};
 use uuid::Uuid;
 
-use crate::api::requests::AddMemberRequest;
+use crate::api::cache::StaleResolvedMembers;
+use crate::api::requests::{
+    check_validity, AddMemberRequest, MembershipQuery, UpdateMembershipRequest,
+};
 use crate::api::state::AppState;
 use crate::presentation::{MembershipSerializer, StaffSerializer};
 
+/// Map a membership write error, reporting overlapping periods as a conflict
+fn membership_error(e: DomainError) -> (StatusCode, String) {
+    match e {
+        DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        DomainError::InvalidInput(_) => (StatusCode::CONFLICT, e.to_string()),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    }
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/groups/{group_id}/members",
@@ -22,6 +33,8 @@ use crate::presentation::{MembershipSerializer, StaffSerializer};
     responses(
         (status = 201, description = "Member added successfully", body = ApiResponse<MembershipSerializer>),
         (status = 400, description = "Bad request"),
+        (status = 404, description = "Staff or group not found"),
+        (status = 409, description = "Staff is already a member of the group during part of the period"),
         (status = 500, description = "Internal server error")
     ),
     tag = "memberships"
@@ -31,12 +44,10 @@ pub async fn add_member(
     Path(group_id): Path<Uuid>,
     Json(request): Json<AddMemberRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let membership = state
-        .membership_repo
-        .add_member(request.staff_id, group_id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    check_validity(request.valid_from, request.valid_to)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
 
+    // Looked up first, so deleted staff and groups cannot gain members
     let staff = state
         .staff_repo
         .find_by_id(request.staff_id)
@@ -51,8 +62,16 @@ pub async fn add_member(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or((StatusCode::NOT_FOUND, "Group not found".to_string()))?;
 
-    let mut redis_conn = state.redis_pool.clone();
-    let _: Result<(), _> = redis_conn.del(format!("group:resolved:{}", group_id)).await;
+    let membership = state
+        .membership_repo
+        .add_member(group_id, request)
+        .await
+        .map_err(membership_error)?;
+
+    StaleResolvedMembers::of_groups(&state, &[group_id])
+        .await
+        .invalidate(&state)
+        .await;
 
     Ok((
         StatusCode::CREATED,
@@ -63,12 +82,92 @@ pub async fn add_member(
     ))
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/v1/groups/{group_id}/members/{staff_id}",
+    params(
+        ("group_id" = Uuid, Path, description = "Group ID"),
+        ("staff_id" = Uuid, Path, description = "Staff ID"),
+        MembershipQuery
+    ),
+    request_body = UpdateMembershipRequest,
+    responses(
+        (status = 200, description = "Membership updated successfully", body = ApiResponse<MembershipSerializer>),
+        (status = 400, description = "Bad request, or membership_id is needed to pick one of several memberships"),
+        (status = 404, description = "Membership not found"),
+        (status = 409, description = "The new period overlaps another membership"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "memberships"
+)]
+pub async fn update_member(
+    State(state): State<AppState>,
+    Path((group_id, staff_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<MembershipQuery>,
+    Json(request): Json<UpdateMembershipRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    check_validity(request.valid_from, request.valid_to)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let staff = state
+        .staff_repo
+        .find_by_id(staff_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Staff not found".to_string()))?;
+
+    let group = state
+        .group_repo
+        .find_by_id(group_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Group not found".to_string()))?;
+
+    let memberships = state
+        .membership_repo
+        .find_memberships(staff_id, group_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let membership_id = match (query.membership_id, memberships.as_slice()) {
+        (Some(id), _) if memberships.iter().any(|m| m.id == id) => id,
+        (None, [membership]) => membership.id,
+        (None, [_, _, ..]) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Staff holds several memberships of this group; pick one with membership_id"
+                    .to_string(),
+            ));
+        }
+        _ => return Err((StatusCode::NOT_FOUND, "Membership not found".to_string())),
+    };
+
+    let membership = state
+        .membership_repo
+        .update_member(membership_id, request)
+        .await
+        .map_err(membership_error)?;
+
+    StaleResolvedMembers::of_groups(&state, &[group_id])
+        .await
+        .invalidate(&state)
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Membership updated successfully",
+            MembershipSerializer::new(membership, &staff, &group),
+        )),
+    ))
+}
+
 #[utoipa::path(
     delete,
     path = "/api/v1/groups/{group_id}/members/{staff_id}",
     params(
         ("group_id" = Uuid, Path, description = "Group ID"),
-        ("staff_id" = Uuid, Path, description = "Staff ID")
+        ("staff_id" = Uuid, Path, description = "Staff ID"),
+        MembershipQuery
     ),
     responses(
         (status = 204, description = "Member removed successfully"),
@@ -80,18 +179,21 @@ pub async fn add_member(
 pub async fn remove_member(
     State(state): State<AppState>,
     Path((group_id, staff_id)): Path<(Uuid, Uuid)>,
+    Query(query): Query<MembershipQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     state
         .membership_repo
-        .remove_member(staff_id, group_id)
+        .remove_member(staff_id, group_id, query.membership_id)
         .await
         .map_err(|e| match e {
             DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         })?;
 
-    let mut redis_conn = state.redis_pool.clone();
-    let _: Result<(), _> = redis_conn.del(format!("group:resolved:{}", group_id)).await;
+    StaleResolvedMembers::of_groups(&state, &[group_id])
+        .await
+        .invalidate(&state)
+        .await;
 
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,6 +1,8 @@
+pub mod admin_handlers;
 pub mod batch_handlers;
 pub mod group_handlers;
 pub mod membership_handlers;
+pub mod organisation_handlers;
 pub mod staff_handlers;
 
 use axum::{http::StatusCode, response::IntoResponse, Json};
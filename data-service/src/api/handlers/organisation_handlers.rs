@@ -0,0 +1,413 @@
+use axum::{
+    body::Bytes,
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use shared::{ApiErrorResponse, ApiResponse, MembershipRole, StaffStatus};
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::api::handlers::batch_handlers::{
+    begin, complete, match_staff, outcome, staff_update, validate_staff,
+};
+use crate::api::requests::{
+    check_validity, AddMemberRequest, BatchImportQuery, CreateGroupRequest, CreateStaffRequest,
+    DocumentFormat, OrganisationExportQuery, OrganisationImportQuery, UpdateMembershipRequest,
+};
+use crate::api::state::AppState;
+use crate::domain::group_tree::{join_path, split_path, GroupTree};
+use crate::presentation::{
+    OrganisationDocument, OrganisationGroup, OrganisationMembership, OrganisationStaff,
+    ORGANISATION_DOCUMENT_VERSION,
+};
+
+/// How many entries of one kind an import created, updated or left alone
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ImportCounts {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+}
+
+/// An entry of the document that could not be imported
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DocumentError {
+    /// The entry, e.g. `groups[2]`
+    pub entry: String,
+    pub message: String,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct OrganisationImportSerializer {
+    pub staff: ImportCounts,
+    pub groups: ImportCounts,
+    pub memberships: ImportCounts,
+    pub error_count: usize,
+    pub errors: Vec<DocumentError>,
+    /// Whether the document was saved. An import is all-or-nothing, so this
+    /// is false for dry runs and whenever there are errors.
+    pub committed: bool,
+}
+
+impl OrganisationImportSerializer {
+    fn fail(&mut self, section: &str, index: usize, message: impl Into<String>) {
+        self.errors.push(DocumentError {
+            entry: format!("{}[{}]", section, index),
+            message: message.into(),
+        });
+    }
+}
+
+/// A staff member's stays in a group cannot overlap, so each is identified by
+/// its staff, group and first day, which may be open
+type MembershipKey = (Uuid, Uuid, Option<NaiveDate>);
+
+/// The id and role of a membership with its last day, which may be open
+type Terms = (Uuid, MembershipRole, Option<NaiveDate>);
+
+fn internal_error(e: impl ToString) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/organisation/export",
+    params(OrganisationExportQuery),
+    responses(
+        (status = 200, description = "Every staff member, group and membership as one versioned document", body = OrganisationDocument),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "organisation"
+)]
+pub async fn export_organisation(
+    State(state): State<AppState>,
+    Query(query): Query<OrganisationExportQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    // Reading all three tables in one transaction gives a consistent snapshot
+    let mut tx = begin(&state).await?;
+    let staff = tx.list_staff().await.map_err(internal_error)?;
+    let groups = tx.list_groups().await.map_err(internal_error)?;
+    let memberships = tx.list_memberships().await.map_err(internal_error)?;
+    drop(tx);
+
+    let tree = GroupTree::new(groups);
+    let emails: HashMap<Uuid, &str> = staff.iter().map(|s| (s.id, s.email.as_str())).collect();
+
+    let mut document = OrganisationDocument {
+        version: ORGANISATION_DOCUMENT_VERSION,
+        exported_at: Some(Utc::now()),
+        staff: staff
+            .iter()
+            .map(|s| OrganisationStaff {
+                name: s.name.clone(),
+                email: s.email.clone(),
+                position: s.position.clone(),
+                status: Some(s.status.clone()),
+                external_id: s.external_id.clone(),
+            })
+            .collect(),
+        groups: tree
+            .groups()
+            .filter_map(|group| tree.path_of(group.id))
+            .map(|path| OrganisationGroup { path })
+            .collect(),
+        memberships: memberships
+            .iter()
+            .filter_map(|membership| {
+                Some(OrganisationMembership {
+                    staff_email: emails.get(&membership.staff_id)?.to_string(),
+                    group_path: tree.path_of(membership.group_id)?,
+                    role: Some(membership.role),
+                    valid_from: membership.valid_from,
+                    valid_to: membership.valid_to,
+                })
+            })
+            .collect(),
+    };
+    document.staff.sort_by(|a, b| a.email.cmp(&b.email));
+    document.groups.sort_by(|a, b| a.path.cmp(&b.path));
+    document
+        .memberships
+        .sort_by(|a, b| (&a.group_path, &a.staff_email).cmp(&(&b.group_path, &b.staff_email)));
+
+    let body = match query.format {
+        DocumentFormat::Json => serde_json::to_string_pretty(&document).map_err(internal_error)?,
+        DocumentFormat::Yaml => serde_yaml::to_string(&document).map_err(internal_error)?,
+    };
+    let disposition = format!(
+        "attachment; filename=\"organisation-{}.{}\"",
+        Utc::now().format("%Y-%m-%d"),
+        query.format.extension()
+    );
+
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/organisation/import",
+    params(OrganisationImportQuery),
+    request_body(
+        description = "A document from the export endpoint, as JSON or YAML. Staff are matched by external id or email and updated, missing groups and memberships are created, membership roles and dates are replaced, and nothing is deleted",
+        content(
+            (OrganisationDocument = "application/json"),
+            (OrganisationDocument = "application/yaml")
+        )
+    ),
+    responses(
+        (status = 200, description = "Document imported or validated", body = ApiResponse<OrganisationImportSerializer>),
+        (status = 400, description = "Document could not be read or has an unsupported version"),
+        (status = 415, description = "Unsupported document type"),
+        (status = 422, description = "Document had errors and nothing was imported", body = ApiErrorResponse<OrganisationImportSerializer>),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "organisation"
+)]
+pub async fn import_organisation(
+    State(state): State<AppState>,
+    Query(query): Query<OrganisationImportQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let format = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(DocumentFormat::from_content_type)
+        .ok_or((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Send the document as application/json or application/yaml".to_string(),
+        ))?;
+    let document: OrganisationDocument = match format {
+        DocumentFormat::Json => serde_json::from_slice(&body).map_err(|e| e.to_string()),
+        DocumentFormat::Yaml => serde_yaml::from_slice(&body).map_err(|e| e.to_string()),
+    }
+    .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid document: {}", e)))?;
+
+    if document.version != ORGANISATION_DOCUMENT_VERSION {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Unsupported document version {} (expected {})",
+                document.version, ORGANISATION_DOCUMENT_VERSION
+            ),
+        ));
+    }
+
+    let mut tx = begin(&state).await?;
+    let mut report = OrganisationImportSerializer::default();
+
+    // Staff: matched like a roster sync, but nobody is deactivated
+    let existing = tx.list_staff().await.map_err(internal_error)?;
+    let mut matched = HashSet::new();
+    let mut emails = HashSet::new();
+    for (index, entry) in document.staff.into_iter().enumerate() {
+        let row = CreateStaffRequest {
+            name: entry.name,
+            email: entry.email,
+            position: entry.position,
+            status: entry.status.or(Some(StaffStatus::Active)),
+            external_id: entry.external_id,
+        };
+        if let Err(e) = validate_staff(&row) {
+            report.fail("staff", index, e);
+            continue;
+        }
+        if !emails.insert(row.email.clone()) {
+            report.fail("staff", index, format!("'{}' is listed twice", row.email));
+            continue;
+        }
+
+        match match_staff(&existing, &row) {
+            Ok(Some(current)) => {
+                if !matched.insert(current.id) {
+                    report.fail(
+                        "staff",
+                        index,
+                        format!("'{}' is the same staff member as another entry", row.email),
+                    );
+                    continue;
+                }
+                let (update, fields) = staff_update(current, &row);
+                if fields.is_empty() {
+                    report.staff.unchanged += 1;
+                } else {
+                    match tx.update_staff(current.id, &update).await {
+                        Ok(_) => report.staff.updated += 1,
+                        Err(e) => report.fail("staff", index, e.to_string()),
+                    }
+                }
+            }
+            Ok(None) => match tx.create_staff(&row).await {
+                Ok(_) => report.staff.created += 1,
+                Err(e) => report.fail("staff", index, e.to_string()),
+            },
+            Err(e) => report.fail("staff", index, e),
+        }
+    }
+
+    // Groups: parents before children, whatever order the document uses
+    let mut tree = GroupTree::new(tx.list_groups().await.map_err(internal_error)?);
+    let mut groups = Vec::new();
+    for (index, entry) in document.groups.iter().enumerate() {
+        match split_path(&entry.path) {
+            Ok(names) => groups.push((index, names)),
+            Err(e) => report.fail("groups", index, e),
+        }
+    }
+    groups.sort_by_key(|(_, names)| names.len());
+
+    for (index, names) in groups {
+        let (name, ancestors) = names.split_last().expect("a path has at least one name");
+        let parent_id = if ancestors.is_empty() {
+            None
+        } else {
+            let parent_path = join_path(ancestors);
+            match tree.find_by_path(&parent_path) {
+                Ok(Some(parent)) => Some(parent.id),
+                Ok(None) => {
+                    report.fail(
+                        "groups",
+                        index,
+                        format!("Parent group '{}' is not in the document", parent_path),
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    report.fail("groups", index, e);
+                    continue;
+                }
+            }
+        };
+
+        match tree.child(parent_id, name) {
+            Ok(Some(_)) => report.groups.unchanged += 1,
+            Ok(None) => {
+                let request = CreateGroupRequest {
+                    name: name.clone(),
+                    parent_id,
+                };
+                match tx.create_group(&request).await {
+                    Ok(group) => {
+                        tree.insert(group);
+                        report.groups.created += 1;
+                    }
+                    Err(e) => report.fail("groups", index, e.to_string()),
+                }
+            }
+            Err(e) => report.fail("groups", index, e),
+        }
+    }
+
+    // Memberships, against staff and groups as they are after the import
+    let staff_ids: HashMap<String, Uuid> = tx
+        .list_staff()
+        .await
+        .map_err(internal_error)?
+        .into_iter()
+        .map(|s| (s.email, s.id))
+        .collect();
+    let mut memberships: HashMap<MembershipKey, Terms> = tx
+        .list_memberships()
+        .await
+        .map_err(internal_error)?
+        .into_iter()
+        .map(|m| {
+            (
+                (m.staff_id, m.group_id, m.valid_from),
+                (m.id, m.role, m.valid_to),
+            )
+        })
+        .collect();
+
+    for (index, entry) in document.memberships.iter().enumerate() {
+        if let Err(e) = check_validity(entry.valid_from, entry.valid_to) {
+            report.fail("memberships", index, e);
+            continue;
+        }
+        let Some(&staff_id) = staff_ids.get(&entry.staff_email) else {
+            report.fail(
+                "memberships",
+                index,
+                format!("Staff with email '{}' not found", entry.staff_email),
+            );
+            continue;
+        };
+        let group_id = match tree.find_by_path(&entry.group_path) {
+            Ok(Some(group)) => group.id,
+            Ok(None) => {
+                report.fail(
+                    "memberships",
+                    index,
+                    format!("Group '{}' not found", entry.group_path),
+                );
+                continue;
+            }
+            Err(e) => {
+                report.fail("memberships", index, e);
+                continue;
+            }
+        };
+
+        let role = entry.role.unwrap_or_default();
+        let key = (staff_id, group_id, entry.valid_from);
+        match memberships.get(&key).copied() {
+            Some((_, current_role, current_to))
+                if current_role == role && current_to == entry.valid_to =>
+            {
+                report.memberships.unchanged += 1
+            }
+            Some((membership_id, _, _)) => {
+                let request = UpdateMembershipRequest {
+                    role,
+                    valid_from: entry.valid_from,
+                    valid_to: entry.valid_to,
+                };
+                match tx.update_member(membership_id, &request).await {
+                    Ok(_) => {
+                        memberships.insert(key, (membership_id, role, entry.valid_to));
+                        report.memberships.updated += 1
+                    }
+                    Err(e) => report.fail("memberships", index, e.to_string()),
+                }
+            }
+            None => {
+                let request = AddMemberRequest {
+                    staff_id,
+                    role,
+                    valid_from: entry.valid_from,
+                    valid_to: entry.valid_to,
+                };
+                match tx.add_member(group_id, &request).await {
+                    Ok(membership) => {
+                        memberships.insert(key, (membership.id, role, entry.valid_to));
+                        report.memberships.created += 1
+                    }
+                    Err(e) => report.fail("memberships", index, e.to_string()),
+                }
+            }
+        }
+    }
+
+    let query = BatchImportQuery {
+        dry_run: query.dry_run,
+        all_or_nothing: true,
+    };
+    report.error_count = report.errors.len();
+    report.committed = complete(&state, query, tx, report.error_count > 0, true).await?;
+    let committed = report.committed;
+    Ok(outcome(query, committed, "Organisation import", report))
+}
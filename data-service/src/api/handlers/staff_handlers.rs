@@ -7,7 +7,10 @@ use axum::{
 use shared::{ApiResponse, DomainError, PaginationParams};
 use uuid::Uuid;
 
-use crate::api::requests::{CreateStaffRequest, UpdateStaffRequest};
+use crate::api::cache::StaleResolvedMembers;
+use crate::api::requests::{
+    ArchiveQuery, CreateStaffRequest, StaffFilter, StaffSort, UpdateStaffRequest,
+};
 use crate::api::state::AppState;
 use crate::presentation::StaffSerializer;
 
@@ -45,7 +48,8 @@ pub async fn create_staff(
     get,
     path = "/api/v1/staff/{id}",
     params(
-        ("id" = Uuid, Path, description = "Staff ID")
+        ("id" = Uuid, Path, description = "Staff ID"),
+        ArchiveQuery
     ),
     responses(
         (status = 200, description = "Staff found", body = ApiResponse<StaffSerializer>),
@@ -57,11 +61,14 @@ pub async fn create_staff(
 pub async fn get_staff_by_id(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Query(archive): Query<ArchiveQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let staff = state
-        .staff_repo
-        .find_by_id(id)
-        .await
+    let staff = if archive.include_deleted {
+        state.staff_repo.find_by_id_including_deleted(id).await
+    } else {
+        state.staff_repo.find_by_id(id).await
+    };
+    let staff = staff
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
         .ok_or((StatusCode::NOT_FOUND, "Staff not found".to_string()))?;
 
@@ -74,9 +81,10 @@ pub async fn get_staff_by_id(
 #[utoipa::path(
     get,
     path = "/api/v1/staff",
-    params(PaginationParams),
+    params(PaginationParams, StaffFilter, StaffSort),
     responses(
-        (status = 200, description = "Staff list", body = ApiResponse<Vec<StaffSerializer>>),
+        (status = 200, description = "Staff list, with `next_cursor` when more staff follow", body = ApiResponse<Vec<StaffSerializer>>),
+        (status = 400, description = "Invalid filter or cursor"),
         (status = 500, description = "Internal server error")
     ),
     tag = "staff"
@@ -84,17 +92,24 @@ pub async fn get_staff_by_id(
 pub async fn list_staff(
     State(state): State<AppState>,
     Query(params): Query<PaginationParams>,
+    Query(filter): Query<StaffFilter>,
+    Query(sort): Query<StaffSort>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    let (staff_list, total) = state
+    let page = state
         .staff_repo
-        .list(params.clone())
+        .list(&filter, &sort, params)
         .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        .map_err(|e| match e {
+            DomainError::InvalidInput(message) => (StatusCode::BAD_REQUEST, message),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
 
     let serialized: Vec<StaffSerializer> =
-        staff_list.into_iter().map(StaffSerializer::from).collect();
+        page.staff.into_iter().map(StaffSerializer::from).collect();
 
-    let response = ApiResponse::with_total("Staff list retrieved successfully", serialized, total);
+    let response =
+        ApiResponse::with_total("Staff list retrieved successfully", serialized, page.total)
+            .with_next_cursor(page.next_cursor);
 
     Ok((StatusCode::OK, Json(response)))
 }
@@ -127,6 +142,12 @@ pub async fn update_staff(
             _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
         })?;
 
+    // Resolved members show the staff details and only list active staff
+    StaleResolvedMembers::of_staff(&state, id)
+        .await
+        .invalidate(&state)
+        .await;
+
     Ok((
         StatusCode::OK,
         Json(ApiResponse::success(
@@ -143,7 +164,7 @@ pub async fn update_staff(
         ("id" = Uuid, Path, description = "Staff ID")
     ),
     responses(
-        (status = 204, description = "Staff deleted successfully"),
+        (status = 204, description = "Staff soft deleted"),
         (status = 404, description = "Staff not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -153,10 +174,53 @@ pub async fn delete_staff(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
+    // Deleted staff drop out of resolved members
+    let stale = StaleResolvedMembers::of_staff(&state, id).await;
+
     state.staff_repo.delete(id).await.map_err(|e| match e {
         DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
         _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
     })?;
 
+    stale.invalidate(&state).await;
+
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/staff/{id}/restore",
+    params(
+        ("id" = Uuid, Path, description = "Staff ID")
+    ),
+    responses(
+        (status = 200, description = "Staff restored successfully", body = ApiResponse<StaffSerializer>),
+        (status = 404, description = "Staff not found"),
+        (status = 409, description = "Email or external id taken by another staff member"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "staff"
+)]
+pub async fn restore_staff(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let staff = state.staff_repo.restore(id).await.map_err(|e| match e {
+        DomainError::NotFound(_) => (StatusCode::NOT_FOUND, e.to_string()),
+        DomainError::InvalidInput(message) => (StatusCode::CONFLICT, message),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })?;
+
+    StaleResolvedMembers::of_staff(&state, id)
+        .await
+        .invalidate(&state)
+        .await;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Staff restored successfully",
+            StaffSerializer::from(staff),
+        )),
+    ))
+}
@@ -0,0 +1,205 @@
+//! Request bodies for batch imports: a JSON array, a CSV file with a header
+//! row, or either one uploaded as the `file` field of a multipart form.
+
+use axum::{
+    async_trait,
+    body::Bytes,
+    extract::{FromRequest, Multipart, Request},
+    http::{header, StatusCode},
+};
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadFormat {
+    Json,
+    Csv,
+}
+
+impl PayloadFormat {
+    fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next()?.trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "application/json" => Some(Self::Json),
+            "text/csv" | "application/csv" => Some(Self::Csv),
+            _ if mime.ends_with("+json") => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    fn from_file_name(file_name: &str) -> Option<Self> {
+        let (_, extension) = file_name.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// One record of a payload, with the line it was found on
+#[derive(Debug)]
+pub struct ImportRow<T> {
+    pub line: u64,
+    pub row: Result<T, String>,
+}
+
+#[derive(Debug)]
+pub struct ImportPayload {
+    format: PayloadFormat,
+    content: Bytes,
+}
+
+impl ImportPayload {
+    pub fn new(format: PayloadFormat, content: impl Into<Bytes>) -> Self {
+        Self {
+            format,
+            content: content.into(),
+        }
+    }
+
+    /// Split the payload into rows. A row that does not fit `T` is reported
+    /// on that row; only a payload that cannot be read at all is an error.
+    pub fn rows<T: DeserializeOwned>(&self) -> Result<Vec<ImportRow<T>>, String> {
+        match self.format {
+            PayloadFormat::Json => self.json_rows(),
+            PayloadFormat::Csv => self.csv_rows(),
+        }
+    }
+
+    fn json_rows<T: DeserializeOwned>(&self) -> Result<Vec<ImportRow<T>>, String> {
+        let text = std::str::from_utf8(&self.content)
+            .map_err(|e| format!("Payload is not valid UTF-8: {}", e))?;
+        let items: Vec<&RawValue> = serde_json::from_str(text)
+            .map_err(|e| format!("Payload must be a JSON array: {}", e))?;
+
+        Ok(items
+            .into_iter()
+            .map(|item| {
+                // Each item borrows from `text`, so its offset gives its line
+                let offset = item.get().as_ptr() as usize - text.as_ptr() as usize;
+                let line = text[..offset].matches('\n').count() as u64 + 1;
+                match serde_json::from_str(item.get()) {
+                    Ok(row) => ImportRow { line, row: Ok(row) },
+                    Err(e) => ImportRow {
+                        line: line + e.line() as u64 - 1,
+                        row: Err(json_error_message(&e)),
+                    },
+                }
+            })
+            .collect())
+    }
+
+    fn csv_rows<T: DeserializeOwned>(&self) -> Result<Vec<ImportRow<T>>, String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(self.content.as_ref());
+        let headers = reader
+            .headers()
+            .map_err(|e| format!("Unreadable CSV header: {}", e))?
+            .clone();
+
+        Ok(reader
+            .records()
+            .map(|record| match record {
+                Ok(record) => ImportRow {
+                    line: record.position().map_or(0, |position| position.line()),
+                    row: record
+                        .deserialize(Some(&headers))
+                        .map_err(|e| csv_error_message(&e)),
+                },
+                Err(e) => ImportRow {
+                    line: e.position().map_or(0, |position| position.line()),
+                    row: Err(csv_error_message(&e)),
+                },
+            })
+            .collect())
+    }
+}
+
+/// The serde message without its position, which is reported as the line
+fn json_error_message(e: &serde_json::Error) -> String {
+    let message = e.to_string();
+    match message.rfind(" at line ") {
+        Some(position) => message[..position].to_string(),
+        None => message,
+    }
+}
+
+fn csv_error_message(e: &csv::Error) -> String {
+    match e.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+        csv::ErrorKind::UnequalLengths {
+            expected_len, len, ..
+        } => format!("expected {} fields, found {}", expected_len, len),
+        _ => e.to_string(),
+    }
+}
+
+fn rejection(status: StatusCode, message: impl Into<String>) -> (StatusCode, String) {
+    (status, message.into())
+}
+
+#[async_trait]
+impl<S> FromRequest<S> for ImportPayload
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, String);
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+
+        if !content_type.starts_with("multipart/form-data") {
+            let format = PayloadFormat::from_content_type(&content_type).ok_or_else(|| {
+                rejection(
+                    StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                    "Send application/json, text/csv or a multipart/form-data upload",
+                )
+            })?;
+            let content = Bytes::from_request(req, state)
+                .await
+                .map_err(|e| rejection(e.status(), e.body_text()))?;
+            return Ok(Self::new(format, content));
+        }
+
+        let mut multipart = Multipart::from_request(req, state)
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?;
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| rejection(e.status(), e.body_text()))?
+        {
+            if field.name() != Some("file") {
+                continue;
+            }
+            // Browsers and curl often send uploads as application/octet-stream
+            let format = field
+                .content_type()
+                .and_then(PayloadFormat::from_content_type)
+                .or_else(|| field.file_name().and_then(PayloadFormat::from_file_name))
+                .ok_or_else(|| {
+                    rejection(
+                        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                        "Upload a .json or .csv file",
+                    )
+                })?;
+            let content = field
+                .bytes()
+                .await
+                .map_err(|e| rejection(e.status(), e.body_text()))?;
+            return Ok(Self::new(format, content));
+        }
+
+        Err(rejection(
+            StatusCode::BAD_REQUEST,
+            "Multipart upload has no `file` field",
+        ))
+    }
+}
@@ -1,4 +1,6 @@
+pub mod cache;
 pub mod handlers;
+pub mod import_payload;
 pub mod requests;
 pub mod routes;
 pub mod state;
@@ -0,0 +1,47 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use shared::MembershipRole;
+use utoipa::{IntoParams, ToSchema};
+
+/// How a batch import applies its rows
+#[derive(Debug, Clone, Copy, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BatchImportQuery {
+    /// Validate every row against the database, then roll back
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Import nothing unless every row succeeds
+    #[serde(default)]
+    pub all_or_nothing: bool,
+}
+
+/// A group to create, placed under its parent when one is given. The parent
+/// may be another row of the same import.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchGroupRow {
+    pub name: String,
+    /// Parent by path, e.g. `Engineering Department/Backend Team`
+    #[serde(default)]
+    pub parent_path: Option<String>,
+    /// Parent by name, only accepted while no other group has that name
+    #[serde(default)]
+    pub parent_name: Option<String>,
+}
+
+/// Adds the staff member with this email to a group, given by path or by
+/// (unique) name, optionally with a role and for a limited period
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BatchMembershipRow {
+    pub staff_email: String,
+    #[serde(default)]
+    pub group_path: Option<String>,
+    #[serde(default)]
+    pub group_name: Option<String>,
+    /// MEMBER when omitted
+    #[serde(default)]
+    pub role: Option<MembershipRole>,
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+}
@@ -1,5 +1,6 @@
+use chrono::NaiveDate;
 use serde::Deserialize;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -11,5 +12,59 @@ pub struct CreateGroupRequest {
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateGroupRequest {
     pub name: Option<String>,
+    /// Leave out to keep the current parent, or set to `null` to make the
+    /// group a root group
+    #[serde(default, deserialize_with = "super::present")]
+    #[schema(value_type = Option<Uuid>, nullable)]
+    pub parent_id: Option<Option<Uuid>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MoveGroupRequest {
+    /// The new parent, or `null` to make the group a root group
+    #[serde(default)]
     pub parent_id: Option<Uuid>,
 }
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GroupTreeQuery {
+    /// Levels to list below the top; 0 lists the top groups alone
+    pub max_depth: Option<u32>,
+    /// Count inactive staff in `member_count` too
+    #[serde(default)]
+    pub include_inactive: bool,
+}
+
+/// Dates to resolve members for. A member is listed when their membership is
+/// in effect on at least one day of the range.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ResolvedMembersQuery {
+    /// A single day; today when no dates are given
+    pub as_of: Option<NaiveDate>,
+    /// First day of a range, used with `to`
+    pub from: Option<NaiveDate>,
+    /// Last day of a range, inclusive
+    pub to: Option<NaiveDate>,
+}
+
+impl ResolvedMembersQuery {
+    /// The first and last day asked for
+    pub fn period(&self, today: NaiveDate) -> Result<(NaiveDate, NaiveDate), String> {
+        match (self.as_of, self.from, self.to) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                Err("Give either as_of or from and to, not both".to_string())
+            }
+            (Some(day), None, None) => Ok((day, day)),
+            (None, Some(from), Some(to)) if from > to => {
+                Err(format!("from {} is after to {}", from, to))
+            }
+            (None, Some(from), Some(to)) => Ok((from, to)),
+            (None, Some(_), None) | (None, None, Some(_)) => {
+                Err("from and to must be given together".to_string())
+            }
+            (None, None, None) => Ok((today, today)),
+        }
+    }
+}
@@ -1,8 +1,53 @@
+use chrono::NaiveDate;
 use serde::Deserialize;
-use utoipa::ToSchema;
+use shared::MembershipRole;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct AddMemberRequest {
     pub staff_id: Uuid,
+    /// MEMBER when omitted
+    #[serde(default)]
+    pub role: MembershipRole,
+    /// First day of the membership, inclusive; open when omitted
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    /// Last day of the membership, inclusive; open when omitted
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+}
+
+/// Replaces the role and dates of a membership. An omitted role is MEMBER,
+/// and an omitted date leaves that end open.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMembershipRequest {
+    #[serde(default)]
+    pub role: MembershipRole,
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+}
+
+/// Picks one of the memberships a staff member holds in a group
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MembershipQuery {
+    /// The membership to change; needed when the staff member has left and
+    /// rejoined the group, so holds several
+    pub membership_id: Option<Uuid>,
+}
+
+/// Check that a membership does not end before it starts
+pub fn check_validity(
+    valid_from: Option<NaiveDate>,
+    valid_to: Option<NaiveDate>,
+) -> Result<(), String> {
+    match (valid_from, valid_to) {
+        (Some(from), Some(to)) if from > to => {
+            Err(format!("valid_from {} is after valid_to {}", from, to))
+        }
+        _ => Ok(()),
+    }
 }
@@ -1,7 +1,31 @@
+use serde::{Deserialize, Deserializer};
+
+pub mod archive_request;
+pub mod batch_request;
 pub mod group_request;
 pub mod membership_request;
+pub mod organisation_request;
 pub mod staff_request;
 
-pub use group_request::{CreateGroupRequest, UpdateGroupRequest};
-pub use membership_request::AddMemberRequest;
-pub use staff_request::{CreateStaffRequest, UpdateStaffRequest};
+pub use archive_request::ArchiveQuery;
+pub use batch_request::{BatchGroupRow, BatchImportQuery, BatchMembershipRow};
+pub use group_request::{
+    CreateGroupRequest, GroupTreeQuery, MoveGroupRequest, ResolvedMembersQuery, UpdateGroupRequest,
+};
+pub use membership_request::{
+    check_validity, AddMemberRequest, MembershipQuery, UpdateMembershipRequest,
+};
+pub use organisation_request::{DocumentFormat, OrganisationExportQuery, OrganisationImportQuery};
+pub use staff_request::{
+    CreateStaffRequest, SortOrder, StaffCursor, StaffFilter, StaffSort, StaffSortField,
+    UpdateStaffRequest,
+};
+
+/// Tell a field set to `null` (`Some(None)`) apart from one left out (`None`)
+fn present<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -1,6 +1,9 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use shared::StaffStatus;
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::domain::entities::Staff;
 
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateStaffRequest {
@@ -9,6 +12,9 @@ pub struct CreateStaffRequest {
     pub position: String,
     #[serde(default)]
     pub status: Option<StaffStatus>,
+    /// Employee id in the HR system
+    #[serde(default)]
+    pub external_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -17,4 +23,121 @@ pub struct UpdateStaffRequest {
     pub email: Option<String>,
     pub position: Option<String>,
     pub status: Option<StaffStatus>,
+    /// Leave out to keep the current HR id, or set to `null` to unlink the
+    /// staff member from the HR system
+    #[serde(default, deserialize_with = "super::present")]
+    #[schema(value_type = Option<String>, nullable)]
+    pub external_id: Option<Option<String>>,
+}
+
+/// Filters for listing staff. All filters are optional and combined with AND.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StaffFilter {
+    /// Only staff in this status
+    pub status: Option<StaffStatus>,
+    /// Only staff with this position, ignoring case
+    pub position: Option<String>,
+    /// Only members of this group
+    pub group_id: Option<Uuid>,
+    /// With `group_id`, also members of its subgroups
+    #[serde(default)]
+    pub include_subgroups: bool,
+    /// Only staff whose name or email contains this text, ignoring case
+    pub q: Option<String>,
+    /// Also return soft-deleted staff
+    #[serde(default)]
+    pub include_deleted: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StaffSortField {
+    Name,
+    Email,
+    Position,
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl StaffSortField {
+    pub fn column(self) -> &'static str {
+        match self {
+            Self::Name => "name",
+            Self::Email => "email",
+            Self::Position => "position",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+
+    /// Newest first for timestamps, alphabetical otherwise
+    pub fn default_order(self) -> SortOrder {
+        match self {
+            Self::CreatedAt | Self::UpdatedAt => SortOrder::Desc,
+            _ => SortOrder::Asc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Order of a staff listing, and where to continue it from
+#[derive(Debug, Clone, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StaffSort {
+    /// Field to sort by, `created_at` by default. Ties are broken by id.
+    #[serde(default)]
+    pub sort: StaffSortField,
+    /// `asc` or `desc`; newest first for timestamps and A to Z otherwise by
+    /// default
+    pub order: Option<SortOrder>,
+    /// `next_cursor` of the previous page. Continues the listing after that
+    /// page instead of using `page`, so rows added or removed meanwhile do not
+    /// shift it. Use the same filters and sort.
+    pub cursor: Option<String>,
+}
+
+impl StaffSort {
+    pub fn order(&self) -> SortOrder {
+        self.order.unwrap_or_else(|| self.sort.default_order())
+    }
+}
+
+/// Position in a staff listing: the sort value and id of the last row seen
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StaffCursor {
+    pub value: String,
+    pub id: Uuid,
+}
+
+impl StaffCursor {
+    pub fn new(staff: &Staff, sort: StaffSortField) -> Self {
+        let value = match sort {
+            StaffSortField::Name => staff.name.clone(),
+            StaffSortField::Email => staff.email.clone(),
+            StaffSortField::Position => staff.position.clone(),
+            StaffSortField::CreatedAt => staff.created_at.to_rfc3339(),
+            StaffSortField::UpdatedAt => staff.updated_at.to_rfc3339(),
+        };
+        Self {
+            value,
+            id: staff.id,
+        }
+    }
+
+    /// Opaque form handed to clients
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).expect("a cursor serializes"))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        serde_json::from_slice(&hex::decode(cursor).ok()?).ok()
+    }
 }
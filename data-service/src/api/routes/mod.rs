@@ -22,25 +22,40 @@ use crate::api::{handlers, state::AppState};
         handlers::staff_handlers::list_staff,
         handlers::staff_handlers::update_staff,
         handlers::staff_handlers::delete_staff,
+        handlers::staff_handlers::restore_staff,
         // Group endpoints
         handlers::group_handlers::create_group,
         handlers::group_handlers::get_group_by_id,
         handlers::group_handlers::list_groups,
         handlers::group_handlers::update_group,
+        handlers::group_handlers::move_group,
         handlers::group_handlers::delete_group,
+        handlers::group_handlers::restore_group,
         handlers::group_handlers::get_resolved_members,
+        handlers::group_handlers::get_group_ancestors,
+        handlers::group_handlers::get_group_tree,
+        handlers::group_handlers::get_group_forest,
         // Membership endpoints
         handlers::membership_handlers::add_member,
+        handlers::membership_handlers::update_member,
         handlers::membership_handlers::remove_member,
         handlers::membership_handlers::get_group_members,
         // Batch import endpoints
         handlers::batch_handlers::batch_import_staff,
+        handlers::batch_handlers::sync_staff,
         handlers::batch_handlers::batch_import_groups,
         handlers::batch_handlers::batch_import_memberships,
+        // Organisation document endpoints
+        handlers::organisation_handlers::export_organisation,
+        handlers::organisation_handlers::import_organisation,
+        // Admin endpoints
+        handlers::admin_handlers::purge_staff,
+        handlers::admin_handlers::purge_group,
     ),
     components(schemas(
         // Shared types
         shared::StaffStatus,
+        shared::MembershipRole,
         shared::PaginationParams,
         // ApiResponse variants
         shared::ApiResponse<crate::presentation::StaffSerializer>,
@@ -49,31 +64,64 @@ use crate::api::{handlers, state::AppState};
         shared::ApiResponse<Vec<crate::presentation::GroupSerializer>>,
         shared::ApiResponse<crate::presentation::MembershipSerializer>,
         shared::ApiResponse<Vec<crate::presentation::ResolvedGroupSerializer>>,
+        shared::ApiResponse<crate::presentation::BreadcrumbSerializer>,
+        shared::ApiResponse<crate::presentation::GroupTreeNodeSerializer>,
+        shared::ApiResponse<Vec<crate::presentation::GroupTreeNodeSerializer>>,
         shared::ApiResponse<crate::api::handlers::batch_handlers::BatchImportSerializer>,
+        shared::ApiResponse<crate::api::handlers::batch_handlers::StaffSyncSerializer>,
+        shared::ApiResponse<crate::api::handlers::organisation_handlers::OrganisationImportSerializer>,
+        shared::ApiErrorResponse<crate::api::handlers::batch_handlers::BatchImportSerializer>,
+        shared::ApiErrorResponse<crate::api::handlers::batch_handlers::StaffSyncSerializer>,
+        shared::ApiErrorResponse<crate::api::handlers::organisation_handlers::OrganisationImportSerializer>,
         // Staff schemas
         crate::domain::entities::Staff,
         crate::presentation::StaffSerializer,
         crate::api::requests::CreateStaffRequest,
         crate::api::requests::UpdateStaffRequest,
+        crate::api::requests::StaffSortField,
+        crate::api::requests::SortOrder,
         // Group schemas
         crate::domain::entities::StaffGroup,
         crate::presentation::GroupSerializer,
         crate::api::requests::CreateGroupRequest,
         crate::api::requests::UpdateGroupRequest,
+        crate::api::requests::MoveGroupRequest,
         // Membership schemas
         crate::domain::entities::GroupMembership,
         crate::presentation::MembershipSerializer,
         crate::api::requests::AddMemberRequest,
+        crate::api::requests::UpdateMembershipRequest,
         // Resolved group schemas
         crate::presentation::ResolvedGroupSerializer,
+        crate::presentation::ResolvedMemberSerializer,
+        // Group tree schemas
+        crate::presentation::BreadcrumbSerializer,
+        crate::presentation::BreadcrumbItemSerializer,
+        crate::presentation::GroupTreeNodeSerializer,
         // Batch import schemas
         crate::api::handlers::batch_handlers::BatchImportSerializer,
+        crate::api::handlers::batch_handlers::BatchRowError,
+        crate::api::handlers::batch_handlers::StaffSyncSerializer,
+        crate::api::handlers::batch_handlers::StaffChange,
+        crate::api::requests::BatchGroupRow,
+        crate::api::requests::BatchMembershipRow,
+        // Organisation document schemas
+        crate::presentation::OrganisationDocument,
+        crate::presentation::OrganisationStaff,
+        crate::presentation::OrganisationGroup,
+        crate::presentation::OrganisationMembership,
+        crate::api::requests::DocumentFormat,
+        crate::api::handlers::organisation_handlers::OrganisationImportSerializer,
+        crate::api::handlers::organisation_handlers::ImportCounts,
+        crate::api::handlers::organisation_handlers::DocumentError,
     )),
     tags(
         (name = "staff", description = "Staff management endpoints"),
         (name = "groups", description = "Group management endpoints"),
         (name = "memberships", description = "Group membership management endpoints"),
-        (name = "batch", description = "Batch import endpoints from sample-data files")
+        (name = "batch", description = "Batch import endpoints for uploaded JSON or CSV payloads"),
+        (name = "organisation", description = "Whole-organisation export and import as one versioned document"),
+        (name = "admin", description = "Permanent deletion of soft-deleted records")
     )
 )]
 struct ApiDoc;
@@ -84,23 +132,47 @@ pub fn create_router(app_state: AppState) -> Router {
         .route("/staff", get(handlers::staff_handlers::list_staff))
         .route("/staff/:id", get(handlers::staff_handlers::get_staff_by_id))
         .route("/staff/:id", put(handlers::staff_handlers::update_staff))
-        .route("/staff/:id", delete(handlers::staff_handlers::delete_staff));
+        .route("/staff/:id", delete(handlers::staff_handlers::delete_staff))
+        .route(
+            "/staff/:id/restore",
+            post(handlers::staff_handlers::restore_staff),
+        );
 
     let group_routes = Router::new()
         .route("/groups", post(handlers::group_handlers::create_group))
         .route("/groups", get(handlers::group_handlers::list_groups))
+        .route(
+            "/groups/tree",
+            get(handlers::group_handlers::get_group_forest),
+        )
         .route(
             "/groups/:id",
             get(handlers::group_handlers::get_group_by_id),
         )
         .route("/groups/:id", put(handlers::group_handlers::update_group))
+        .route(
+            "/groups/:id/move",
+            post(handlers::group_handlers::move_group),
+        )
         .route(
             "/groups/:id",
             delete(handlers::group_handlers::delete_group),
         )
+        .route(
+            "/groups/:id/restore",
+            post(handlers::group_handlers::restore_group),
+        )
         .route(
             "/groups/:id/resolved-members",
             get(handlers::group_handlers::get_resolved_members),
+        )
+        .route(
+            "/groups/:id/ancestors",
+            get(handlers::group_handlers::get_group_ancestors),
+        )
+        .route(
+            "/groups/:id/tree",
+            get(handlers::group_handlers::get_group_tree),
         );
 
     let membership_routes = Router::new()
@@ -114,7 +186,8 @@ pub fn create_router(app_state: AppState) -> Router {
         )
         .route(
             "/groups/:group_id/members/:staff_id",
-            delete(handlers::membership_handlers::remove_member),
+            put(handlers::membership_handlers::update_member)
+                .delete(handlers::membership_handlers::remove_member),
         );
 
     let batch_routes = Router::new()
@@ -122,6 +195,10 @@ pub fn create_router(app_state: AppState) -> Router {
             "/batch/staff",
             post(handlers::batch_handlers::batch_import_staff),
         )
+        .route(
+            "/batch/staff/sync",
+            post(handlers::batch_handlers::sync_staff),
+        )
         .route(
             "/batch/groups",
             post(handlers::batch_handlers::batch_import_groups),
@@ -131,12 +208,34 @@ pub fn create_router(app_state: AppState) -> Router {
             post(handlers::batch_handlers::batch_import_memberships),
         );
 
+    let organisation_routes = Router::new()
+        .route(
+            "/organisation/export",
+            get(handlers::organisation_handlers::export_organisation),
+        )
+        .route(
+            "/organisation/import",
+            post(handlers::organisation_handlers::import_organisation),
+        );
+
+    let admin_routes = Router::new()
+        .route(
+            "/admin/staff/:id",
+            delete(handlers::admin_handlers::purge_staff),
+        )
+        .route(
+            "/admin/groups/:id",
+            delete(handlers::admin_handlers::purge_group),
+        );
+
     let api_router = Router::new()
         .route("/health", get(handlers::health_check))
         .merge(staff_routes)
         .merge(group_routes)
         .merge(membership_routes)
-        .merge(batch_routes);
+        .merge(batch_routes)
+        .merge(organisation_routes)
+        .merge(admin_routes);
 
     Router::new()
         .nest("/api/v1", api_router)
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
-use crate::domain::repositories::{GroupRepository, MembershipRepository, StaffRepository};
+use crate::domain::repositories::{
+    GroupRepository, MembershipRepository, StaffRepository, UnitOfWork,
+};
 use crate::infrastructure::redis::RedisPool;
 
 #[derive(Clone)]
@@ -8,6 +10,7 @@ pub struct AppState {
     pub staff_repo: Arc<dyn StaffRepository>,
     pub group_repo: Arc<dyn GroupRepository>,
     pub membership_repo: Arc<dyn MembershipRepository>,
+    pub unit_of_work: Arc<dyn UnitOfWork>,
     pub redis_pool: RedisPool,
 }
 
@@ -16,12 +19,14 @@ impl AppState {
         staff_repo: Arc<dyn StaffRepository>,
         group_repo: Arc<dyn GroupRepository>,
         membership_repo: Arc<dyn MembershipRepository>,
+        unit_of_work: Arc<dyn UnitOfWork>,
         redis_pool: RedisPool,
     ) -> Self {
         Self {
             staff_repo,
             group_repo,
             membership_repo,
+            unit_of_work,
             redis_pool,
         }
     }
@@ -12,6 +12,8 @@ pub struct StaffGroup {
     pub parent_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the group is deleted; it can still be restored
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Identifiable for StaffGroup {
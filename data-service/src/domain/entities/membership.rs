@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use shared::MembershipRole;
 use sqlx::FromRow;
 use utoipa::ToSchema;
 use uuid::Uuid;
@@ -10,4 +11,9 @@ pub struct GroupMembership {
     pub staff_id: Uuid,
     pub group_id: Uuid,
     pub created_at: DateTime<Utc>,
+    pub role: MembershipRole,
+    /// First day of the membership; it has always applied when `None`
+    pub valid_from: Option<NaiveDate>,
+    /// Last day of the membership; it never ends when `None`
+    pub valid_to: Option<NaiveDate>,
 }
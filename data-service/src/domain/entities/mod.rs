@@ -6,7 +6,41 @@ pub use group::StaffGroup;
 pub use membership::GroupMembership;
 pub use staff::Staff;
 
+use chrono::NaiveDate;
+use shared::MembershipRole;
+use uuid::Uuid;
+
 pub struct GroupWithMembers {
     pub group: StaffGroup,
-    pub members: Vec<Staff>,
+    /// Groups from the resolved group down to this one, both included
+    pub path: Vec<Uuid>,
+    pub members: Vec<GroupMember>,
+}
+
+/// A staff member with their role in one group and the days they belong to it
+pub struct GroupMember {
+    pub staff: Staff,
+    pub role: MembershipRole,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+}
+
+/// A group in a tree listing, with counts for drawing it collapsed
+pub struct GroupNode {
+    pub group: StaffGroup,
+    /// Levels below the top of the listing
+    pub depth: i32,
+    /// Direct members, not counting subgroups
+    pub member_count: i64,
+    /// Direct subgroups, including any cut off by a depth limit
+    pub child_count: i64,
+}
+
+/// One page of a staff listing
+pub struct StaffPage {
+    pub staff: Vec<Staff>,
+    /// Staff matching the filters, on any page
+    pub total: u64,
+    /// Cursor to continue after this page, if any staff are left
+    pub next_cursor: Option<String>,
 }
@@ -10,10 +10,14 @@ pub struct Staff {
     pub id: Uuid,
     pub name: String,
     pub email: String,
+    /// Employee id in the HR system
+    pub external_id: Option<String>,
     pub position: String,
     pub status: StaffStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the staff member is deleted; they can still be restored
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Identifiable for Staff {
@@ -0,0 +1,367 @@
+//! Groups addressed by path, e.g. `Engineering Department/Backend Team`.
+//!
+//! Group names are not unique, but the names along a path are, as long as no
+//! two groups with the same parent share a name. A `/` or `\` inside a name
+//! is escaped with a backslash.
+
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::StaffGroup;
+
+pub const SEPARATOR: char = '/';
+const ESCAPE: char = '\\';
+
+/// Join group names, root first, into a path
+pub fn join_path<S: AsRef<str>>(names: &[S]) -> String {
+    names
+        .iter()
+        .map(|name| {
+            let mut escaped = String::with_capacity(name.as_ref().len());
+            for c in name.as_ref().chars() {
+                if c == SEPARATOR || c == ESCAPE {
+                    escaped.push(ESCAPE);
+                }
+                escaped.push(c);
+            }
+            escaped
+        })
+        .collect::<Vec<_>>()
+        .join(&SEPARATOR.to_string())
+}
+
+/// Split a path into group names, root first
+pub fn split_path(path: &str) -> Result<Vec<String>, String> {
+    let mut names = vec![String::new()];
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ESCAPE => match chars.next() {
+                Some(escaped) => names.last_mut().unwrap().push(escaped),
+                None => return Err(format!("Path '{}' ends with an escape", path)),
+            },
+            SEPARATOR => names.push(String::new()),
+            _ => names.last_mut().unwrap().push(c),
+        }
+    }
+
+    if names.iter().any(|name| name.trim().is_empty()) {
+        return Err(format!("Path '{}' has an empty group name", path));
+    }
+    Ok(names)
+}
+
+/// Every group, looked up by id, path or (when unambiguous) name
+#[derive(Debug, Default)]
+pub struct GroupTree {
+    groups: HashMap<Uuid, StaffGroup>,
+    children: HashMap<Option<Uuid>, Vec<Uuid>>,
+}
+
+impl GroupTree {
+    pub fn new(groups: Vec<StaffGroup>) -> Self {
+        let mut tree = Self::default();
+        for group in groups {
+            tree.insert(group);
+        }
+        tree
+    }
+
+    /// Add a group, or move it when it is already known
+    pub fn insert(&mut self, group: StaffGroup) {
+        if let Some(previous) = self.groups.get(&group.id) {
+            if let Some(siblings) = self.children.get_mut(&previous.parent_id) {
+                siblings.retain(|id| *id != group.id);
+            }
+        }
+        self.children
+            .entry(group.parent_id)
+            .or_default()
+            .push(group.id);
+        self.groups.insert(group.id, group);
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &StaffGroup> {
+        self.groups.values()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&StaffGroup> {
+        self.groups.get(&id)
+    }
+
+    /// The group's ancestors, its parent first
+    pub fn ancestors(&self, id: Uuid) -> Vec<&StaffGroup> {
+        let mut ancestors: Vec<&StaffGroup> = Vec::new();
+        let mut current = self.groups.get(&id).and_then(|group| group.parent_id);
+        while let Some(group) = current.and_then(|parent| self.groups.get(&parent)) {
+            // A corrupt hierarchy must not loop forever
+            if group.id == id || ancestors.iter().any(|a| a.id == group.id) {
+                break;
+            }
+            ancestors.push(group);
+            current = group.parent_id;
+        }
+        ancestors
+    }
+
+    /// Path from the root down to the group
+    pub fn path_of(&self, id: Uuid) -> Option<String> {
+        let group = self.groups.get(&id)?;
+        let mut names: Vec<&str> = self
+            .ancestors(id)
+            .iter()
+            .map(|ancestor| ancestor.name.as_str())
+            .collect();
+        names.reverse();
+        names.push(group.name.as_str());
+        Some(join_path(&names))
+    }
+
+    /// Check that a new group may be named `name` and created under `parent`
+    /// (as a root group when `None`). The parent must exist and no sibling
+    /// may share the name.
+    pub fn check_new_group(&self, name: &str, parent: Option<Uuid>) -> Result<(), String> {
+        if let Some(parent_id) = parent.filter(|p| !self.groups.contains_key(p)) {
+            return Err(format!("Parent group with id {} not found", parent_id));
+        }
+        self.check_sibling_names(None, name, parent)
+    }
+
+    /// Check that the group may be named `name` and placed under `parent`
+    /// (made a root group when `None`). The parent must exist and must not be
+    /// the group or one of its descendants, and no sibling may share the name,
+    /// so that every path stays unambiguous.
+    pub fn check_placement(
+        &self,
+        id: Uuid,
+        name: &str,
+        parent: Option<Uuid>,
+    ) -> Result<(), String> {
+        if let Some(parent_id) = parent {
+            if parent_id == id {
+                return Err("A group cannot be its own parent".to_string());
+            }
+            if !self.groups.contains_key(&parent_id) {
+                return Err(format!("Parent group with id {} not found", parent_id));
+            }
+            if self.ancestors(parent_id).iter().any(|a| a.id == id) {
+                return Err(format!(
+                    "Group {} is a descendant of {} and cannot become its parent",
+                    parent_id, id
+                ));
+            }
+        }
+
+        self.check_sibling_names(Some(id), name, parent)
+    }
+
+    /// Names are compared ignoring case, like the unique index behind them
+    fn check_sibling_names(
+        &self,
+        id: Option<Uuid>,
+        name: &str,
+        parent: Option<Uuid>,
+    ) -> Result<(), String> {
+        let name_key = name.to_lowercase();
+        let clash = self
+            .children
+            .get(&parent)
+            .into_iter()
+            .flatten()
+            .filter_map(|sibling| self.groups.get(sibling))
+            .any(|sibling| Some(sibling.id) != id && sibling.name.to_lowercase() == name_key);
+        if clash {
+            return Err(match parent.and_then(|p| self.path_of(p)) {
+                Some(path) => format!("'{}' already has a group named '{}'", path, name),
+                None => format!("There is already a root group named '{}'", name),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The child of `parent` (a root group when `None`) with this name
+    pub fn child(&self, parent: Option<Uuid>, name: &str) -> Result<Option<&StaffGroup>, String> {
+        let mut matches = self
+            .children
+            .get(&parent)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.groups.get(id))
+            .filter(|group| group.name == name);
+
+        match (matches.next(), matches.next()) {
+            (Some(_), Some(_)) => Err(format!(
+                "More than one group named '{}' has the same parent",
+                name
+            )),
+            (found, _) => Ok(found),
+        }
+    }
+
+    pub fn find_by_path(&self, path: &str) -> Result<Option<&StaffGroup>, String> {
+        let mut current: Option<&StaffGroup> = None;
+        for name in split_path(path)? {
+            match self.child(current.map(|group| group.id), &name)? {
+                Some(group) => current = Some(group),
+                None => return Ok(None),
+            }
+        }
+        Ok(current)
+    }
+
+    /// Find a group by name alone, which only works while the name is unique
+    pub fn find_by_name(&self, name: &str) -> Result<Option<&StaffGroup>, String> {
+        let mut matches = self.groups.values().filter(|group| group.name == name);
+        match (matches.next(), matches.next()) {
+            (Some(_), Some(_)) => Err(format!(
+                "More than one group is named '{}'; refer to it by path",
+                name
+            )),
+            (found, _) => Ok(found),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn group(name: &str, parent_id: Option<Uuid>) -> StaffGroup {
+        StaffGroup {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            parent_id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_paths_round_trip_escaped_names() {
+        let names = ["R&D", "Front/Back end", r"C:\Ops"];
+        let path = join_path(&names);
+        assert_eq!(path, r"R&D/Front\/Back end/C:\\Ops");
+        assert_eq!(split_path(&path).unwrap(), names);
+
+        assert!(split_path("Engineering//Backend").is_err());
+        assert!(split_path(r"Engineering\").is_err());
+    }
+
+    #[test]
+    fn test_same_name_under_different_parents_is_found_by_path() {
+        let engineering = group("Engineering", None);
+        let sales = group("Sales", None);
+        let eng_support = group("Support", Some(engineering.id));
+        let sales_support = group("Support", Some(sales.id));
+        let tree = GroupTree::new(vec![
+            engineering.clone(),
+            sales.clone(),
+            eng_support.clone(),
+            sales_support.clone(),
+        ]);
+
+        assert_eq!(
+            tree.path_of(sales_support.id).as_deref(),
+            Some("Sales/Support")
+        );
+        assert_eq!(
+            tree.find_by_path("Engineering/Support")
+                .unwrap()
+                .unwrap()
+                .id,
+            eng_support.id
+        );
+        assert!(tree.find_by_path("Marketing/Support").unwrap().is_none());
+        assert!(tree.find_by_name("Support").is_err());
+        assert_eq!(tree.find_by_name("Sales").unwrap().unwrap().id, sales.id);
+    }
+
+    #[test]
+    fn test_check_placement_rejects_cycles_and_name_clashes() {
+        let engineering = group("Engineering", None);
+        let backend = group("Backend", Some(engineering.id));
+        let api = group("API", Some(backend.id));
+        let sales = group("Sales", None);
+        let sales_api = group("API", Some(sales.id));
+        let tree = GroupTree::new(vec![
+            engineering.clone(),
+            backend.clone(),
+            api.clone(),
+            sales.clone(),
+            sales_api.clone(),
+        ]);
+
+        assert!(tree
+            .check_placement(engineering.id, "Engineering", Some(engineering.id))
+            .is_err());
+        assert!(tree
+            .check_placement(engineering.id, "Engineering", Some(api.id))
+            .unwrap_err()
+            .contains("descendant"));
+        assert!(tree
+            .check_placement(backend.id, "Backend", Some(Uuid::new_v4()))
+            .is_err());
+        assert_eq!(
+            tree.check_placement(api.id, "API", Some(sales.id)),
+            Err("'Sales' already has a group named 'API'".to_string())
+        );
+        assert!(tree
+            .check_placement(api.id, "API", Some(engineering.id))
+            .is_ok());
+        assert!(tree.check_placement(backend.id, "Backend", None).is_ok());
+        assert!(tree.check_placement(api.id, "Sales", None).is_err());
+        assert!(tree.check_placement(api.id, "sales", None).is_err());
+        assert!(tree
+            .check_placement(backend.id, "backend", Some(engineering.id))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_check_new_group_rejects_sibling_names_and_missing_parents() {
+        let engineering = group("Engineering", None);
+        let backend = group("Backend", Some(engineering.id));
+        let tree = GroupTree::new(vec![engineering.clone(), backend.clone()]);
+
+        assert_eq!(
+            tree.check_new_group("backend", Some(engineering.id)),
+            Err("'Engineering' already has a group named 'backend'".to_string())
+        );
+        assert_eq!(
+            tree.check_new_group("Engineering", None),
+            Err("There is already a root group named 'Engineering'".to_string())
+        );
+        assert!(tree.check_new_group("Backend", Some(backend.id)).is_ok());
+        assert!(tree.check_new_group("Backend", None).is_ok());
+        assert!(tree.check_new_group("API", Some(Uuid::new_v4())).is_err());
+    }
+
+    #[test]
+    fn test_ancestors_stop_at_a_corrupt_cycle() {
+        let mut a = group("A", None);
+        let b = group("B", Some(a.id));
+        a.parent_id = Some(b.id);
+        let tree = GroupTree::new(vec![a.clone(), b.clone()]);
+
+        let ancestors: Vec<Uuid> = tree.ancestors(a.id).iter().map(|g| g.id).collect();
+        assert_eq!(ancestors, vec![b.id]);
+    }
+
+    #[test]
+    fn test_insert_moves_a_known_group() {
+        let engineering = group("Engineering", None);
+        let mut backend = group("Backend", None);
+        let mut tree = GroupTree::new(vec![engineering.clone(), backend.clone()]);
+
+        backend.parent_id = Some(engineering.id);
+        tree.insert(backend.clone());
+
+        assert!(tree.child(None, "Backend").unwrap().is_none());
+        assert_eq!(
+            tree.path_of(backend.id).as_deref(),
+            Some("Engineering/Backend")
+        );
+    }
+}
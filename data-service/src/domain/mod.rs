@@ -1,2 +1,3 @@
 pub mod entities;
+pub mod group_tree;
 pub mod repositories;
@@ -1,34 +1,72 @@
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use shared::{DomainResult, PaginationParams};
 use uuid::Uuid;
 
 use crate::api::requests::{CreateGroupRequest, UpdateGroupRequest};
-use crate::domain::entities::{GroupWithMembers, StaffGroup};
+use crate::domain::entities::{GroupNode, GroupWithMembers, StaffGroup};
 
 #[async_trait]
 pub trait GroupRepository: Send + Sync {
-    /// Create a new staff group
+    /// Create a new staff group. The parent must exist and not be deleted.
     async fn create(&self, request: CreateGroupRequest) -> DomainResult<StaffGroup>;
 
-    /// Find group by ID
+    /// Find group by ID, unless deleted
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<StaffGroup>>;
 
-    /// List all groups with pagination
-    async fn list(&self, params: PaginationParams) -> DomainResult<(Vec<StaffGroup>, u64)>;
+    /// Find group by ID, deleted or not
+    async fn find_by_id_including_deleted(&self, id: Uuid) -> DomainResult<Option<StaffGroup>>;
+
+    /// List groups with pagination, leaving out deleted groups unless asked
+    async fn list(
+        &self,
+        params: PaginationParams,
+        include_deleted: bool,
+    ) -> DomainResult<(Vec<StaffGroup>, u64)>;
 
     /// Update group by ID
     async fn update(&self, id: Uuid, request: UpdateGroupRequest) -> DomainResult<StaffGroup>;
 
-    /// Delete group by ID
+    /// Soft delete the group and its subgroups. Rows and memberships are
+    /// kept, so the group can be restored.
     async fn delete(&self, id: Uuid) -> DomainResult<()>;
 
+    /// Undo a soft delete, bringing back the subgroups deleted with the group.
+    /// The parent must not be deleted. Groups that are not deleted are
+    /// returned unchanged.
+    async fn restore(&self, id: Uuid) -> DomainResult<StaffGroup>;
+
+    /// Permanently delete a soft-deleted group, with its subgroups and
+    /// memberships
+    async fn purge(&self, id: Uuid) -> DomainResult<()>;
+
     /// Find group by name
+    #[allow(dead_code)]
     async fn find_by_name(&self, name: &str) -> DomainResult<Option<StaffGroup>>;
 
-    /// Get all members in a group hierarchy, grouped by subgroup.
+    /// Get all members in a group hierarchy, grouped by subgroup, whose
+    /// membership is in effect on any day from `from` to `to`, inclusive.
     /// Returns (groups_with_members, total_unique_active_members).
     async fn get_resolved_members(
         &self,
         group_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
     ) -> DomainResult<(Vec<GroupWithMembers>, u64)>;
+
+    /// The given groups and all of their ancestors, in no particular order
+    async fn find_ancestor_ids(&self, group_ids: &[Uuid]) -> DomainResult<Vec<Uuid>>;
+
+    /// Get the group and its ancestors, root first
+    async fn get_ancestors(&self, group_id: Uuid) -> DomainResult<Vec<StaffGroup>>;
+
+    /// Get the group and its descendants, or every root group and its
+    /// descendants when `root` is `None`, parents before children.
+    /// `max_depth` limits how many levels below the top are listed.
+    async fn get_subtree(
+        &self,
+        root: Option<Uuid>,
+        max_depth: Option<u32>,
+        include_inactive: bool,
+    ) -> DomainResult<Vec<GroupNode>>;
 }
@@ -2,13 +2,44 @@ use async_trait::async_trait;
 use shared::DomainResult;
 use uuid::Uuid;
 
+use crate::api::requests::{AddMemberRequest, UpdateMembershipRequest};
 use crate::domain::entities::GroupMembership;
 
 #[async_trait]
 pub trait MembershipRepository: Send + Sync {
-    /// Add staff to group
-    async fn add_member(&self, staff_id: Uuid, group_id: Uuid) -> DomainResult<GroupMembership>;
+    /// Add staff to group with a role, for the given dates when any. Fails
+    /// with `InvalidInput` when the period overlaps another membership of the
+    /// same staff and group.
+    async fn add_member(
+        &self,
+        group_id: Uuid,
+        request: AddMemberRequest,
+    ) -> DomainResult<GroupMembership>;
 
-    /// Remove staff from group
-    async fn remove_member(&self, staff_id: Uuid, group_id: Uuid) -> DomainResult<()>;
+    /// Memberships of a staff member in a group, one per period, earliest first
+    async fn find_memberships(
+        &self,
+        staff_id: Uuid,
+        group_id: Uuid,
+    ) -> DomainResult<Vec<GroupMembership>>;
+
+    /// Replace the role and dates of a membership. Fails with `InvalidInput`
+    /// when the new period overlaps another membership.
+    async fn update_member(
+        &self,
+        membership_id: Uuid,
+        request: UpdateMembershipRequest,
+    ) -> DomainResult<GroupMembership>;
+
+    /// Remove staff from group: only the given membership, or every one they
+    /// hold in the group when `membership_id` is `None`
+    async fn remove_member(
+        &self,
+        staff_id: Uuid,
+        group_id: Uuid,
+        membership_id: Option<Uuid>,
+    ) -> DomainResult<()>;
+
+    /// Groups the staff member belongs to directly
+    async fn find_group_ids_by_staff(&self, staff_id: Uuid) -> DomainResult<Vec<Uuid>>;
 }
@@ -1,7 +1,9 @@
 pub mod group_repository;
 pub mod membership_repository;
 pub mod staff_repository;
+pub mod unit_of_work;
 
 pub use group_repository::GroupRepository;
 pub use membership_repository::MembershipRepository;
 pub use staff_repository::StaffRepository;
+pub use unit_of_work::{Transaction, UnitOfWork};
@@ -1,39 +1,48 @@
 use async_trait::async_trait;
-use shared::{DomainResult, PaginationParams, StaffStatus};
+use shared::{DomainResult, PaginationParams};
 use uuid::Uuid;
 
-use crate::api::requests::{CreateStaffRequest, UpdateStaffRequest};
-use crate::domain::entities::Staff;
+use crate::api::requests::{CreateStaffRequest, StaffFilter, StaffSort, UpdateStaffRequest};
+use crate::domain::entities::{Staff, StaffPage};
 
 #[async_trait]
 pub trait StaffRepository: Send + Sync {
     /// Create a new staff member
     async fn create(&self, request: CreateStaffRequest) -> DomainResult<Staff>;
 
-    /// Find staff by ID
+    /// Find staff by ID, unless deleted
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Staff>>;
 
+    /// Find staff by ID, deleted or not
+    async fn find_by_id_including_deleted(&self, id: Uuid) -> DomainResult<Option<Staff>>;
+
     /// Find staff by email
     #[allow(dead_code)]
     async fn find_by_email(&self, email: &str) -> DomainResult<Option<Staff>>;
 
-    /// List all staff with pagination
-    async fn list(&self, params: PaginationParams) -> DomainResult<(Vec<Staff>, u64)>;
-
-    /// List staff by status
-    #[allow(dead_code)]
-    async fn list_by_status(
+    /// List staff matching the filter, one page at a time. A cursor in
+    /// `sort` takes the place of the page number; an invalid one is
+    /// `InvalidInput`.
+    async fn list(
         &self,
-        status: StaffStatus,
+        filter: &StaffFilter,
+        sort: &StaffSort,
         params: PaginationParams,
-    ) -> DomainResult<(Vec<Staff>, u64)>;
+    ) -> DomainResult<StaffPage>;
 
     /// Update staff by ID
     async fn update(&self, id: Uuid, request: UpdateStaffRequest) -> DomainResult<Staff>;
 
-    /// Delete staff by ID
+    /// Soft delete staff by ID. The row and its memberships are kept, so the
+    /// staff member can be restored.
     async fn delete(&self, id: Uuid) -> DomainResult<()>;
 
+    /// Undo a soft delete. Staff that are not deleted are returned unchanged.
+    async fn restore(&self, id: Uuid) -> DomainResult<Staff>;
+
+    /// Permanently delete soft-deleted staff, with their memberships
+    async fn purge(&self, id: Uuid) -> DomainResult<()>;
+
     /// Get staff by group ID
     async fn find_by_group_id(&self, group_id: Uuid) -> DomainResult<Vec<Staff>>;
 }
@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use shared::DomainResult;
+use uuid::Uuid;
+
+use crate::api::requests::{
+    AddMemberRequest, CreateGroupRequest, CreateStaffRequest, UpdateMembershipRequest,
+    UpdateStaffRequest,
+};
+use crate::domain::entities::{GroupMembership, Staff, StaffGroup};
+
+/// Starts transactions spanning several repositories
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    /// Begin a new transaction
+    async fn begin(&self) -> DomainResult<Box<dyn Transaction>>;
+}
+
+/// Writes made through a transaction only become visible on `commit`.
+/// Dropping it without committing rolls everything back.
+///
+/// A write that fails is undone on its own and leaves the transaction
+/// usable, so a batch can carry on past rejected rows.
+///
+/// Deleted staff and groups are invisible here: they are not listed or found,
+/// and cannot be updated or used as a parent.
+#[async_trait]
+pub trait Transaction: Send {
+    /// Create a new staff member
+    async fn create_staff(&mut self, request: &CreateStaffRequest) -> DomainResult<Staff>;
+
+    /// Update staff by ID
+    async fn update_staff(&mut self, id: Uuid, request: &UpdateStaffRequest)
+        -> DomainResult<Staff>;
+
+    /// List every staff member that is not deleted, active or not
+    async fn list_staff(&mut self) -> DomainResult<Vec<Staff>>;
+
+    /// Find staff by email, including staff created in this transaction
+    async fn find_staff_by_email(&mut self, email: &str) -> DomainResult<Option<Staff>>;
+
+    /// Create a new staff group under a parent that is not deleted
+    async fn create_group(&mut self, request: &CreateGroupRequest) -> DomainResult<StaffGroup>;
+
+    /// List every group that is not deleted, including groups created in this transaction
+    async fn list_groups(&mut self) -> DomainResult<Vec<StaffGroup>>;
+
+    /// Move a group under a new parent
+    async fn set_group_parent(&mut self, group_id: Uuid, parent_id: Uuid) -> DomainResult<()>;
+
+    /// Add staff to group with a role, for the given dates when any. Fails
+    /// with `InvalidInput` when the period overlaps another membership of the
+    /// same staff and group.
+    async fn add_member(
+        &mut self,
+        group_id: Uuid,
+        request: &AddMemberRequest,
+    ) -> DomainResult<GroupMembership>;
+
+    /// Replace the role and dates of a membership
+    async fn update_member(
+        &mut self,
+        membership_id: Uuid,
+        request: &UpdateMembershipRequest,
+    ) -> DomainResult<GroupMembership>;
+
+    /// List every membership of staff and groups that are not deleted
+    async fn list_memberships(&mut self) -> DomainResult<Vec<GroupMembership>>;
+
+    /// Commit all writes made through this transaction
+    async fn commit(self: Box<Self>) -> DomainResult<()>;
+}
@@ -1,7 +1,9 @@
 mod postgres_group_repository;
 mod postgres_membership_repository;
 mod postgres_staff_repository;
+mod postgres_unit_of_work;
 
 pub use postgres_group_repository::PostgresGroupRepository;
 pub use postgres_membership_repository::PostgresMembershipRepository;
 pub use postgres_staff_repository::PostgresStaffRepository;
+pub use postgres_unit_of_work::PostgresUnitOfWork;
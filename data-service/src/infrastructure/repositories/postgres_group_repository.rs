@@ -1,26 +1,108 @@
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use shared::{DomainError, DomainResult, PaginationParams};
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
 use crate::api::requests::{CreateGroupRequest, UpdateGroupRequest};
-use crate::domain::entities::{GroupWithMembers, Staff, StaffGroup};
+use crate::domain::entities::{GroupMember, GroupNode, GroupWithMembers, Staff, StaffGroup};
+use crate::domain::group_tree::GroupTree;
 use crate::domain::repositories::GroupRepository;
 
+/// Advisory lock key taken by every change to the hierarchy
+const HIERARCHY_LOCK_KEY: i64 = 0x5354_4146_4647_5250;
+
+/// Lock the hierarchy for the rest of the transaction.
+///
+/// Moves are serialised, so two concurrent moves that are each valid cannot
+/// together form a cycle.
+pub(crate) async fn lock_hierarchy(conn: &mut PgConnection) -> DomainResult<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(HIERARCHY_LOCK_KEY)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Lock the hierarchy and load the groups that are not deleted
+pub(crate) async fn load_hierarchy(conn: &mut PgConnection) -> DomainResult<GroupTree> {
+    lock_hierarchy(conn).await?;
+
+    let groups = sqlx::query_as::<_, StaffGroup>(
+        r#"
+        SELECT id, name, parent_id, created_at, updated_at, deleted_at
+        FROM staff_groups
+        WHERE deleted_at IS NULL
+        "#,
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+    Ok(GroupTree::new(groups))
+}
+
+/// Insert a group, unless its parent is missing or deleted. The caller must
+/// hold the hierarchy lock.
+pub(crate) async fn insert_group(
+    conn: &mut PgConnection,
+    request: &CreateGroupRequest,
+) -> Result<Option<StaffGroup>, sqlx::Error> {
+    sqlx::query_as::<_, StaffGroup>(
+        r#"
+        INSERT INTO staff_groups (name, parent_id)
+        SELECT $1, $2
+        WHERE $2::uuid IS NULL
+           OR EXISTS (SELECT 1 FROM staff_groups WHERE id = $2 AND deleted_at IS NULL)
+        RETURNING id, name, parent_id, created_at, updated_at, deleted_at
+        "#,
+    )
+    .bind(&request.name)
+    .bind(request.parent_id)
+    .fetch_optional(&mut *conn)
+    .await
+}
+
+pub(crate) fn parent_not_found(request: &CreateGroupRequest) -> DomainError {
+    DomainError::InvalidInput(format!(
+        "Parent group with id {} not found",
+        request.parent_id.unwrap_or_default()
+    ))
+}
+
 #[derive(sqlx::FromRow)]
 struct ResolvedMemberRow {
     group_id: Uuid,
     group_name: String,
     group_parent_id: Option<Uuid>,
+    group_path: Vec<Uuid>,
     group_created_at: chrono::DateTime<chrono::Utc>,
     group_updated_at: chrono::DateTime<chrono::Utc>,
     staff_id: Uuid,
     staff_name: String,
     staff_email: String,
+    staff_external_id: Option<String>,
     staff_position: String,
     staff_status: shared::StaffStatus,
     staff_created_at: chrono::DateTime<chrono::Utc>,
     staff_updated_at: chrono::DateTime<chrono::Utc>,
+    role: shared::MembershipRole,
+    valid_from: Option<NaiveDate>,
+    valid_to: Option<NaiveDate>,
+}
+
+#[derive(sqlx::FromRow)]
+struct GroupNodeRow {
+    id: Uuid,
+    name: String,
+    parent_id: Option<Uuid>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    updated_at: chrono::DateTime<chrono::Utc>,
+    depth: i32,
+    member_count: i64,
+    child_count: i64,
 }
 
 pub struct PostgresGroupRepository {
@@ -36,26 +118,47 @@ impl PostgresGroupRepository {
 #[async_trait]
 impl GroupRepository for PostgresGroupRepository {
     async fn create(&self, request: CreateGroupRequest) -> DomainResult<StaffGroup> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let tree = load_hierarchy(&mut tx).await?;
+        tree.check_new_group(&request.name, request.parent_id)
+            .map_err(DomainError::InvalidInput)?;
+        let group = insert_group(&mut tx, &request)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| parent_not_found(&request))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(group)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<StaffGroup>> {
         let group = sqlx::query_as::<_, StaffGroup>(
             r#"
-            INSERT INTO staff_groups (name, parent_id)
-            VALUES ($1, $2)
-            RETURNING id, name, parent_id, created_at, updated_at
+            SELECT id, name, parent_id, created_at, updated_at, deleted_at
+            FROM staff_groups
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
-        .bind(&request.name)
-        .bind(request.parent_id)
-        .fetch_one(&self.pool)
+        .bind(id)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
         Ok(group)
     }
 
-    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<StaffGroup>> {
+    async fn find_by_id_including_deleted(&self, id: Uuid) -> DomainResult<Option<StaffGroup>> {
         let group = sqlx::query_as::<_, StaffGroup>(
             r#"
-            SELECT id, name, parent_id, created_at, updated_at
+            SELECT id, name, parent_id, created_at, updated_at, deleted_at
             FROM staff_groups
             WHERE id = $1
             "#,
@@ -68,62 +171,106 @@ impl GroupRepository for PostgresGroupRepository {
         Ok(group)
     }
 
-    async fn list(&self, params: PaginationParams) -> DomainResult<(Vec<StaffGroup>, u64)> {
+    async fn list(
+        &self,
+        params: PaginationParams,
+        include_deleted: bool,
+    ) -> DomainResult<(Vec<StaffGroup>, u64)> {
         let offset = (params.page - 1) * params.page_size;
 
         let groups = sqlx::query_as::<_, StaffGroup>(
             r#"
-            SELECT id, name, parent_id, created_at, updated_at
+            SELECT id, name, parent_id, created_at, updated_at, deleted_at
             FROM staff_groups
+            WHERE $3 OR deleted_at IS NULL
             ORDER BY created_at DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(params.page_size as i64)
         .bind(offset as i64)
+        .bind(include_deleted)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM staff_groups")
-            .fetch_one(&self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM staff_groups WHERE $1 OR deleted_at IS NULL")
+                .bind(include_deleted)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
         Ok((groups, total.0 as u64))
     }
 
     async fn update(&self, id: Uuid, request: UpdateGroupRequest) -> DomainResult<StaffGroup> {
-        let current = self
-            .find_by_id(id)
-            .await?
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let tree = load_hierarchy(&mut tx).await?;
+        let current = tree
+            .get(id)
             .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
+        let name = request.name.unwrap_or_else(|| current.name.clone());
+        let parent_id = request.parent_id.unwrap_or(current.parent_id);
+        tree.check_placement(id, &name, parent_id)
+            .map_err(DomainError::InvalidInput)?;
 
         let group = sqlx::query_as::<_, StaffGroup>(
             r#"
             UPDATE staff_groups
             SET name = $1, parent_id = $2, updated_at = NOW()
             WHERE id = $3
-            RETURNING id, name, parent_id, created_at, updated_at
+            RETURNING id, name, parent_id, created_at, updated_at, deleted_at
             "#,
         )
-        .bind(request.name.unwrap_or(current.name))
-        .bind(request.parent_id.or(current.parent_id))
+        .bind(name)
+        .bind(parent_id)
         .bind(id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
         Ok(group)
     }
 
     async fn delete(&self, id: Uuid) -> DomainResult<()> {
-        let result = sqlx::query("DELETE FROM staff_groups WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
+        let mut tx = self
+            .pool
+            .begin()
             .await
             .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
+        // Subgroups go with the group, sharing its deletion time so they are
+        // restored with it
+        lock_hierarchy(&mut tx).await?;
+        let result = sqlx::query(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT id, ARRAY[id] AS path
+                FROM staff_groups WHERE id = $1 AND deleted_at IS NULL
+                UNION ALL
+                SELECT sg.id, t.path || sg.id FROM staff_groups sg
+                INNER JOIN subtree t ON sg.parent_id = t.id
+                WHERE sg.deleted_at IS NULL AND sg.id <> ALL(t.path)
+            )
+            UPDATE staff_groups SET deleted_at = NOW()
+            WHERE id IN (SELECT id FROM subtree)
+            "#,
+        )
+        .bind(id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
         if result.rows_affected() == 0 {
             return Err(DomainError::NotFound(format!(
                 "Group with id {} not found",
@@ -131,15 +278,106 @@ impl GroupRepository for PostgresGroupRepository {
             )));
         }
 
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn restore(&self, id: Uuid) -> DomainResult<StaffGroup> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let tree = load_hierarchy(&mut tx).await?;
+        let group = sqlx::query_as::<_, StaffGroup>(
+            r#"
+            SELECT id, name, parent_id, created_at, updated_at, deleted_at
+            FROM staff_groups
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?
+        .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
+
+        let Some(deleted_at) = group.deleted_at else {
+            return Ok(group);
+        };
+        if let Some(parent_id) = group.parent_id.filter(|p| tree.get(*p).is_none()) {
+            return Err(DomainError::InvalidInput(format!(
+                "Parent group with id {} is deleted; restore it first",
+                parent_id
+            )));
+        }
+        tree.check_placement(id, &group.name, group.parent_id)
+            .map_err(DomainError::InvalidInput)?;
+
+        // Bring back the subgroups deleted along with it, not those deleted
+        // on their own before
+        let restored = sqlx::query_as::<_, StaffGroup>(
+            r#"
+            WITH RECURSIVE subtree AS (
+                SELECT id, ARRAY[id] AS path FROM staff_groups WHERE id = $1
+                UNION ALL
+                SELECT sg.id, t.path || sg.id FROM staff_groups sg
+                INNER JOIN subtree t ON sg.parent_id = t.id
+                WHERE sg.deleted_at = $2 AND sg.id <> ALL(t.path)
+            )
+            UPDATE staff_groups SET deleted_at = NULL
+            WHERE id IN (SELECT id FROM subtree)
+            RETURNING id, name, parent_id, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(id)
+        .bind(deleted_at)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        restored
+            .into_iter()
+            .find(|g| g.id == id)
+            .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))
+    }
+
+    async fn purge(&self, id: Uuid) -> DomainResult<()> {
+        let group = self
+            .find_by_id_including_deleted(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
+        if group.deleted_at.is_none() {
+            return Err(DomainError::InvalidInput(format!(
+                "Group with id {} must be deleted before it is purged",
+                id
+            )));
+        }
+
+        // Subgroups and memberships cascade
+        sqlx::query("DELETE FROM staff_groups WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
         Ok(())
     }
 
     async fn find_by_name(&self, name: &str) -> DomainResult<Option<StaffGroup>> {
         let group = sqlx::query_as::<_, StaffGroup>(
             r#"
-            SELECT id, name, parent_id, created_at, updated_at
+            SELECT id, name, parent_id, created_at, updated_at, deleted_at
             FROM staff_groups
-            WHERE name = $1
+            WHERE name = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(name)
@@ -153,37 +391,50 @@ impl GroupRepository for PostgresGroupRepository {
     async fn get_resolved_members(
         &self,
         group_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
     ) -> DomainResult<(Vec<GroupWithMembers>, u64)> {
         let rows = sqlx::query_as::<_, ResolvedMemberRow>(
             r#"
             WITH RECURSIVE descendants AS (
-                SELECT id FROM staff_groups WHERE id = $1
+                SELECT id, ARRAY[id] AS path
+                FROM staff_groups WHERE id = $1 AND deleted_at IS NULL
                 UNION
-                SELECT sg.id FROM staff_groups sg
+                SELECT sg.id, d.path || sg.id FROM staff_groups sg
                 INNER JOIN descendants d ON sg.parent_id = d.id
+                WHERE sg.deleted_at IS NULL AND sg.id <> ALL(d.path)
             )
             SELECT
                 sg.id          AS group_id,
                 sg.name        AS group_name,
                 sg.parent_id   AS group_parent_id,
+                d.path         AS group_path,
                 sg.created_at  AS group_created_at,
                 sg.updated_at  AS group_updated_at,
                 s.id           AS staff_id,
                 s.name         AS staff_name,
                 s.email        AS staff_email,
+                s.external_id  AS staff_external_id,
                 s.position     AS staff_position,
                 s.status       AS staff_status,
                 s.created_at   AS staff_created_at,
-                s.updated_at   AS staff_updated_at
+                s.updated_at   AS staff_updated_at,
+                gm.role,
+                gm.valid_from,
+                gm.valid_to
             FROM descendants d
             JOIN staff_groups sg       ON sg.id = d.id
             JOIN group_memberships gm  ON gm.group_id = sg.id
             JOIN staff s               ON s.id = gm.staff_id
-            WHERE s.status = 'ACTIVE'
+            WHERE s.status = 'ACTIVE' AND s.deleted_at IS NULL
+              AND (gm.valid_from IS NULL OR gm.valid_from <= $3)
+              AND (gm.valid_to IS NULL OR gm.valid_to >= $2)
             ORDER BY sg.name, s.name
             "#,
         )
         .bind(group_id)
+        .bind(from)
+        .bind(to)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
@@ -203,15 +454,23 @@ impl GroupRepository for PostgresGroupRepository {
                 id: row.staff_id,
                 name: row.staff_name,
                 email: row.staff_email,
+                external_id: row.staff_external_id,
                 position: row.staff_position,
                 status: row.staff_status,
                 created_at: row.staff_created_at,
                 updated_at: row.staff_updated_at,
+                deleted_at: None,
+            };
+            let member = GroupMember {
+                staff,
+                role: row.role,
+                valid_from: row.valid_from,
+                valid_to: row.valid_to,
             };
 
             if current_group_id == Some(row.group_id) {
                 // Same group — push member to the last entry
-                result.last_mut().unwrap().members.push(staff);
+                result.last_mut().unwrap().members.push(member);
             } else {
                 current_group_id = Some(row.group_id);
                 let group = StaffGroup {
@@ -220,14 +479,125 @@ impl GroupRepository for PostgresGroupRepository {
                     parent_id: row.group_parent_id,
                     created_at: row.group_created_at,
                     updated_at: row.group_updated_at,
+                    deleted_at: None,
                 };
                 result.push(GroupWithMembers {
                     group,
-                    members: vec![staff],
+                    path: row.group_path,
+                    members: vec![member],
                 });
             }
         }
 
         Ok((result, unique_count))
     }
+
+    async fn find_ancestor_ids(&self, group_ids: &[Uuid]) -> DomainResult<Vec<Uuid>> {
+        let ids: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_id, ARRAY[id] AS path
+                FROM staff_groups WHERE id = ANY($1)
+                UNION ALL
+                SELECT sg.id, sg.parent_id, a.path || sg.id FROM staff_groups sg
+                INNER JOIN ancestors a ON sg.id = a.parent_id
+                WHERE sg.id <> ALL(a.path)
+            )
+            SELECT DISTINCT id FROM ancestors
+            "#,
+        )
+        .bind(group_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(ids.into_iter().map(|(id,)| id).collect())
+    }
+
+    async fn get_ancestors(&self, group_id: Uuid) -> DomainResult<Vec<StaffGroup>> {
+        let groups = sqlx::query_as::<_, StaffGroup>(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT id, parent_id, 0 AS depth, ARRAY[id] AS path
+                FROM staff_groups WHERE id = $1 AND deleted_at IS NULL
+                UNION ALL
+                SELECT sg.id, sg.parent_id, a.depth + 1, a.path || sg.id FROM staff_groups sg
+                INNER JOIN ancestors a ON sg.id = a.parent_id
+                WHERE sg.id <> ALL(a.path)
+            )
+            SELECT sg.id, sg.name, sg.parent_id, sg.created_at, sg.updated_at, sg.deleted_at
+            FROM ancestors a
+            JOIN staff_groups sg ON sg.id = a.id
+            ORDER BY a.depth DESC
+            "#,
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(groups)
+    }
+
+    async fn get_subtree(
+        &self,
+        root: Option<Uuid>,
+        max_depth: Option<u32>,
+        include_inactive: bool,
+    ) -> DomainResult<Vec<GroupNode>> {
+        let rows = sqlx::query_as::<_, GroupNodeRow>(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id, 0 AS depth, ARRAY[id] AS path FROM staff_groups
+                WHERE (($1::uuid IS NULL AND parent_id IS NULL) OR id = $1)
+                  AND deleted_at IS NULL
+                UNION ALL
+                SELECT sg.id, t.depth + 1, t.path || sg.id FROM staff_groups sg
+                INNER JOIN tree t ON sg.parent_id = t.id
+                WHERE sg.deleted_at IS NULL
+                  AND sg.id <> ALL(t.path)
+                  AND ($2::int IS NULL OR t.depth < $2)
+            )
+            SELECT
+                sg.id, sg.name, sg.parent_id, sg.created_at, sg.updated_at, t.depth,
+                (
+                    SELECT COUNT(DISTINCT gm.staff_id) FROM group_memberships gm
+                    JOIN staff s ON s.id = gm.staff_id
+                    WHERE gm.group_id = sg.id
+                      AND s.deleted_at IS NULL
+                      AND ($3 OR s.status = 'ACTIVE')
+                ) AS member_count,
+                (
+                    SELECT COUNT(*) FROM staff_groups c
+                    WHERE c.parent_id = sg.id AND c.deleted_at IS NULL
+                ) AS child_count
+            FROM tree t
+            JOIN staff_groups sg ON sg.id = t.id
+            ORDER BY t.depth, sg.name
+            "#,
+        )
+        .bind(root)
+        .bind(max_depth.map(|depth| depth.min(i32::MAX as u32) as i32))
+        .bind(include_inactive)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| GroupNode {
+                group: StaffGroup {
+                    id: row.id,
+                    name: row.name,
+                    parent_id: row.parent_id,
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                    deleted_at: None,
+                },
+                depth: row.depth,
+                member_count: row.member_count,
+                child_count: row.child_count,
+            })
+            .collect())
+    }
 }
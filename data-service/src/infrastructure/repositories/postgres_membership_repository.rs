@@ -3,6 +3,7 @@ use shared::{DomainError, DomainResult};
 use sqlx::PgPool;
 use uuid::Uuid;
 
+use crate::api::requests::{AddMemberRequest, UpdateMembershipRequest};
 use crate::domain::entities::GroupMembership;
 use crate::domain::repositories::MembershipRepository;
 
@@ -16,34 +17,109 @@ impl PostgresMembershipRepository {
     }
 }
 
+/// SQLSTATE of an exclusion constraint violation
+const EXCLUSION_VIOLATION: &str = "23P01";
+
+/// Map a failed membership write, reporting a period that overlaps another
+/// membership of the same staff and group as invalid input
+pub(crate) fn membership_write_error(e: sqlx::Error) -> DomainError {
+    match e.as_database_error() {
+        Some(db) if db.code().as_deref() == Some(EXCLUSION_VIOLATION) => DomainError::InvalidInput(
+            "Staff is already a member of this group during part of this period".to_string(),
+        ),
+        _ => DomainError::DatabaseError(e.to_string()),
+    }
+}
+
 #[async_trait]
 impl MembershipRepository for PostgresMembershipRepository {
-    async fn add_member(&self, staff_id: Uuid, group_id: Uuid) -> DomainResult<GroupMembership> {
+    async fn add_member(
+        &self,
+        group_id: Uuid,
+        request: AddMemberRequest,
+    ) -> DomainResult<GroupMembership> {
         let membership = sqlx::query_as::<_, GroupMembership>(
             r#"
-            INSERT INTO group_memberships (staff_id, group_id)
-            VALUES ($1, $2)
-            ON CONFLICT (staff_id, group_id) DO NOTHING
-            RETURNING id, staff_id, group_id, created_at
+            INSERT INTO group_memberships (staff_id, group_id, role, valid_from, valid_to)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, staff_id, group_id, created_at, role, valid_from, valid_to
             "#,
         )
-        .bind(staff_id)
+        .bind(request.staff_id)
         .bind(group_id)
+        .bind(request.role)
+        .bind(request.valid_from)
+        .bind(request.valid_to)
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        .map_err(membership_write_error)?;
 
         Ok(membership)
     }
 
-    async fn remove_member(&self, staff_id: Uuid, group_id: Uuid) -> DomainResult<()> {
-        let result =
-            sqlx::query("DELETE FROM group_memberships WHERE staff_id = $1 AND group_id = $2")
-                .bind(staff_id)
-                .bind(group_id)
-                .execute(&self.pool)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+    async fn find_memberships(
+        &self,
+        staff_id: Uuid,
+        group_id: Uuid,
+    ) -> DomainResult<Vec<GroupMembership>> {
+        sqlx::query_as::<_, GroupMembership>(
+            r#"
+            SELECT id, staff_id, group_id, created_at, role, valid_from, valid_to
+            FROM group_memberships
+            WHERE staff_id = $1 AND group_id = $2
+            ORDER BY valid_from NULLS FIRST
+            "#,
+        )
+        .bind(staff_id)
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    async fn update_member(
+        &self,
+        membership_id: Uuid,
+        request: UpdateMembershipRequest,
+    ) -> DomainResult<GroupMembership> {
+        let membership = sqlx::query_as::<_, GroupMembership>(
+            r#"
+            UPDATE group_memberships
+            SET role = $2, valid_from = $3, valid_to = $4
+            WHERE id = $1
+            RETURNING id, staff_id, group_id, created_at, role, valid_from, valid_to
+            "#,
+        )
+        .bind(membership_id)
+        .bind(request.role)
+        .bind(request.valid_from)
+        .bind(request.valid_to)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(membership_write_error)?
+        .ok_or_else(|| DomainError::NotFound("Membership not found".to_string()))?;
+
+        Ok(membership)
+    }
+
+    async fn remove_member(
+        &self,
+        staff_id: Uuid,
+        group_id: Uuid,
+        membership_id: Option<Uuid>,
+    ) -> DomainResult<()> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM group_memberships
+            WHERE staff_id = $1 AND group_id = $2 AND ($3::uuid IS NULL OR id = $3)
+            "#,
+        )
+        .bind(staff_id)
+        .bind(group_id)
+        .bind(membership_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
         if result.rows_affected() == 0 {
             return Err(DomainError::NotFound("Membership not found".to_string()));
@@ -51,4 +127,15 @@ impl MembershipRepository for PostgresMembershipRepository {
 
         Ok(())
     }
+
+    async fn find_group_ids_by_staff(&self, staff_id: Uuid) -> DomainResult<Vec<Uuid>> {
+        let ids: Vec<(Uuid,)> =
+            sqlx::query_as("SELECT DISTINCT group_id FROM group_memberships WHERE staff_id = $1")
+                .bind(staff_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(ids.into_iter().map(|(id,)| id).collect())
+    }
 }
@@ -1,10 +1,13 @@
 use async_trait::async_trait;
 use shared::{DomainError, DomainResult, PaginationParams, StaffStatus};
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
-use crate::api::requests::{CreateStaffRequest, UpdateStaffRequest};
-use crate::domain::entities::Staff;
+use crate::api::requests::{
+    CreateStaffRequest, SortOrder, StaffCursor, StaffFilter, StaffSort, StaffSortField,
+    UpdateStaffRequest,
+};
+use crate::domain::entities::{Staff, StaffPage};
 use crate::domain::repositories::StaffRepository;
 
 pub struct PostgresStaffRepository {
@@ -15,6 +18,81 @@ impl PostgresStaffRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    fn push_filter(query_builder: &mut QueryBuilder<'_, Postgres>, filter: &StaffFilter) {
+        query_builder.push(" WHERE TRUE");
+
+        if !filter.include_deleted {
+            query_builder.push(" AND deleted_at IS NULL");
+        }
+        if let Some(status) = &filter.status {
+            query_builder
+                .push(" AND status = ")
+                .push_bind(status.clone());
+        }
+        if let Some(position) = &filter.position {
+            query_builder
+                .push(" AND LOWER(position) = LOWER(")
+                .push_bind(position.clone())
+                .push(")");
+        }
+        if let Some(q) = &filter.q {
+            let pattern = format!("%{}%", escape_like(q));
+            query_builder
+                .push(" AND (name ILIKE ")
+                .push_bind(pattern.clone())
+                .push(" OR email ILIKE ")
+                .push_bind(pattern)
+                .push(")");
+        }
+        if let Some(group_id) = filter.group_id {
+            if filter.include_subgroups {
+                query_builder
+                    .push(
+                        r#" AND id IN (
+                            SELECT gm.staff_id FROM group_memberships gm
+                            WHERE gm.group_id IN (
+                                WITH RECURSIVE subtree AS (
+                                    SELECT id, ARRAY[id] AS path FROM staff_groups
+                                    WHERE deleted_at IS NULL AND id = "#,
+                    )
+                    .push_bind(group_id)
+                    .push(
+                        r#"
+                                    UNION ALL
+                                    SELECT sg.id, t.path || sg.id FROM staff_groups sg
+                                    INNER JOIN subtree t ON sg.parent_id = t.id
+                                    WHERE sg.deleted_at IS NULL AND sg.id <> ALL(t.path)
+                                )
+                                SELECT id FROM subtree
+                            )
+                        )"#,
+                    );
+            } else {
+                query_builder
+                    .push(
+                        r#" AND id IN (
+                            SELECT gm.staff_id FROM group_memberships gm
+                            INNER JOIN staff_groups sg ON sg.id = gm.group_id
+                            WHERE sg.deleted_at IS NULL AND gm.group_id = "#,
+                    )
+                    .push_bind(group_id)
+                    .push(")");
+            }
+        }
+    }
+}
+
+/// Escape the wildcards of a LIKE pattern
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
 #[async_trait]
@@ -24,15 +102,16 @@ impl StaffRepository for PostgresStaffRepository {
 
         let staff = sqlx::query_as::<_, Staff>(
             r#"
-            INSERT INTO staff (name, email, position, status)
-            VALUES ($1, $2, $3, $4)
-            RETURNING id, name, email, position, status, created_at, updated_at
+            INSERT INTO staff (name, email, position, status, external_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, email, external_id, position, status, created_at, updated_at, deleted_at
             "#,
         )
         .bind(&request.name)
         .bind(&request.email)
         .bind(&request.position)
         .bind(&status)
+        .bind(&request.external_id)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
@@ -43,9 +122,9 @@ impl StaffRepository for PostgresStaffRepository {
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Staff>> {
         let staff = sqlx::query_as::<_, Staff>(
             r#"
-            SELECT id, name, email, position, status, created_at, updated_at
+            SELECT id, name, email, external_id, position, status, created_at, updated_at, deleted_at
             FROM staff
-            WHERE id = $1
+            WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(id)
@@ -56,15 +135,15 @@ impl StaffRepository for PostgresStaffRepository {
         Ok(staff)
     }
 
-    async fn find_by_email(&self, email: &str) -> DomainResult<Option<Staff>> {
+    async fn find_by_id_including_deleted(&self, id: Uuid) -> DomainResult<Option<Staff>> {
         let staff = sqlx::query_as::<_, Staff>(
             r#"
-            SELECT id, name, email, position, status, created_at, updated_at
+            SELECT id, name, email, external_id, position, status, created_at, updated_at, deleted_at
             FROM staff
-            WHERE email = $1
+            WHERE id = $1
             "#,
         )
-        .bind(email)
+        .bind(id)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
@@ -72,61 +151,100 @@ impl StaffRepository for PostgresStaffRepository {
         Ok(staff)
     }
 
-    async fn list(&self, params: PaginationParams) -> DomainResult<(Vec<Staff>, u64)> {
-        let offset = (params.page - 1) * params.page_size;
-
-        let staff_list = sqlx::query_as::<_, Staff>(
+    async fn find_by_email(&self, email: &str) -> DomainResult<Option<Staff>> {
+        let staff = sqlx::query_as::<_, Staff>(
             r#"
-            SELECT id, name, email, position, status, created_at, updated_at
+            SELECT id, name, email, external_id, position, status, created_at, updated_at, deleted_at
             FROM staff
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
+            WHERE email = $1 AND deleted_at IS NULL
             "#,
         )
-        .bind(params.page_size as i64)
-        .bind(offset as i64)
-        .fetch_all(&self.pool)
+        .bind(email)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM staff")
-            .fetch_one(&self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
-
-        Ok((staff_list, total.0 as u64))
+        Ok(staff)
     }
 
-    async fn list_by_status(
+    async fn list(
         &self,
-        status: StaffStatus,
+        filter: &StaffFilter,
+        sort: &StaffSort,
         params: PaginationParams,
-    ) -> DomainResult<(Vec<Staff>, u64)> {
-        let offset = (params.page - 1) * params.page_size;
+    ) -> DomainResult<StaffPage> {
+        let cursor = match &sort.cursor {
+            Some(cursor) => Some(
+                StaffCursor::decode(cursor)
+                    .ok_or_else(|| DomainError::InvalidInput("Invalid cursor".to_string()))?,
+            ),
+            None => None,
+        };
+        let column = sort.sort.column();
+        let (direction, comparison) = match sort.order() {
+            SortOrder::Asc => ("ASC", ">"),
+            SortOrder::Desc => ("DESC", "<"),
+        };
 
-        let staff_list = sqlx::query_as::<_, Staff>(
-            r#"
-            SELECT id, name, email, position, status, created_at, updated_at
-            FROM staff
-            WHERE status = $1
-            ORDER BY created_at DESC
-            LIMIT $2 OFFSET $3
-            "#,
-        )
-        .bind(&status)
-        .bind(params.page_size as i64)
-        .bind(offset as i64)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT id, name, email, external_id, position, status, created_at, updated_at, deleted_at FROM staff",
+        );
+        Self::push_filter(&mut query_builder, filter);
+        if let Some(cursor) = &cursor {
+            let cast = match sort.sort {
+                StaffSortField::CreatedAt | StaffSortField::UpdatedAt => "::timestamptz",
+                _ => "",
+            };
+            query_builder
+                .push(format!(" AND ({}, id) {} (", column, comparison))
+                .push_bind(cursor.value.clone())
+                .push(cast)
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(")");
+        }
+        // One extra row tells whether another page follows
+        query_builder
+            .push(format!(
+                " ORDER BY {} {}, id {} LIMIT ",
+                column, direction, direction
+            ))
+            .push_bind(params.page_size as i64 + 1);
+        if cursor.is_none() {
+            let offset = (params.page - 1) * params.page_size;
+            query_builder.push(" OFFSET ").push_bind(offset as i64);
+        }
+
+        let mut staff = query_builder
+            .build_query_as::<Staff>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM staff WHERE status = $1")
-            .bind(&status)
+        let mut count_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM staff");
+        Self::push_filter(&mut count_builder, filter);
+
+        let total: (i64,) = count_builder
+            .build_query_as()
             .fetch_one(&self.pool)
             .await
             .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        Ok((staff_list, total.0 as u64))
+        let next_cursor = if staff.len() > params.page_size as usize {
+            staff.truncate(params.page_size as usize);
+            staff
+                .last()
+                .map(|last| StaffCursor::new(last, sort.sort).encode())
+        } else {
+            None
+        };
+
+        Ok(StaffPage {
+            staff,
+            total: total.0 as u64,
+            next_cursor,
+        })
     }
 
     async fn update(&self, id: Uuid, request: UpdateStaffRequest) -> DomainResult<Staff> {
@@ -138,15 +256,17 @@ impl StaffRepository for PostgresStaffRepository {
         let staff = sqlx::query_as::<_, Staff>(
             r#"
             UPDATE staff
-            SET name = $1, email = $2, position = $3, status = $4, updated_at = NOW()
-            WHERE id = $5
-            RETURNING id, name, email, position, status, created_at, updated_at
+            SET name = $1, email = $2, position = $3, status = $4, external_id = $5,
+                updated_at = NOW()
+            WHERE id = $6 AND deleted_at IS NULL
+            RETURNING id, name, email, external_id, position, status, created_at, updated_at, deleted_at
             "#,
         )
         .bind(request.name.unwrap_or(current.name))
         .bind(request.email.unwrap_or(current.email))
         .bind(request.position.unwrap_or(current.position))
         .bind(request.status.unwrap_or(current.status))
+        .bind(request.external_id.unwrap_or(current.external_id))
         .bind(id)
         .fetch_one(&self.pool)
         .await
@@ -156,11 +276,12 @@ impl StaffRepository for PostgresStaffRepository {
     }
 
     async fn delete(&self, id: Uuid) -> DomainResult<()> {
-        let result = sqlx::query("DELETE FROM staff WHERE id = $1")
-            .bind(id)
-            .execute(&self.pool)
-            .await
-            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let result =
+            sqlx::query("UPDATE staff SET deleted_at = NOW() WHERE id = $1 AND deleted_at IS NULL")
+                .bind(id)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
         if result.rows_affected() == 0 {
             return Err(DomainError::NotFound(format!(
@@ -172,13 +293,59 @@ impl StaffRepository for PostgresStaffRepository {
         Ok(())
     }
 
+    async fn restore(&self, id: Uuid) -> DomainResult<Staff> {
+        let staff = sqlx::query_as::<_, Staff>(
+            r#"
+            UPDATE staff
+            SET deleted_at = NULL
+            WHERE id = $1
+            RETURNING id, name, email, external_id, position, status, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| match e.as_database_error() {
+            // Someone else took the email or external id while it was deleted
+            Some(db) if db.is_unique_violation() => DomainError::InvalidInput(format!(
+                "Staff with id {} cannot be restored: its email or external id is in use",
+                id
+            )),
+            _ => DomainError::DatabaseError(e.to_string()),
+        })?;
+
+        staff.ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))
+    }
+
+    async fn purge(&self, id: Uuid) -> DomainResult<()> {
+        let staff = self
+            .find_by_id_including_deleted(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))?;
+        if staff.deleted_at.is_none() {
+            return Err(DomainError::InvalidInput(format!(
+                "Staff with id {} must be deleted before it is purged",
+                id
+            )));
+        }
+
+        sqlx::query("DELETE FROM staff WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn find_by_group_id(&self, group_id: Uuid) -> DomainResult<Vec<Staff>> {
         let staff_list = sqlx::query_as::<_, Staff>(
             r#"
-            SELECT s.id, s.name, s.email, s.position, s.status, s.created_at, s.updated_at
+            SELECT s.id, s.name, s.email, s.external_id, s.position, s.status,
+                   s.created_at, s.updated_at, s.deleted_at
             FROM staff s
-            INNER JOIN group_memberships gm ON s.id = gm.staff_id
-            WHERE gm.group_id = $1
+            WHERE s.id IN (SELECT staff_id FROM group_memberships WHERE group_id = $1)
+              AND s.deleted_at IS NULL
             ORDER BY s.name
             "#,
         )
@@ -0,0 +1,296 @@
+use async_trait::async_trait;
+use shared::{DomainError, DomainResult, StaffStatus};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::api::requests::{
+    AddMemberRequest, CreateGroupRequest, CreateStaffRequest, UpdateMembershipRequest,
+    UpdateStaffRequest,
+};
+use crate::domain::entities::{GroupMembership, Staff, StaffGroup};
+use crate::domain::repositories::{Transaction, UnitOfWork};
+use crate::infrastructure::repositories::postgres_group_repository::{
+    insert_group, load_hierarchy, parent_not_found,
+};
+use crate::infrastructure::repositories::postgres_membership_repository::membership_write_error;
+
+pub struct PostgresUnitOfWork {
+    pool: PgPool,
+}
+
+impl PostgresUnitOfWork {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for PostgresUnitOfWork {
+    async fn begin(&self) -> DomainResult<Box<dyn Transaction>> {
+        let tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(Box::new(PostgresTransaction { tx }))
+    }
+}
+
+/// Wraps a sqlx transaction, which rolls back when dropped uncommitted.
+///
+/// Every write runs under a savepoint: a failed statement would otherwise
+/// abort the whole transaction.
+pub struct PostgresTransaction {
+    tx: sqlx::Transaction<'static, sqlx::Postgres>,
+}
+
+impl PostgresTransaction {
+    async fn execute(&mut self, statement: &str) -> DomainResult<()> {
+        sqlx::query(statement)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn savepoint(&mut self) -> DomainResult<()> {
+        self.execute("SAVEPOINT write").await
+    }
+
+    /// Keep the write on success, undo only the write on failure
+    async fn settle<T>(&mut self, result: Result<T, sqlx::Error>) -> DomainResult<T> {
+        self.settle_with(result, |e| DomainError::DatabaseError(e.to_string()))
+            .await
+    }
+
+    /// Like `settle`, reporting a failed write with `map_err`
+    async fn settle_with<T>(
+        &mut self,
+        result: Result<T, sqlx::Error>,
+        map_err: fn(sqlx::Error) -> DomainError,
+    ) -> DomainResult<T> {
+        match result {
+            Ok(value) => {
+                self.execute("RELEASE SAVEPOINT write").await?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.execute("ROLLBACK TO SAVEPOINT write").await?;
+                Err(map_err(e))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transaction for PostgresTransaction {
+    async fn create_staff(&mut self, request: &CreateStaffRequest) -> DomainResult<Staff> {
+        let status = request.status.clone().unwrap_or(StaffStatus::Active);
+
+        self.savepoint().await?;
+        let result = sqlx::query_as::<_, Staff>(
+            r#"
+            INSERT INTO staff (name, email, position, status, external_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, name, email, external_id, position, status, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(&request.name)
+        .bind(&request.email)
+        .bind(&request.position)
+        .bind(&status)
+        .bind(&request.external_id)
+        .fetch_one(&mut *self.tx)
+        .await;
+
+        self.settle(result).await
+    }
+
+    async fn update_staff(
+        &mut self,
+        id: Uuid,
+        request: &UpdateStaffRequest,
+    ) -> DomainResult<Staff> {
+        self.savepoint().await?;
+        let result = sqlx::query_as::<_, Staff>(
+            r#"
+            UPDATE staff
+            SET name = COALESCE($1, name),
+                email = COALESCE($2, email),
+                position = COALESCE($3, position),
+                status = COALESCE($4, status),
+                external_id = CASE WHEN $6 THEN $5 ELSE external_id END,
+                updated_at = NOW()
+            WHERE id = $7 AND deleted_at IS NULL
+            RETURNING id, name, email, external_id, position, status, created_at, updated_at, deleted_at
+            "#,
+        )
+        .bind(&request.name)
+        .bind(&request.email)
+        .bind(&request.position)
+        .bind(&request.status)
+        .bind(request.external_id.as_ref().and_then(Option::as_ref))
+        .bind(request.external_id.is_some())
+        .bind(id)
+        .fetch_optional(&mut *self.tx)
+        .await;
+
+        self.settle(result)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))
+    }
+
+    async fn list_staff(&mut self) -> DomainResult<Vec<Staff>> {
+        sqlx::query_as::<_, Staff>(
+            r#"
+            SELECT id, name, email, external_id, position, status, created_at, updated_at, deleted_at
+            FROM staff
+            WHERE deleted_at IS NULL
+            ORDER BY email
+            "#,
+        )
+        .fetch_all(&mut *self.tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    async fn find_staff_by_email(&mut self, email: &str) -> DomainResult<Option<Staff>> {
+        sqlx::query_as::<_, Staff>(
+            r#"
+            SELECT id, name, email, external_id, position, status, created_at, updated_at, deleted_at
+            FROM staff
+            WHERE email = $1 AND deleted_at IS NULL
+            "#,
+        )
+        .bind(email)
+        .fetch_optional(&mut *self.tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    async fn create_group(&mut self, request: &CreateGroupRequest) -> DomainResult<StaffGroup> {
+        let tree = load_hierarchy(&mut self.tx).await?;
+        tree.check_new_group(&request.name, request.parent_id)
+            .map_err(DomainError::InvalidInput)?;
+        self.savepoint().await?;
+        let result = insert_group(&mut self.tx, request).await;
+
+        self.settle(result)
+            .await?
+            .ok_or_else(|| parent_not_found(request))
+    }
+
+    async fn list_groups(&mut self) -> DomainResult<Vec<StaffGroup>> {
+        sqlx::query_as::<_, StaffGroup>(
+            r#"
+            SELECT id, name, parent_id, created_at, updated_at, deleted_at
+            FROM staff_groups
+            WHERE deleted_at IS NULL
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&mut *self.tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    async fn set_group_parent(&mut self, group_id: Uuid, parent_id: Uuid) -> DomainResult<()> {
+        let tree = load_hierarchy(&mut self.tx).await?;
+        let group = tree.get(group_id).ok_or_else(|| {
+            DomainError::NotFound(format!("Group with id {} not found", group_id))
+        })?;
+        tree.check_placement(group_id, &group.name, Some(parent_id))
+            .map_err(DomainError::InvalidInput)?;
+
+        self.savepoint().await?;
+        let result =
+            sqlx::query("UPDATE staff_groups SET parent_id = $1, updated_at = NOW() WHERE id = $2")
+                .bind(parent_id)
+                .bind(group_id)
+                .execute(&mut *self.tx)
+                .await;
+
+        let result = self.settle(result).await?;
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Group with id {} not found",
+                group_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn add_member(
+        &mut self,
+        group_id: Uuid,
+        request: &AddMemberRequest,
+    ) -> DomainResult<GroupMembership> {
+        self.savepoint().await?;
+        let result = sqlx::query_as::<_, GroupMembership>(
+            r#"
+            INSERT INTO group_memberships (staff_id, group_id, role, valid_from, valid_to)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, staff_id, group_id, created_at, role, valid_from, valid_to
+            "#,
+        )
+        .bind(request.staff_id)
+        .bind(group_id)
+        .bind(request.role)
+        .bind(request.valid_from)
+        .bind(request.valid_to)
+        .fetch_one(&mut *self.tx)
+        .await;
+
+        self.settle_with(result, membership_write_error).await
+    }
+
+    async fn update_member(
+        &mut self,
+        membership_id: Uuid,
+        request: &UpdateMembershipRequest,
+    ) -> DomainResult<GroupMembership> {
+        self.savepoint().await?;
+        let result = sqlx::query_as::<_, GroupMembership>(
+            r#"
+            UPDATE group_memberships
+            SET role = $2, valid_from = $3, valid_to = $4
+            WHERE id = $1
+            RETURNING id, staff_id, group_id, created_at, role, valid_from, valid_to
+            "#,
+        )
+        .bind(membership_id)
+        .bind(request.role)
+        .bind(request.valid_from)
+        .bind(request.valid_to)
+        .fetch_optional(&mut *self.tx)
+        .await;
+
+        self.settle_with(result, membership_write_error)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Membership not found".to_string()))
+    }
+
+    async fn list_memberships(&mut self) -> DomainResult<Vec<GroupMembership>> {
+        sqlx::query_as::<_, GroupMembership>(
+            r#"
+            SELECT gm.id, gm.staff_id, gm.group_id, gm.created_at, gm.role, gm.valid_from, gm.valid_to
+            FROM group_memberships gm
+            INNER JOIN staff s ON s.id = gm.staff_id
+            INNER JOIN staff_groups sg ON sg.id = gm.group_id
+            WHERE s.deleted_at IS NULL AND sg.deleted_at IS NULL
+            "#,
+        )
+        .fetch_all(&mut *self.tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+
+    async fn commit(self: Box<Self>) -> DomainResult<()> {
+        self.tx
+            .commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+}
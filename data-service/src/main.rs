@@ -8,12 +8,13 @@ use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use api::AppState;
-use domain::repositories::{GroupRepository, MembershipRepository, StaffRepository};
+use domain::repositories::{GroupRepository, MembershipRepository, StaffRepository, UnitOfWork};
 use infrastructure::{
     config::Settings,
     database, redis,
     repositories::{
         PostgresGroupRepository, PostgresMembershipRepository, PostgresStaffRepository,
+        PostgresUnitOfWork,
     },
 };
 
@@ -46,10 +47,17 @@ async fn main() -> Result<()> {
         Arc::new(PostgresGroupRepository::new(db_pool.clone()));
     let membership_repo: Arc<dyn MembershipRepository> =
         Arc::new(PostgresMembershipRepository::new(db_pool.clone()));
+    let unit_of_work: Arc<dyn UnitOfWork> = Arc::new(PostgresUnitOfWork::new(db_pool.clone()));
 
     tracing::info!("Repositories initialized");
 
-    let app_state = AppState::new(staff_repo, group_repo, membership_repo, redis_pool);
+    let app_state = AppState::new(
+        staff_repo,
+        group_repo,
+        membership_repo,
+        unit_of_work,
+        redis_pool,
+    );
 
     let app = api::create_router(app_state);
     let listener = tokio::net::TcpListener::bind(settings.server_address()).await?;
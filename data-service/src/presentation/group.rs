@@ -1,9 +1,13 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::entities::{GroupWithMembers, StaffGroup};
+use shared::MembershipRole;
+
+use crate::domain::entities::{GroupMember, GroupNode, GroupWithMembers, StaffGroup};
+use crate::domain::group_tree::join_path;
 use crate::presentation::StaffSerializer;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -14,6 +18,7 @@ pub struct GroupSerializer {
     pub parent_name: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl GroupSerializer {
@@ -25,6 +30,7 @@ impl GroupSerializer {
             parent_name,
             created_at: group.created_at,
             updated_at: group.updated_at,
+            deleted_at: group.deleted_at,
         }
     }
 }
@@ -33,7 +39,31 @@ impl GroupSerializer {
 pub struct ResolvedGroupSerializer {
     pub group_id: Uuid,
     pub group_name: String,
-    pub members: Vec<StaffSerializer>,
+    /// Groups from the resolved group down to this one, both included
+    pub path: Vec<Uuid>,
+    pub members: Vec<ResolvedMemberSerializer>,
+}
+
+/// A member of a resolved group, with their role in that group and the days
+/// the membership is in effect
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ResolvedMemberSerializer {
+    #[serde(flatten)]
+    pub staff: StaffSerializer,
+    pub role: MembershipRole,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+}
+
+impl From<GroupMember> for ResolvedMemberSerializer {
+    fn from(member: GroupMember) -> Self {
+        Self {
+            staff: StaffSerializer::from(member.staff),
+            role: member.role,
+            valid_from: member.valid_from,
+            valid_to: member.valid_to,
+        }
+    }
 }
 
 impl From<GroupWithMembers> for ResolvedGroupSerializer {
@@ -41,7 +71,93 @@ impl From<GroupWithMembers> for ResolvedGroupSerializer {
         Self {
             group_id: gwm.group.id,
             group_name: gwm.group.name,
-            members: gwm.members.into_iter().map(StaffSerializer::from).collect(),
+            path: gwm.path,
+            members: gwm
+                .members
+                .into_iter()
+                .map(ResolvedMemberSerializer::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BreadcrumbItemSerializer {
+    pub id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BreadcrumbSerializer {
+    /// Path from the root, e.g. `Engineering/Backend`
+    pub path: String,
+    /// Groups from the root down to the requested group, both included
+    pub groups: Vec<BreadcrumbItemSerializer>,
+}
+
+impl From<Vec<StaffGroup>> for BreadcrumbSerializer {
+    fn from(groups: Vec<StaffGroup>) -> Self {
+        Self {
+            path: join_path(&groups.iter().map(|g| g.name.as_str()).collect::<Vec<_>>()),
+            groups: groups
+                .into_iter()
+                .map(|group| BreadcrumbItemSerializer {
+                    id: group.id,
+                    name: group.name,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupTreeNodeSerializer {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    /// Levels below the top of the tree
+    pub depth: i32,
+    /// Direct members, not counting subgroups
+    pub member_count: i64,
+    /// Direct subgroups. More than `children.len()` when the depth limit cut
+    /// them off
+    pub child_count: i64,
+    #[schema(no_recursion)]
+    pub children: Vec<GroupTreeNodeSerializer>,
+}
+
+impl GroupTreeNodeSerializer {
+    /// Nest nodes listed parents before children under the top-level ones
+    pub fn build(nodes: Vec<GroupNode>) -> Vec<Self> {
+        let mut children: HashMap<Uuid, Vec<GroupNode>> = HashMap::new();
+        let mut top = Vec::new();
+        for node in nodes {
+            match node.group.parent_id {
+                Some(parent_id) if node.depth > 0 => {
+                    children.entry(parent_id).or_default().push(node)
+                }
+                _ => top.push(node),
+            }
+        }
+
+        top.into_iter()
+            .map(|node| Self::nest(node, &mut children))
+            .collect()
+    }
+
+    fn nest(node: GroupNode, children: &mut HashMap<Uuid, Vec<GroupNode>>) -> Self {
+        let below = children.remove(&node.group.id).unwrap_or_default();
+        Self {
+            id: node.group.id,
+            name: node.group.name,
+            parent_id: node.group.parent_id,
+            depth: node.depth,
+            member_count: node.member_count,
+            child_count: node.child_count,
+            children: below
+                .into_iter()
+                .map(|child| Self::nest(child, children))
+                .collect(),
         }
     }
 }
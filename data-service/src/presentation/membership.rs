@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use shared::MembershipRole;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -13,6 +14,9 @@ pub struct MembershipSerializer {
     pub staff_name: String,
     pub staff_email: String,
     pub group_name: String,
+    pub role: MembershipRole,
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -25,6 +29,9 @@ impl MembershipSerializer {
             staff_name: staff.name.clone(),
             staff_email: staff.email.clone(),
             group_name: group.name.clone(),
+            role: membership.role,
+            valid_from: membership.valid_from,
+            valid_to: membership.valid_to,
             created_at: membership.created_at,
         }
     }
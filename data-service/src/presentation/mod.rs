@@ -1,7 +1,15 @@
 pub mod group;
 pub mod membership;
+pub mod organisation;
 pub mod staff;
 
-pub use group::{GroupSerializer, ResolvedGroupSerializer};
+pub use group::{
+    BreadcrumbItemSerializer, BreadcrumbSerializer, GroupSerializer, GroupTreeNodeSerializer,
+    ResolvedGroupSerializer, ResolvedMemberSerializer,
+};
 pub use membership::MembershipSerializer;
+pub use organisation::{
+    OrganisationDocument, OrganisationGroup, OrganisationMembership, OrganisationStaff,
+    ORGANISATION_DOCUMENT_VERSION,
+};
 pub use staff::StaffSerializer;
@@ -0,0 +1,59 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{MembershipRole, StaffStatus};
+use utoipa::ToSchema;
+
+/// Version written by exports. Imports reject any other version.
+pub const ORGANISATION_DOCUMENT_VERSION: u32 = 1;
+
+/// Staff, the group tree and memberships in one document. Groups are
+/// referenced by path, e.g. `Engineering Department/Backend Team`, with `/`
+/// and `\` inside a name escaped by a backslash.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OrganisationDocument {
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub staff: Vec<OrganisationStaff>,
+    #[serde(default)]
+    pub groups: Vec<OrganisationGroup>,
+    #[serde(default)]
+    pub memberships: Vec<OrganisationMembership>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OrganisationStaff {
+    pub name: String,
+    pub email: String,
+    pub position: String,
+    /// Active when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<StaffStatus>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OrganisationGroup {
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OrganisationMembership {
+    pub staff_email: String,
+    pub group_path: String,
+    /// MEMBER when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<MembershipRole>,
+    /// First day of the membership; open when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<NaiveDate>,
+    /// Last day of the membership; open when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_to: Option<NaiveDate>,
+}
@@ -11,10 +11,12 @@ pub struct StaffSerializer {
     pub id: Uuid,
     pub name: String,
     pub email: String,
+    pub external_id: Option<String>,
     pub position: String,
     pub status: StaffStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Staff> for StaffSerializer {
@@ -23,10 +25,12 @@ impl From<Staff> for StaffSerializer {
             id: staff.id,
             name: staff.name,
             email: staff.email,
+            external_id: staff.external_id,
             position: staff.position,
             status: staff.status,
             created_at: staff.created_at,
             updated_at: staff.updated_at,
+            deleted_at: staff.deleted_at,
         }
     }
 }
@@ -0,0 +1,496 @@
+//! Batch import API integration tests
+
+#[path = "common/mod.rs"]
+mod common;
+
+use axum::http::StatusCode;
+use axum_test::multipart::{MultipartForm, Part};
+use axum_test::TestServer;
+use common::{
+    create_mock_redis_pool, create_sample_group, create_sample_staff, MockGroupRepository,
+    MockMembershipRepository, MockStaffRepository, MockStore, MockUnitOfWork,
+};
+use data_service::api::import_payload::{ImportPayload, PayloadFormat};
+use data_service::api::requests::{BatchGroupRow, CreateStaffRequest};
+use data_service::api::{create_router, AppState};
+use data_service::domain::entities::Staff;
+use shared::{MembershipRole, StaffStatus};
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn setup_test_server(store: MockStore) -> (TestServer, Arc<MockUnitOfWork>) {
+    let unit_of_work = Arc::new(MockUnitOfWork::with_store(store));
+    let state = AppState::new(
+        Arc::new(MockStaffRepository::new()),
+        Arc::new(MockGroupRepository::new()),
+        Arc::new(MockMembershipRepository::new()),
+        unit_of_work.clone(),
+        create_mock_redis_pool().await,
+    );
+
+    (TestServer::new(create_router(state)).unwrap(), unit_of_work)
+}
+
+const STAFF_JSON: &str = r#"[
+  { "name": "John Doe", "email": "john@example.com", "position": "Nurse" },
+  { "name": "No Email", "position": "Nurse" },
+  {
+    "name": "Jane Smith",
+    "email": "jane-at-example.com",
+    "position": "Nurse"
+  },
+  { "name": "Bob Brown", "email": "bob@example.com", "position": "Porter", "status": "INACTIVE" }
+]"#;
+
+#[test]
+fn test_json_rows_report_their_lines() {
+    let rows = ImportPayload::new(PayloadFormat::Json, STAFF_JSON)
+        .rows::<CreateStaffRequest>()
+        .unwrap();
+
+    let lines: Vec<u64> = rows.iter().map(|row| row.line).collect();
+    assert_eq!(lines, vec![2, 3, 4, 9]);
+    assert!(rows[1]
+        .row
+        .as_ref()
+        .unwrap_err()
+        .contains("missing field `email`"));
+    assert!(rows[2].row.is_ok());
+}
+
+#[test]
+fn test_csv_rows_report_their_lines() {
+    let csv = "name,parent_name\nWards,\n Ward A , Wards\nWard B,Wards,extra\n";
+    let rows = ImportPayload::new(PayloadFormat::Csv, csv)
+        .rows::<BatchGroupRow>()
+        .unwrap();
+
+    assert_eq!(rows.len(), 3);
+    let wards = rows[0].row.as_ref().unwrap();
+    assert_eq!((rows[0].line, wards.parent_name.as_deref()), (2, None));
+    let ward_a = rows[1].row.as_ref().unwrap();
+    assert_eq!(ward_a.name, "Ward A");
+    assert_eq!(ward_a.parent_name.as_deref(), Some("Wards"));
+    assert_eq!(rows[2].line, 4);
+    assert_eq!(
+        rows[2].row.as_ref().unwrap_err(),
+        "expected 2 fields, found 3"
+    );
+}
+
+#[test]
+fn test_payload_that_is_not_an_array_is_rejected() {
+    let result =
+        ImportPayload::new(PayloadFormat::Json, r#"{"name": "Wards"}"#).rows::<BatchGroupRow>();
+
+    assert!(result
+        .unwrap_err()
+        .starts_with("Payload must be a JSON array"));
+}
+
+#[tokio::test]
+async fn test_batch_import_staff_reports_failed_rows() {
+    let (server, unit_of_work) = setup_test_server(MockStore::default()).await;
+
+    let response = server
+        .post("/api/v1/batch/staff")
+        .text(STAFF_JSON)
+        .content_type("application/json")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["message"], "Batch staff import completed");
+    assert_eq!(body["data"]["success_count"], 2);
+    assert_eq!(body["data"]["error_count"], 2);
+    assert_eq!(body["data"]["committed"], true);
+    assert_eq!(body["data"]["errors"][0]["line"], 3);
+    assert_eq!(body["data"]["errors"][1]["line"], 4);
+    assert_eq!(
+        body["data"]["errors"][1]["message"],
+        "'jane-at-example.com' is not a valid email"
+    );
+    assert_eq!(unit_of_work.store().staff.len(), 2);
+}
+
+#[tokio::test]
+async fn test_batch_import_dry_run_saves_nothing() {
+    let (server, unit_of_work) = setup_test_server(MockStore::default()).await;
+
+    let response = server
+        .post("/api/v1/batch/staff?dry_run=true")
+        .text("name,email,position\nJohn Doe,john@example.com,Nurse\n")
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["message"], "Batch staff import validated");
+    assert_eq!(body["data"]["success_count"], 1);
+    assert_eq!(body["data"]["committed"], false);
+    assert!(unit_of_work.store().staff.is_empty());
+}
+
+#[tokio::test]
+async fn test_batch_import_all_or_nothing_rolls_back_on_error() {
+    let existing = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![existing],
+        ..MockStore::default()
+    })
+    .await;
+
+    let response = server
+        .post("/api/v1/batch/staff?all_or_nothing=true")
+        .text("name,email,position\nJane Smith,jane@example.com,Nurse\nJohn Again,john@example.com,Nurse\n")
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["message"], "Batch staff import rolled back");
+    assert!(body.get("data").is_none());
+    assert_eq!(body["details"]["error_count"], 1);
+    assert_eq!(body["details"]["errors"][0]["line"], 3);
+    assert_eq!(unit_of_work.store().staff.len(), 1);
+}
+
+#[tokio::test]
+async fn test_batch_import_groups_resolves_parents_within_the_batch() {
+    let existing = create_sample_group(Uuid::new_v4(), "Hospital", None);
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        groups: vec![existing.clone()],
+        ..MockStore::default()
+    })
+    .await;
+
+    let response = server
+        .post("/api/v1/batch/groups")
+        .text("name,parent_name\nWard A,Wards\nWards,Hospital\nWard B,Clinics\n")
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["success_count"], 2);
+    assert_eq!(body["data"]["errors"][0]["line"], 4);
+    assert_eq!(
+        body["data"]["errors"][0]["message"],
+        "Parent group 'Clinics' not found for 'Ward B'"
+    );
+
+    let groups = unit_of_work.store().groups;
+    let find = |name: &str| groups.iter().find(|g| g.name == name).unwrap();
+    assert_eq!(find("Wards").parent_id, Some(existing.id));
+    assert_eq!(find("Ward A").parent_id, Some(find("Wards").id));
+}
+
+#[tokio::test]
+async fn test_batch_import_groups_rejects_cycles() {
+    let (server, unit_of_work) = setup_test_server(MockStore::default()).await;
+
+    let response = server
+        .post("/api/v1/batch/groups")
+        .text("name,parent_name\nA,B\nB,A\n")
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["success_count"], 1);
+    assert_eq!(body["data"]["errors"][0]["line"], 3);
+    assert!(body["data"]["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("descendant"));
+
+    let groups = unit_of_work.store().groups;
+    let find = |name: &str| groups.iter().find(|g| g.name == name).unwrap();
+    assert_eq!(find("A").parent_id, Some(find("B").id));
+    assert_eq!(find("B").parent_id, None);
+}
+
+#[tokio::test]
+async fn test_batch_import_refers_to_duplicate_names_by_path() {
+    let engineering = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let sales = create_sample_group(Uuid::new_v4(), "Sales", None);
+    let sales_support = create_sample_group(Uuid::new_v4(), "Support", Some(sales.id));
+    let eng_support = create_sample_group(Uuid::new_v4(), "Support", Some(engineering.id));
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![staff.clone()],
+        groups: vec![engineering, sales, sales_support, eng_support.clone()],
+        ..MockStore::default()
+    })
+    .await;
+
+    let response = server
+        .post("/api/v1/batch/groups")
+        .json(&serde_json::json!([
+            { "name": "Tier 1", "parent_path": "Engineering/Support" },
+            { "name": "Tier 2", "parent_name": "Support" }
+        ]))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["success_count"], 1);
+    assert_eq!(
+        body["data"]["errors"][0]["message"],
+        "More than one group is named 'Support'; refer to it by path"
+    );
+    let groups = unit_of_work.store().groups;
+    let tier = groups.iter().find(|g| g.name == "Tier 1").unwrap();
+    assert_eq!(tier.parent_id, Some(eng_support.id));
+
+    let response = server
+        .post("/api/v1/batch/memberships")
+        .text("staff_email,group_path\njohn@example.com,Engineering/Support/Tier 1\n")
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let memberships = unit_of_work.store().memberships;
+    assert_eq!(memberships.len(), 1);
+    assert_eq!(
+        (memberships[0].staff_id, memberships[0].group_id),
+        (staff.id, tier.id)
+    );
+}
+
+#[tokio::test]
+async fn test_batch_import_memberships_from_multipart_upload() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let group = create_sample_group(Uuid::new_v4(), "Ward A", None);
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![staff.clone()],
+        groups: vec![group.clone()],
+        ..MockStore::default()
+    })
+    .await;
+
+    let csv = "staff_email,group_name\njohn@example.com,Ward A\njohn@example.com,Ward A\nnobody@example.com,Ward A\n";
+    let form = MultipartForm::new().add_part(
+        "file",
+        Part::bytes(csv.as_bytes().to_vec())
+            .file_name("memberships.csv")
+            .mime_type("application/octet-stream"),
+    );
+    let response = server
+        .post("/api/v1/batch/memberships")
+        .multipart(form)
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["success_count"], 1);
+    assert_eq!(body["data"]["error_count"], 2);
+    assert_eq!(body["data"]["errors"][0]["line"], 3);
+    assert_eq!(body["data"]["errors"][1]["line"], 4);
+
+    let memberships = unit_of_work.store().memberships;
+    assert_eq!(memberships.len(), 1);
+    assert_eq!(
+        (memberships[0].staff_id, memberships[0].group_id),
+        (staff.id, group.id)
+    );
+}
+
+#[tokio::test]
+async fn test_batch_import_memberships_with_roles_and_dates() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let ward_a = create_sample_group(Uuid::new_v4(), "Ward A", None);
+    let ward_b = create_sample_group(Uuid::new_v4(), "Ward B", None);
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![staff.clone()],
+        groups: vec![ward_a.clone(), ward_b.clone()],
+        ..MockStore::default()
+    })
+    .await;
+
+    let csv = "staff_email,group_name,role,valid_from,valid_to\n\
+               john@example.com,Ward A,LEAD,2025-01-01,2025-03-31\n\
+               john@example.com,Ward B,,2025-04-01,\n\
+               john@example.com,Ward A,,2025-05-01,2025-04-01\n";
+    let response = server
+        .post("/api/v1/batch/memberships")
+        .text(csv)
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["success_count"], 2);
+    assert_eq!(body["data"]["errors"][0]["line"], 4);
+
+    let memberships = unit_of_work.store().memberships;
+    let terms = |group_id| {
+        let m = memberships.iter().find(|m| m.group_id == group_id).unwrap();
+        (
+            m.role,
+            m.valid_from.map(|d| d.to_string()),
+            m.valid_to.map(|d| d.to_string()),
+        )
+    };
+    assert_eq!(
+        terms(ward_a.id),
+        (
+            MembershipRole::Lead,
+            Some("2025-01-01".to_string()),
+            Some("2025-03-31".to_string())
+        )
+    );
+    assert_eq!(
+        terms(ward_b.id),
+        (MembershipRole::Member, Some("2025-04-01".to_string()), None)
+    );
+}
+
+#[tokio::test]
+async fn test_batch_import_rejects_unreadable_payloads() {
+    let (server, _) = setup_test_server(MockStore::default()).await;
+
+    let response = server
+        .post("/api/v1/batch/groups")
+        .text("<groups/>")
+        .content_type("application/xml")
+        .await;
+    response.assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let response = server
+        .post("/api/v1/batch/groups")
+        .text("[{\"name\": ")
+        .content_type("application/json")
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+fn staff_with_external_id(name: &str, email: &str, external_id: &str) -> Staff {
+    Staff {
+        external_id: Some(external_id.to_string()),
+        ..create_sample_staff(Uuid::new_v4(), name, email)
+    }
+}
+
+#[tokio::test]
+async fn test_sync_staff_reports_each_kind_of_change() {
+    let kept = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let renamed = staff_with_external_id("Jane Smith", "jane@example.com", "E-2");
+    let promoted = create_sample_staff(Uuid::new_v4(), "Bob Brown", "bob@example.com");
+    let departed = create_sample_staff(Uuid::new_v4(), "Alice Green", "alice@example.com");
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![
+            kept.clone(),
+            renamed.clone(),
+            promoted.clone(),
+            departed.clone(),
+        ],
+        ..MockStore::default()
+    })
+    .await;
+
+    let csv = "name,email,position,external_id\n\
+               John Doe,john@example.com,Developer,\n\
+               Jane Jones,jane.jones@example.com,Developer,E-2\n\
+               Bob Brown,bob@example.com,Lead,E-3\n\
+               Carol White,carol@example.com,Nurse,E-4\n";
+    let response = server
+        .post("/api/v1/batch/staff/sync")
+        .text(csv)
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let data = &body["data"];
+    assert_eq!(body["message"], "Staff sync completed");
+    assert_eq!(data["committed"], true);
+    assert_eq!(data["created"][0]["email"], "carol@example.com");
+    assert_eq!(data["unchanged"][0]["id"], kept.id.to_string());
+    assert_eq!(data["updated"][0]["id"], renamed.id.to_string());
+    assert_eq!(
+        data["updated"][0]["changed_fields"],
+        serde_json::json!(["name", "email"])
+    );
+    assert_eq!(data["updated"][1]["id"], promoted.id.to_string());
+    assert_eq!(
+        data["updated"][1]["changed_fields"],
+        serde_json::json!(["position", "external_id"])
+    );
+    assert_eq!(data["deactivated"][0]["id"], departed.id.to_string());
+
+    let staff = unit_of_work.store().staff;
+    let find = |id: Uuid| staff.iter().find(|s| s.id == id).unwrap();
+    assert_eq!(find(renamed.id).email, "jane.jones@example.com");
+    assert_eq!(find(promoted.id).external_id.as_deref(), Some("E-3"));
+    assert_eq!(find(departed.id).status, StaffStatus::Inactive);
+    assert_eq!(staff.len(), 5);
+}
+
+#[tokio::test]
+async fn test_sync_staff_reactivates_and_skips_deactivation_on_errors() {
+    let returning = Staff {
+        status: StaffStatus::Inactive,
+        ..create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com")
+    };
+    let absent = create_sample_staff(Uuid::new_v4(), "Alice Green", "alice@example.com");
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![returning.clone(), absent.clone()],
+        ..MockStore::default()
+    })
+    .await;
+
+    let response = server
+        .post("/api/v1/batch/staff/sync")
+        .json(&serde_json::json!([
+            { "name": "John Doe", "email": "john@example.com", "position": "Developer" },
+            { "name": "John Doe", "email": "john@example.com", "position": "Developer" },
+            { "name": "Unreadable" }
+        ]))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let data = &body["data"];
+    assert_eq!(
+        data["updated"][0]["changed_fields"],
+        serde_json::json!(["status"])
+    );
+    assert_eq!(data["error_count"], 2);
+    assert_eq!(
+        data["errors"][0]["message"],
+        "'john@example.com' is already listed on line 1"
+    );
+    assert_eq!(data["deactivated"], serde_json::json!([]));
+
+    let staff = unit_of_work.store().staff;
+    assert!(staff.iter().all(|s| s.status == StaffStatus::Active));
+}
+
+#[tokio::test]
+async fn test_sync_staff_rejects_conflicting_external_id() {
+    let existing = staff_with_external_id("John Doe", "john@example.com", "E-1");
+    let (server, unit_of_work) = setup_test_server(MockStore {
+        staff: vec![existing],
+        ..MockStore::default()
+    })
+    .await;
+
+    let response = server
+        .post("/api/v1/batch/staff/sync?dry_run=true")
+        .text("name,email,position,external_id\nJohn Doe,john@example.com,Developer,E-9\n")
+        .content_type("text/csv")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["committed"], false);
+    assert_eq!(
+        body["data"]["errors"][0]["message"],
+        "'john@example.com' belongs to staff with external id 'E-1', not 'E-9'"
+    );
+    assert_eq!(
+        unit_of_work.store().staff[0].external_id.as_deref(),
+        Some("E-1")
+    );
+}
@@ -1,11 +1,17 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{NaiveDate, Utc};
 use data_service::api::requests::{
-    CreateGroupRequest, CreateStaffRequest, UpdateGroupRequest, UpdateStaffRequest,
+    AddMemberRequest, CreateGroupRequest, CreateStaffRequest, SortOrder, StaffCursor, StaffFilter,
+    StaffSort, StaffSortField, UpdateGroupRequest, UpdateMembershipRequest, UpdateStaffRequest,
 };
 use data_service::api::AppState;
-use data_service::domain::entities::{GroupMembership, GroupWithMembers, Staff, StaffGroup};
-use data_service::domain::repositories::{GroupRepository, MembershipRepository, StaffRepository};
+use data_service::domain::entities::{
+    GroupMembership, GroupNode, GroupWithMembers, Staff, StaffGroup, StaffPage,
+};
+use data_service::domain::group_tree::GroupTree;
+use data_service::domain::repositories::{
+    GroupRepository, MembershipRepository, StaffRepository, Transaction, UnitOfWork,
+};
 use data_service::infrastructure::redis::RedisPool;
 use shared::{DomainError, DomainResult, PaginationParams, StaffStatus};
 use std::collections::HashMap;
@@ -44,14 +50,26 @@ impl StaffRepository for MockStaffRepository {
             email: request.email,
             position: request.position,
             status: request.status.unwrap_or(StaffStatus::Active),
+            external_id: request.external_id,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         };
         self.staff.write().unwrap().insert(staff.id, staff.clone());
         Ok(staff)
     }
 
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<Staff>> {
+        Ok(self
+            .staff
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|s| s.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn find_by_id_including_deleted(&self, id: Uuid) -> DomainResult<Option<Staff>> {
         Ok(self.staff.read().unwrap().get(&id).cloned())
     }
 
@@ -61,48 +79,91 @@ impl StaffRepository for MockStaffRepository {
             .read()
             .unwrap()
             .values()
-            .find(|s| s.email == email)
+            .find(|s| s.email == email && s.deleted_at.is_none())
             .cloned())
     }
 
-    async fn list(&self, params: PaginationParams) -> DomainResult<(Vec<Staff>, u64)> {
-        let staff = self.staff.read().unwrap();
-        let all: Vec<Staff> = staff.values().cloned().collect();
-        let total = all.len() as u64;
-        let offset = (params.page - 1) * params.page_size;
-        let paginated: Vec<Staff> = all
-            .into_iter()
-            .skip(offset as usize)
-            .take(params.page_size as usize)
-            .collect();
-        Ok((paginated, total))
-    }
-
-    async fn list_by_status(
+    /// Filters like the database, except that group filters are ignored
+    async fn list(
         &self,
-        status: StaffStatus,
+        filter: &StaffFilter,
+        sort: &StaffSort,
         params: PaginationParams,
-    ) -> DomainResult<(Vec<Staff>, u64)> {
+    ) -> DomainResult<StaffPage> {
         let staff = self.staff.read().unwrap();
-        let filtered: Vec<Staff> = staff
+        let q = filter.q.as_deref().map(str::to_lowercase);
+        let mut all: Vec<Staff> = staff
             .values()
-            .filter(|s| s.status == status)
+            .filter(|s| filter.include_deleted || s.deleted_at.is_none())
+            .filter(|s| {
+                filter
+                    .status
+                    .as_ref()
+                    .is_none_or(|status| &s.status == status)
+            })
+            .filter(|s| {
+                filter
+                    .position
+                    .as_ref()
+                    .is_none_or(|position| s.position.eq_ignore_ascii_case(position))
+            })
+            .filter(|s| {
+                q.as_ref().is_none_or(|q| {
+                    s.name.to_lowercase().contains(q) || s.email.to_lowercase().contains(q)
+                })
+            })
             .cloned()
             .collect();
-        let total = filtered.len() as u64;
-        let offset = (params.page - 1) * params.page_size;
-        let paginated: Vec<Staff> = filtered
-            .into_iter()
-            .skip(offset as usize)
+        let total = all.len() as u64;
+
+        all.sort_by(|a, b| {
+            let ordering = match sort.sort {
+                StaffSortField::Name => a.name.cmp(&b.name),
+                StaffSortField::Email => a.email.cmp(&b.email),
+                StaffSortField::Position => a.position.cmp(&b.position),
+                StaffSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+                StaffSortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+            }
+            .then(a.id.cmp(&b.id));
+            match sort.order() {
+                SortOrder::Asc => ordering,
+                SortOrder::Desc => ordering.reverse(),
+            }
+        });
+
+        let skip = match &sort.cursor {
+            Some(cursor) => {
+                let cursor = StaffCursor::decode(cursor)
+                    .ok_or_else(|| DomainError::InvalidInput("Invalid cursor".to_string()))?;
+                all.iter()
+                    .position(|s| s.id == cursor.id)
+                    .map_or(all.len(), |index| index + 1)
+            }
+            None => ((params.page - 1) * params.page_size) as usize,
+        };
+        let rest: Vec<Staff> = all.into_iter().skip(skip).collect();
+        let page: Vec<Staff> = rest
+            .iter()
             .take(params.page_size as usize)
+            .cloned()
             .collect();
-        Ok((paginated, total))
+        let next_cursor = (rest.len() > page.len())
+            .then(|| page.last())
+            .flatten()
+            .map(|last| StaffCursor::new(last, sort.sort).encode());
+
+        Ok(StaffPage {
+            staff: page,
+            total,
+            next_cursor,
+        })
     }
 
     async fn update(&self, id: Uuid, request: UpdateStaffRequest) -> DomainResult<Staff> {
         let mut staff_map = self.staff.write().unwrap();
         let staff = staff_map
             .get_mut(&id)
+            .filter(|s| s.deleted_at.is_none())
             .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))?;
 
         if let Some(name) = request.name {
@@ -117,26 +178,57 @@ impl StaffRepository for MockStaffRepository {
         if let Some(status) = request.status {
             staff.status = status;
         }
+        if let Some(external_id) = request.external_id {
+            staff.external_id = external_id;
+        }
         staff.updated_at = Utc::now();
 
         Ok(staff.clone())
     }
 
     async fn delete(&self, id: Uuid) -> DomainResult<()> {
-        self.staff
-            .write()
-            .unwrap()
-            .remove(&id)
+        let mut staff_map = self.staff.write().unwrap();
+        let staff = staff_map
+            .get_mut(&id)
+            .filter(|s| s.deleted_at.is_none())
             .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))?;
+        staff.deleted_at = Some(Utc::now());
         Ok(())
     }
 
+    async fn restore(&self, id: Uuid) -> DomainResult<Staff> {
+        let mut staff_map = self.staff.write().unwrap();
+        let staff = staff_map
+            .get_mut(&id)
+            .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))?;
+        staff.deleted_at = None;
+        Ok(staff.clone())
+    }
+
+    async fn purge(&self, id: Uuid) -> DomainResult<()> {
+        let mut staff_map = self.staff.write().unwrap();
+        match staff_map.get(&id) {
+            None => Err(DomainError::NotFound(format!(
+                "Staff with id {} not found",
+                id
+            ))),
+            Some(staff) if staff.deleted_at.is_none() => Err(DomainError::InvalidInput(format!(
+                "Staff with id {} must be deleted before it is purged",
+                id
+            ))),
+            Some(_) => {
+                staff_map.remove(&id);
+                Ok(())
+            }
+        }
+    }
+
     async fn find_by_group_id(&self, _group_id: Uuid) -> DomainResult<Vec<Staff>> {
         // Simplified: return all active staff for testing
         let staff = self.staff.read().unwrap();
         Ok(staff
             .values()
-            .filter(|s| s.status == StaffStatus::Active)
+            .filter(|s| s.status == StaffStatus::Active && s.deleted_at.is_none())
             .cloned()
             .collect())
     }
@@ -163,11 +255,25 @@ impl MockGroupRepository {
         }
         repo
     }
+
+    /// Groups that are not deleted
+    fn live(&self) -> Vec<StaffGroup> {
+        self.groups
+            .read()
+            .unwrap()
+            .values()
+            .filter(|g| g.deleted_at.is_none())
+            .cloned()
+            .collect()
+    }
 }
 
 #[async_trait]
 impl GroupRepository for MockGroupRepository {
     async fn create(&self, request: CreateGroupRequest) -> DomainResult<StaffGroup> {
+        GroupTree::new(self.live())
+            .check_new_group(&request.name, request.parent_id)
+            .map_err(DomainError::InvalidInput)?;
         let now = Utc::now();
         let group = StaffGroup {
             id: Uuid::new_v4(),
@@ -175,18 +281,37 @@ impl GroupRepository for MockGroupRepository {
             parent_id: request.parent_id,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
         };
         self.groups.write().unwrap().insert(group.id, group.clone());
         Ok(group)
     }
 
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<StaffGroup>> {
+        Ok(self
+            .groups
+            .read()
+            .unwrap()
+            .get(&id)
+            .filter(|g| g.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn find_by_id_including_deleted(&self, id: Uuid) -> DomainResult<Option<StaffGroup>> {
         Ok(self.groups.read().unwrap().get(&id).cloned())
     }
 
-    async fn list(&self, params: PaginationParams) -> DomainResult<(Vec<StaffGroup>, u64)> {
+    async fn list(
+        &self,
+        params: PaginationParams,
+        include_deleted: bool,
+    ) -> DomainResult<(Vec<StaffGroup>, u64)> {
         let groups = self.groups.read().unwrap();
-        let all: Vec<StaffGroup> = groups.values().cloned().collect();
+        let all: Vec<StaffGroup> = groups
+            .values()
+            .filter(|g| include_deleted || g.deleted_at.is_none())
+            .cloned()
+            .collect();
         let total = all.len() as u64;
         let offset = (params.page - 1) * params.page_size;
         let paginated: Vec<StaffGroup> = all
@@ -198,50 +323,117 @@ impl GroupRepository for MockGroupRepository {
     }
 
     async fn update(&self, id: Uuid, request: UpdateGroupRequest) -> DomainResult<StaffGroup> {
+        let tree = GroupTree::new(self.live());
         let mut groups_map = self.groups.write().unwrap();
         let group = groups_map
             .get_mut(&id)
+            .filter(|g| g.deleted_at.is_none())
             .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
 
-        if let Some(name) = request.name {
-            group.name = name;
-        }
-        if let Some(parent_id) = request.parent_id {
-            group.parent_id = Some(parent_id);
-        }
+        let name = request.name.unwrap_or_else(|| group.name.clone());
+        let parent_id = request.parent_id.unwrap_or(group.parent_id);
+        tree.check_placement(id, &name, parent_id)
+            .map_err(DomainError::InvalidInput)?;
+        group.name = name;
+        group.parent_id = parent_id;
         group.updated_at = Utc::now();
 
         Ok(group.clone())
     }
 
     async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        if self.find_by_id(id).await?.is_none() {
+            return Err(DomainError::NotFound(format!(
+                "Group with id {} not found",
+                id
+            )));
+        }
+        let tree = GroupTree::new(self.live());
+        let now = Utc::now();
+        let mut groups = self.groups.write().unwrap();
+        for group in groups.values_mut() {
+            if group.id == id || tree.ancestors(group.id).iter().any(|a| a.id == id) {
+                group.deleted_at = Some(now);
+            }
+        }
+        Ok(())
+    }
+
+    async fn restore(&self, id: Uuid) -> DomainResult<StaffGroup> {
+        let group = self
+            .find_by_id_including_deleted(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
+        let Some(deleted_at) = group.deleted_at else {
+            return Ok(group);
+        };
+        let tree = GroupTree::new(self.live());
+        if let Some(parent_id) = group.parent_id.filter(|p| tree.get(*p).is_none()) {
+            return Err(DomainError::InvalidInput(format!(
+                "Parent group with id {} is deleted; restore it first",
+                parent_id
+            )));
+        }
+        tree.check_placement(id, &group.name, group.parent_id)
+            .map_err(DomainError::InvalidInput)?;
+
+        let deleted_with_it = GroupTree::new(
+            self.groups
+                .read()
+                .unwrap()
+                .values()
+                .filter(|g| g.deleted_at == Some(deleted_at))
+                .cloned()
+                .collect(),
+        );
+        let mut groups = self.groups.write().unwrap();
+        for group in groups.values_mut() {
+            if group.id == id
+                || deleted_with_it
+                    .ancestors(group.id)
+                    .iter()
+                    .any(|a| a.id == id)
+            {
+                group.deleted_at = None;
+            }
+        }
+        Ok(groups[&id].clone())
+    }
+
+    async fn purge(&self, id: Uuid) -> DomainResult<()> {
+        let group = self
+            .find_by_id_including_deleted(id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
+        if group.deleted_at.is_none() {
+            return Err(DomainError::InvalidInput(format!(
+                "Group with id {} must be deleted before it is purged",
+                id
+            )));
+        }
+        let tree = GroupTree::new(self.groups.read().unwrap().values().cloned().collect());
         self.groups
             .write()
             .unwrap()
-            .remove(&id)
-            .ok_or_else(|| DomainError::NotFound(format!("Group with id {} not found", id)))?;
+            .retain(|gid, _| *gid != id && !tree.ancestors(*gid).iter().any(|a| a.id == id));
         Ok(())
     }
 
     async fn find_by_name(&self, name: &str) -> DomainResult<Option<StaffGroup>> {
-        Ok(self
-            .groups
-            .read()
-            .unwrap()
-            .values()
-            .find(|g| g.name == name)
-            .cloned())
+        Ok(self.live().into_iter().find(|g| g.name == name))
     }
 
     async fn get_resolved_members(
         &self,
         group_id: Uuid,
+        _from: NaiveDate,
+        _to: NaiveDate,
     ) -> DomainResult<(Vec<GroupWithMembers>, u64)> {
-        let groups = self.groups.read().unwrap();
-        if let Some(group) = groups.get(&group_id) {
+        if let Some(group) = self.find_by_id(group_id).await? {
             Ok((
                 vec![GroupWithMembers {
-                    group: group.clone(),
+                    path: vec![group.id],
+                    group,
                     members: vec![],
                 }],
                 0,
@@ -253,6 +445,74 @@ impl GroupRepository for MockGroupRepository {
             )))
         }
     }
+
+    async fn find_ancestor_ids(&self, group_ids: &[Uuid]) -> DomainResult<Vec<Uuid>> {
+        let tree = GroupTree::new(self.live());
+        let mut ids: Vec<Uuid> = group_ids
+            .iter()
+            .filter(|id| tree.get(**id).is_some())
+            .flat_map(|id| tree.ancestors(*id).into_iter().map(|g| g.id).chain([*id]))
+            .collect();
+        ids.sort();
+        ids.dedup();
+        Ok(ids)
+    }
+
+    async fn get_ancestors(&self, group_id: Uuid) -> DomainResult<Vec<StaffGroup>> {
+        let tree = GroupTree::new(self.live());
+        let Some(group) = tree.get(group_id) else {
+            return Ok(vec![]);
+        };
+        let mut groups: Vec<StaffGroup> = tree.ancestors(group_id).into_iter().cloned().collect();
+        groups.reverse();
+        groups.push(group.clone());
+        Ok(groups)
+    }
+
+    /// Lists the subtree breadth first; the mock has no members to count
+    async fn get_subtree(
+        &self,
+        root: Option<Uuid>,
+        max_depth: Option<u32>,
+        _include_inactive: bool,
+    ) -> DomainResult<Vec<GroupNode>> {
+        let groups = self.live();
+        let children_of = |parent: Option<Uuid>| {
+            let mut children: Vec<StaffGroup> = groups
+                .iter()
+                .filter(|g| g.parent_id == parent)
+                .cloned()
+                .collect();
+            children.sort_by(|a, b| a.name.cmp(&b.name));
+            children
+        };
+
+        let mut level = match root {
+            Some(id) => groups.iter().filter(|g| g.id == id).cloned().collect(),
+            None => children_of(None),
+        };
+        let mut nodes = Vec::new();
+        let mut depth = 0;
+        while !level.is_empty() {
+            let mut next = Vec::new();
+            for group in level {
+                let children = children_of(Some(group.id));
+                let child_count = children.len() as i64;
+                if max_depth.is_none_or(|max| depth < max as i32) {
+                    next.extend(children);
+                }
+                nodes.push(GroupNode {
+                    group,
+                    depth,
+                    member_count: 0,
+                    child_count,
+                });
+            }
+            level = next;
+            depth += 1;
+        }
+        Ok(nodes)
+    }
 }
 
 /// Mock Membership Repository for testing
@@ -265,31 +525,358 @@ impl MockMembershipRepository {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub fn with_memberships(membership_list: Vec<GroupMembership>) -> Self {
+        Self {
+            memberships: RwLock::new(membership_list),
+        }
+    }
 }
 
 #[async_trait]
 impl MembershipRepository for MockMembershipRepository {
-    async fn add_member(&self, staff_id: Uuid, group_id: Uuid) -> DomainResult<GroupMembership> {
+    async fn add_member(
+        &self,
+        group_id: Uuid,
+        request: AddMemberRequest,
+    ) -> DomainResult<GroupMembership> {
+        check_overlap(
+            &self.memberships.read().unwrap(),
+            None,
+            request.staff_id,
+            group_id,
+            request.valid_from,
+            request.valid_to,
+        )?;
         let membership = GroupMembership {
             id: Uuid::new_v4(),
-            staff_id,
+            staff_id: request.staff_id,
             group_id,
             created_at: Utc::now(),
+            role: request.role,
+            valid_from: request.valid_from,
+            valid_to: request.valid_to,
         };
         self.memberships.write().unwrap().push(membership.clone());
         Ok(membership)
     }
 
-    async fn remove_member(&self, staff_id: Uuid, group_id: Uuid) -> DomainResult<()> {
+    async fn find_memberships(
+        &self,
+        staff_id: Uuid,
+        group_id: Uuid,
+    ) -> DomainResult<Vec<GroupMembership>> {
+        let mut memberships: Vec<GroupMembership> = self
+            .memberships
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| m.staff_id == staff_id && m.group_id == group_id)
+            .cloned()
+            .collect();
+        memberships.sort_by_key(|m| m.valid_from);
+        Ok(memberships)
+    }
+
+    async fn update_member(
+        &self,
+        membership_id: Uuid,
+        request: UpdateMembershipRequest,
+    ) -> DomainResult<GroupMembership> {
+        let mut memberships = self.memberships.write().unwrap();
+        update_membership(&mut memberships, membership_id, &request)
+    }
+
+    async fn remove_member(
+        &self,
+        staff_id: Uuid,
+        group_id: Uuid,
+        membership_id: Option<Uuid>,
+    ) -> DomainResult<()> {
         let mut memberships = self.memberships.write().unwrap();
         let initial_len = memberships.len();
-        memberships.retain(|m| !(m.staff_id == staff_id && m.group_id == group_id));
+        memberships.retain(|m| {
+            !(m.staff_id == staff_id
+                && m.group_id == group_id
+                && membership_id.is_none_or(|id| m.id == id))
+        });
         if memberships.len() == initial_len {
             Err(DomainError::NotFound("Membership not found".to_string()))
         } else {
             Ok(())
         }
     }
+
+    async fn find_group_ids_by_staff(&self, staff_id: Uuid) -> DomainResult<Vec<Uuid>> {
+        Ok(self
+            .memberships
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|m| m.staff_id == staff_id)
+            .map(|m| m.group_id)
+            .collect())
+    }
+}
+
+/// Refuse a period overlapping another membership of the same staff and
+/// group, like the exclusion constraint on `group_memberships`
+fn check_overlap(
+    memberships: &[GroupMembership],
+    except: Option<Uuid>,
+    staff_id: Uuid,
+    group_id: Uuid,
+    valid_from: Option<NaiveDate>,
+    valid_to: Option<NaiveDate>,
+) -> DomainResult<()> {
+    let overlapping = memberships.iter().any(|m| {
+        Some(m.id) != except
+            && m.staff_id == staff_id
+            && m.group_id == group_id
+            && m.valid_from
+                .is_none_or(|from| valid_to.is_none_or(|to| from <= to))
+            && m.valid_to
+                .is_none_or(|to| valid_from.is_none_or(|from| from <= to))
+    });
+    if overlapping {
+        return Err(DomainError::InvalidInput(
+            "Staff is already a member of this group during part of this period".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn update_membership(
+    memberships: &mut [GroupMembership],
+    membership_id: Uuid,
+    request: &UpdateMembershipRequest,
+) -> DomainResult<GroupMembership> {
+    let (staff_id, group_id) = memberships
+        .iter()
+        .find(|m| m.id == membership_id)
+        .map(|m| (m.staff_id, m.group_id))
+        .ok_or_else(|| DomainError::NotFound("Membership not found".to_string()))?;
+    check_overlap(
+        memberships,
+        Some(membership_id),
+        staff_id,
+        group_id,
+        request.valid_from,
+        request.valid_to,
+    )?;
+    let membership = memberships
+        .iter_mut()
+        .find(|m| m.id == membership_id)
+        .expect("membership was found above");
+    membership.role = request.role;
+    membership.valid_from = request.valid_from;
+    membership.valid_to = request.valid_to;
+    Ok(membership.clone())
+}
+
+/// Everything a mock transaction can write
+#[derive(Debug, Default, Clone)]
+pub struct MockStore {
+    pub staff: Vec<Staff>,
+    pub groups: Vec<StaffGroup>,
+    pub memberships: Vec<GroupMembership>,
+}
+
+/// Mock Unit of Work: each transaction works on a copy of the store and
+/// writes it back on commit
+#[derive(Default)]
+pub struct MockUnitOfWork {
+    store: Arc<RwLock<MockStore>>,
+}
+
+impl MockUnitOfWork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_store(store: MockStore) -> Self {
+        Self {
+            store: Arc::new(RwLock::new(store)),
+        }
+    }
+
+    /// Committed state
+    pub fn store(&self) -> MockStore {
+        self.store.read().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for MockUnitOfWork {
+    async fn begin(&self) -> DomainResult<Box<dyn Transaction>> {
+        Ok(Box::new(MockTransaction {
+            working: self.store(),
+            store: self.store.clone(),
+        }))
+    }
+}
+
+struct MockTransaction {
+    store: Arc<RwLock<MockStore>>,
+    working: MockStore,
+}
+
+#[async_trait]
+impl Transaction for MockTransaction {
+    async fn create_staff(&mut self, request: &CreateStaffRequest) -> DomainResult<Staff> {
+        if self.working.staff.iter().any(|s| s.email == request.email) {
+            return Err(DomainError::DatabaseError(
+                "duplicate key value violates unique constraint \"staff_email_key\"".to_string(),
+            ));
+        }
+        let now = Utc::now();
+        let staff = Staff {
+            id: Uuid::new_v4(),
+            name: request.name.clone(),
+            email: request.email.clone(),
+            position: request.position.clone(),
+            status: request.status.clone().unwrap_or(StaffStatus::Active),
+            external_id: request.external_id.clone(),
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        self.working.staff.push(staff.clone());
+        Ok(staff)
+    }
+
+    async fn update_staff(
+        &mut self,
+        id: Uuid,
+        request: &UpdateStaffRequest,
+    ) -> DomainResult<Staff> {
+        if let Some(email) = &request.email {
+            if self
+                .working
+                .staff
+                .iter()
+                .any(|s| s.id != id && &s.email == email)
+            {
+                return Err(DomainError::DatabaseError(
+                    "duplicate key value violates unique constraint \"staff_email_key\""
+                        .to_string(),
+                ));
+            }
+        }
+        let staff = self
+            .working
+            .staff
+            .iter_mut()
+            .find(|s| s.id == id)
+            .ok_or_else(|| DomainError::NotFound(format!("Staff with id {} not found", id)))?;
+
+        if let Some(name) = &request.name {
+            staff.name = name.clone();
+        }
+        if let Some(email) = &request.email {
+            staff.email = email.clone();
+        }
+        if let Some(position) = &request.position {
+            staff.position = position.clone();
+        }
+        if let Some(status) = &request.status {
+            staff.status = status.clone();
+        }
+        if let Some(external_id) = &request.external_id {
+            staff.external_id = external_id.clone();
+        }
+        staff.updated_at = Utc::now();
+
+        Ok(staff.clone())
+    }
+
+    async fn list_staff(&mut self) -> DomainResult<Vec<Staff>> {
+        Ok(self.working.staff.clone())
+    }
+
+    async fn find_staff_by_email(&mut self, email: &str) -> DomainResult<Option<Staff>> {
+        Ok(self
+            .working
+            .staff
+            .iter()
+            .find(|s| s.email == email)
+            .cloned())
+    }
+
+    async fn create_group(&mut self, request: &CreateGroupRequest) -> DomainResult<StaffGroup> {
+        GroupTree::new(self.working.groups.clone())
+            .check_new_group(&request.name, request.parent_id)
+            .map_err(DomainError::InvalidInput)?;
+        let group = create_sample_group(Uuid::new_v4(), &request.name, request.parent_id);
+        self.working.groups.push(group.clone());
+        Ok(group)
+    }
+
+    async fn list_groups(&mut self) -> DomainResult<Vec<StaffGroup>> {
+        Ok(self.working.groups.clone())
+    }
+
+    async fn set_group_parent(&mut self, group_id: Uuid, parent_id: Uuid) -> DomainResult<()> {
+        let tree = GroupTree::new(self.working.groups.clone());
+        if let Some(group) = tree.get(group_id) {
+            tree.check_placement(group_id, &group.name, Some(parent_id))
+                .map_err(DomainError::InvalidInput)?;
+        }
+
+        let group = self
+            .working
+            .groups
+            .iter_mut()
+            .find(|g| g.id == group_id)
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Group with id {} not found", group_id))
+            })?;
+        group.parent_id = Some(parent_id);
+        Ok(())
+    }
+
+    async fn add_member(
+        &mut self,
+        group_id: Uuid,
+        request: &AddMemberRequest,
+    ) -> DomainResult<GroupMembership> {
+        check_overlap(
+            &self.working.memberships,
+            None,
+            request.staff_id,
+            group_id,
+            request.valid_from,
+            request.valid_to,
+        )?;
+        let membership = GroupMembership {
+            id: Uuid::new_v4(),
+            staff_id: request.staff_id,
+            group_id,
+            created_at: Utc::now(),
+            role: request.role,
+            valid_from: request.valid_from,
+            valid_to: request.valid_to,
+        };
+        self.working.memberships.push(membership.clone());
+        Ok(membership)
+    }
+
+    async fn update_member(
+        &mut self,
+        membership_id: Uuid,
+        request: &UpdateMembershipRequest,
+    ) -> DomainResult<GroupMembership> {
+        update_membership(&mut self.working.memberships, membership_id, request)
+    }
+
+    async fn list_memberships(&mut self) -> DomainResult<Vec<GroupMembership>> {
+        Ok(self.working.memberships.clone())
+    }
+
+    async fn commit(self: Box<Self>) -> DomainResult<()> {
+        *self.store.write().unwrap() = self.working;
+        Ok(())
+    }
 }
 
 /// Mock Redis Pool for testing (no-op implementation)
@@ -300,6 +887,36 @@ pub async fn create_mock_redis_pool() -> RedisPool {
     redis::aio::ConnectionManager::new(client).await.unwrap()
 }
 
+/// Period the placeholder resolved members are cached for
+const CACHED_PERIOD: &str = "2025-01-01:2025-01-31";
+
+/// Put a placeholder resolved members list in the cache for each group
+pub async fn cache_resolved_members(redis_pool: &RedisPool, group_ids: &[Uuid]) {
+    let mut redis_conn = redis_pool.clone();
+    for group_id in group_ids {
+        shared::set_cached_field(
+            &mut redis_conn,
+            &shared::cache_keys::resolved_members(*group_id),
+            CACHED_PERIOD,
+            &"cached",
+            shared::cache_ttl::RESOLVED_MEMBERS,
+        )
+        .await;
+    }
+}
+
+/// Whether the group's resolved members are still cached
+pub async fn is_resolved_members_cached(redis_pool: &RedisPool, group_id: Uuid) -> bool {
+    let mut redis_conn = redis_pool.clone();
+    shared::get_cached_field::<String>(
+        &mut redis_conn,
+        &shared::cache_keys::resolved_members(group_id),
+        CACHED_PERIOD,
+    )
+    .await
+    .is_some()
+}
+
 /// Create test app state with mock repositories
 pub fn create_test_app_state(
     staff_repo: Arc<dyn StaffRepository>,
@@ -307,7 +924,13 @@ pub fn create_test_app_state(
     membership_repo: Arc<dyn MembershipRepository>,
     redis_pool: RedisPool,
 ) -> AppState {
-    AppState::new(staff_repo, group_repo, membership_repo, redis_pool)
+    AppState::new(
+        staff_repo,
+        group_repo,
+        membership_repo,
+        Arc::new(MockUnitOfWork::new()),
+        redis_pool,
+    )
 }
 
 /// Create a sample staff for testing
@@ -319,8 +942,10 @@ pub fn create_sample_staff(id: Uuid, name: &str, email: &str) -> Staff {
         email: email.to_string(),
         position: "Developer".to_string(),
         status: StaffStatus::Active,
+        external_id: None,
         created_at: now,
         updated_at: now,
+        deleted_at: None,
     }
 }
 
@@ -333,5 +958,6 @@ pub fn create_sample_group(id: Uuid, name: &str, parent_id: Option<Uuid>) -> Sta
         parent_id,
         created_at: now,
         updated_at: now,
+        deleted_at: None,
     }
 }
@@ -6,8 +6,8 @@ mod common;
 use axum::http::StatusCode;
 use axum_test::TestServer;
 use common::{
-    create_mock_redis_pool, create_sample_group, create_test_app_state, MockGroupRepository,
-    MockMembershipRepository, MockStaffRepository,
+    cache_resolved_members, create_mock_redis_pool, create_sample_group, create_test_app_state,
+    is_resolved_members_cached, MockGroupRepository, MockMembershipRepository, MockStaffRepository,
 };
 use data_service::api::create_router;
 use serde_json::json;
@@ -76,6 +76,36 @@ async fn test_create_group_with_parent() {
     assert_eq!(body["data"]["parent_id"], parent_id.to_string());
 }
 
+#[tokio::test]
+async fn test_create_group_rejects_sibling_name() {
+    let parent = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let child = create_sample_group(Uuid::new_v4(), "Backend", Some(parent.id));
+    let server = setup_test_server_with_groups(vec![parent.clone(), child]).await;
+
+    let response = server
+        .post("/api/v1/groups")
+        .json(&json!({ "name": "backend", "parent_id": parent.id }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.text(),
+        "'Engineering' already has a group named 'backend'"
+    );
+
+    let response = server
+        .post("/api/v1/groups")
+        .json(&json!({ "name": "Engineering" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    // The same name is fine under another parent
+    let response = server
+        .post("/api/v1/groups")
+        .json(&json!({ "name": "Backend" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+}
+
 #[tokio::test]
 async fn test_get_group_by_id_success() {
     let group_id = Uuid::new_v4();
@@ -181,6 +211,112 @@ async fn test_update_group_not_found() {
     response.assert_status(StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_update_group_keeps_or_clears_parent() {
+    let parent = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let child = create_sample_group(Uuid::new_v4(), "Backend", Some(parent.id));
+    let server = setup_test_server_with_groups(vec![parent.clone(), child.clone()]).await;
+
+    let response = server
+        .put(&format!("/api/v1/groups/{}", child.id))
+        .json(&json!({ "name": "Platform" }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["parent_id"], parent.id.to_string());
+
+    let response = server
+        .put(&format!("/api/v1/groups/{}", child.id))
+        .json(&json!({ "parent_id": null }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["name"], "Platform");
+    assert!(body["data"]["parent_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_update_group_rejects_cycles() {
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let child = create_sample_group(Uuid::new_v4(), "Backend", Some(root.id));
+    let grandchild = create_sample_group(Uuid::new_v4(), "API", Some(child.id));
+    let server =
+        setup_test_server_with_groups(vec![root.clone(), child.clone(), grandchild.clone()]).await;
+
+    let response = server
+        .put(&format!("/api/v1/groups/{}", root.id))
+        .json(&json!({ "parent_id": root.id }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(response.text(), "A group cannot be its own parent");
+
+    let response = server
+        .put(&format!("/api/v1/groups/{}", root.id))
+        .json(&json!({ "parent_id": grandchild.id }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server.get(&format!("/api/v1/groups/{}", root.id)).await;
+    let body: serde_json::Value = response.json();
+    assert!(body["data"]["parent_id"].is_null());
+}
+
+#[tokio::test]
+async fn test_move_group_subtree() {
+    let engineering = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let sales = create_sample_group(Uuid::new_v4(), "Sales", None);
+    let support = create_sample_group(Uuid::new_v4(), "Support", Some(engineering.id));
+    let sales_support = create_sample_group(Uuid::new_v4(), "Support", Some(sales.id));
+    let tier = create_sample_group(Uuid::new_v4(), "Tier 1", Some(support.id));
+    let server = setup_test_server_with_groups(vec![
+        engineering.clone(),
+        sales.clone(),
+        support.clone(),
+        sales_support,
+        tier.clone(),
+    ])
+    .await;
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/move", support.id))
+        .json(&json!({ "parent_id": sales.id }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert_eq!(
+        response.text(),
+        "'Sales' already has a group named 'Support'"
+    );
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/move", support.id))
+        .json(&json!({ "parent_id": Uuid::new_v4() }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/move", tier.id))
+        .json(&json!({ "parent_id": sales.id }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["message"], "Group moved successfully");
+    assert_eq!(body["data"]["parent_name"], "Sales");
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/move", support.id))
+        .json(&json!({}))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body["data"]["parent_id"].is_null());
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/move", Uuid::new_v4()))
+        .json(&json!({ "parent_id": null }))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 async fn test_delete_group_success() {
     let group_id = Uuid::new_v4();
@@ -196,6 +332,92 @@ async fn test_delete_group_success() {
     get_response.assert_status(StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_delete_group_takes_its_subgroups_and_restore_brings_them_back() {
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let team = create_sample_group(Uuid::new_v4(), "Backend", Some(root.id));
+    let squad = create_sample_group(Uuid::new_v4(), "API", Some(team.id));
+    let server =
+        setup_test_server_with_groups(vec![root.clone(), team.clone(), squad.clone()]).await;
+
+    let response = server.delete(&format!("/api/v1/groups/{}", team.id)).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    server
+        .get(&format!("/api/v1/groups/{}", squad.id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    let body: serde_json::Value = server.get("/api/v1/groups").await.json();
+    assert_eq!(body["total"], 1);
+    let body: serde_json::Value = server
+        .get("/api/v1/groups?include_deleted=true")
+        .await
+        .json();
+    assert_eq!(body["total"], 3);
+
+    // A deleted parent has to come back first
+    let response = server
+        .post(&format!("/api/v1/groups/{}/restore", squad.id))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/restore", team.id))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body["data"]["deleted_at"].is_null());
+
+    server
+        .get(&format!("/api/v1/groups/{}", squad.id))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_create_group_under_deleted_parent_is_rejected() {
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let server = setup_test_server_with_groups(vec![root.clone()]).await;
+
+    server
+        .delete(&format!("/api/v1/groups/{}", root.id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+
+    let response = server
+        .post("/api/v1/groups")
+        .json(&json!({ "name": "Backend", "parent_id": root.id }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_purge_group_requires_a_soft_delete_first() {
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let team = create_sample_group(Uuid::new_v4(), "Backend", Some(root.id));
+    let server = setup_test_server_with_groups(vec![root.clone(), team.clone()]).await;
+
+    let response = server
+        .delete(&format!("/api/v1/admin/groups/{}", root.id))
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    server
+        .delete(&format!("/api/v1/groups/{}", root.id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    let response = server
+        .delete(&format!("/api/v1/admin/groups/{}", root.id))
+        .await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let body: serde_json::Value = server
+        .get("/api/v1/groups?include_deleted=true")
+        .await
+        .json();
+    assert_eq!(body["total"], 0);
+}
+
 #[tokio::test]
 async fn test_delete_group_not_found() {
     let server = setup_test_server().await;
@@ -221,6 +443,30 @@ async fn test_get_resolved_members_success() {
     response.assert_status_ok();
     let body: serde_json::Value = response.json();
     assert_eq!(body["message"], "Resolved members retrieved successfully");
+    assert_eq!(body["data"][0]["path"], serde_json::json!([group_id]));
+}
+
+#[tokio::test]
+async fn test_get_resolved_members_validates_the_period() {
+    let group_id = Uuid::new_v4();
+    let group = create_sample_group(group_id, "Test Group", None);
+    let server = setup_test_server_with_groups(vec![group]).await;
+    let url = format!("/api/v1/groups/{}/resolved-members", group_id);
+
+    for query in [
+        "as_of=2025-01-06&from=2025-01-06&to=2025-02-02",
+        "from=2025-01-06",
+        "to=2025-02-02",
+        "from=2025-02-02&to=2025-01-06",
+    ] {
+        let response = server.get(&format!("{}?{}", url, query)).await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    for query in ["as_of=2025-01-06", "from=2025-01-06&to=2025-02-02"] {
+        let response = server.get(&format!("{}?{}", url, query)).await;
+        response.assert_status_ok();
+    }
 }
 
 #[tokio::test]
@@ -237,3 +483,129 @@ async fn test_get_resolved_members_not_found() {
 
     response.assert_status(StatusCode::NOT_FOUND);
 }
+
+fn sample_hierarchy() -> Vec<data_service::domain::entities::StaffGroup> {
+    let engineering = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let backend = create_sample_group(Uuid::new_v4(), "Backend", Some(engineering.id));
+    let frontend = create_sample_group(Uuid::new_v4(), "Frontend", Some(engineering.id));
+    let api = create_sample_group(Uuid::new_v4(), "API", Some(backend.id));
+    let sales = create_sample_group(Uuid::new_v4(), "Sales", None);
+    vec![engineering, backend, frontend, api, sales]
+}
+
+#[tokio::test]
+async fn test_get_group_ancestors_as_breadcrumb() {
+    let groups = sample_hierarchy();
+    let api_id = groups[3].id;
+    let server = setup_test_server_with_groups(groups).await;
+
+    let response = server
+        .get(&format!("/api/v1/groups/{}/ancestors", api_id))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["path"], "Engineering/Backend/API");
+    let names: Vec<&str> = body["data"]["groups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| g["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, vec!["Engineering", "Backend", "API"]);
+    assert_eq!(body["data"]["groups"][2]["id"], api_id.to_string());
+
+    let response = server
+        .get(&format!("/api/v1/groups/{}/ancestors", Uuid::new_v4()))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_group_tree_nests_descendants() {
+    let groups = sample_hierarchy();
+    let engineering_id = groups[0].id;
+    let server = setup_test_server_with_groups(groups).await;
+
+    let response = server
+        .get(&format!("/api/v1/groups/{}/tree", engineering_id))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let tree = &body["data"];
+    assert_eq!(tree["name"], "Engineering");
+    assert_eq!(tree["child_count"], 2);
+    assert_eq!(tree["children"][0]["name"], "Backend");
+    assert_eq!(tree["children"][0]["children"][0]["name"], "API");
+    assert_eq!(tree["children"][0]["children"][0]["depth"], 2);
+    assert_eq!(tree["children"][1]["name"], "Frontend");
+
+    let response = server
+        .get(&format!(
+            "/api/v1/groups/{}/tree?max_depth=1",
+            engineering_id
+        ))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let backend = &body["data"]["children"][0];
+    assert_eq!(backend["child_count"], 1);
+    assert_eq!(backend["children"], json!([]));
+
+    let response = server
+        .get(&format!("/api/v1/groups/{}/tree", Uuid::new_v4()))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_get_group_forest() {
+    let server = setup_test_server_with_groups(sample_hierarchy()).await;
+
+    let response = server.get("/api/v1/groups/tree?max_depth=0").await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["total"], 2);
+    assert_eq!(body["data"][0]["name"], "Engineering");
+    assert_eq!(body["data"][0]["children"], json!([]));
+    assert_eq!(body["data"][1]["name"], "Sales");
+    assert_eq!(body["data"][1]["child_count"], 0);
+}
+
+#[tokio::test]
+async fn test_moving_a_group_invalidates_old_and_new_ancestors() {
+    let groups = sample_hierarchy();
+    let (engineering, backend, frontend, api, sales) = (
+        groups[0].id,
+        groups[1].id,
+        groups[2].id,
+        groups[3].id,
+        groups[4].id,
+    );
+    let server = setup_test_server_with_groups(groups).await;
+    let redis_pool = create_mock_redis_pool().await;
+    cache_resolved_members(&redis_pool, &[engineering, backend, frontend, api, sales]).await;
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/move", backend))
+        .json(&json!({ "parent_id": sales }))
+        .await;
+    response.assert_status_ok();
+
+    for stale in [engineering, backend, sales] {
+        assert!(!is_resolved_members_cached(&redis_pool, stale).await);
+    }
+    // The moved subtree and unrelated groups keep their lists
+    assert!(is_resolved_members_cached(&redis_pool, api).await);
+    assert!(is_resolved_members_cached(&redis_pool, frontend).await);
+
+    cache_resolved_members(&redis_pool, &[backend, sales]).await;
+    let response = server.delete(&format!("/api/v1/groups/{}", api)).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+    for stale in [api, backend, sales] {
+        assert!(!is_resolved_members_cached(&redis_pool, stale).await);
+    }
+    assert!(is_resolved_members_cached(&redis_pool, frontend).await);
+}
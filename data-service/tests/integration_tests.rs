@@ -3,7 +3,9 @@
 //! These tests use mock repositories to test the API handlers in isolation
 //! without requiring a real database or Redis connection.
 
+mod batch_api_tests;
 mod common;
 mod group_api_tests;
 mod membership_api_tests;
+mod organisation_api_tests;
 mod staff_api_tests;
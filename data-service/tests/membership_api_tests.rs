@@ -6,8 +6,9 @@ mod common;
 use axum::http::StatusCode;
 use axum_test::TestServer;
 use common::{
-    create_mock_redis_pool, create_sample_group, create_sample_staff, create_test_app_state,
-    MockGroupRepository, MockMembershipRepository, MockStaffRepository,
+    cache_resolved_members, create_mock_redis_pool, create_sample_group, create_sample_staff,
+    create_test_app_state, is_resolved_members_cached, MockGroupRepository,
+    MockMembershipRepository, MockStaffRepository,
 };
 use data_service::api::create_router;
 use serde_json::json;
@@ -52,6 +53,7 @@ async fn test_add_member_success() {
     assert_eq!(body["message"], "Member added successfully");
     assert_eq!(body["data"]["staff_id"], staff_id.to_string());
     assert_eq!(body["data"]["group_id"], group_id.to_string());
+    assert_eq!(body["data"]["role"], "MEMBER");
 }
 
 #[tokio::test]
@@ -94,6 +96,22 @@ async fn test_add_member_group_not_found() {
     response.assert_status(StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_add_member_deleted_staff_is_rejected() {
+    let group = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let mut staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    staff.deleted_at = Some(chrono::Utc::now());
+
+    let server = setup_test_server_with_data(vec![staff.clone()], vec![group.clone()]).await;
+
+    let response = server
+        .post(&format!("/api/v1/groups/{}/members", group.id))
+        .json(&json!({ "staff_id": staff.id }))
+        .await;
+
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 async fn test_get_group_members_empty() {
     let group_id = Uuid::new_v4();
@@ -125,3 +143,197 @@ async fn test_remove_member_not_found() {
 
     response.assert_status(StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn test_membership_changes_invalidate_ancestors_resolved_members() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let team = create_sample_group(Uuid::new_v4(), "Backend", Some(root.id));
+    let sibling = create_sample_group(Uuid::new_v4(), "Frontend", Some(root.id));
+    let server = setup_test_server_with_data(
+        vec![staff.clone()],
+        vec![root.clone(), team.clone(), sibling.clone()],
+    )
+    .await;
+    let redis_pool = create_mock_redis_pool().await;
+
+    cache_resolved_members(&redis_pool, &[root.id, team.id, sibling.id]).await;
+    let response = server
+        .post(&format!("/api/v1/groups/{}/members", team.id))
+        .json(&json!({ "staff_id": staff.id }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+
+    assert!(!is_resolved_members_cached(&redis_pool, team.id).await);
+    assert!(!is_resolved_members_cached(&redis_pool, root.id).await);
+    assert!(is_resolved_members_cached(&redis_pool, sibling.id).await);
+
+    cache_resolved_members(&redis_pool, &[root.id, team.id]).await;
+    let response = server
+        .delete(&format!("/api/v1/groups/{}/members/{}", team.id, staff.id))
+        .await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    assert!(!is_resolved_members_cached(&redis_pool, team.id).await);
+    assert!(!is_resolved_members_cached(&redis_pool, root.id).await);
+}
+
+#[tokio::test]
+async fn test_membership_dates_are_validated_and_replaced() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let team = create_sample_group(Uuid::new_v4(), "Backend", Some(root.id));
+    let server =
+        setup_test_server_with_data(vec![staff.clone()], vec![root.clone(), team.clone()]).await;
+    let redis_pool = create_mock_redis_pool().await;
+    let members = format!("/api/v1/groups/{}/members", team.id);
+    let membership = format!("/api/v1/groups/{}/members/{}", team.id, staff.id);
+
+    let response = server
+        .post(&members)
+        .json(&json!({
+            "staff_id": staff.id,
+            "valid_from": "2025-03-01",
+            "valid_to": "2025-02-01"
+        }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&members)
+        .json(&json!({ "staff_id": staff.id, "valid_from": "2025-03-01" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["valid_from"], "2025-03-01");
+    assert!(body["data"]["valid_to"].is_null());
+
+    cache_resolved_members(&redis_pool, &[root.id, team.id]).await;
+    let response = server
+        .put(&membership)
+        .json(&json!({ "valid_to": "2025-06-30" }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body["data"]["valid_from"].is_null());
+    assert_eq!(body["data"]["valid_to"], "2025-06-30");
+    assert!(!is_resolved_members_cached(&redis_pool, team.id).await);
+    assert!(!is_resolved_members_cached(&redis_pool, root.id).await);
+
+    let response = server
+        .put(&membership)
+        .json(&json!({ "valid_from": "2025-07-01", "valid_to": "2025-06-30" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .put(&format!("/api/v1/groups/{}/members/{}", root.id, staff.id))
+        .json(&json!({}))
+        .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_membership_role_is_set_and_replaced() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let group = create_sample_group(Uuid::new_v4(), "Ward A", None);
+    let server = setup_test_server_with_data(vec![staff.clone()], vec![group.clone()]).await;
+    let members = format!("/api/v1/groups/{}/members", group.id);
+    let membership = format!("/api/v1/groups/{}/members/{}", group.id, staff.id);
+
+    let response = server
+        .post(&members)
+        .json(&json!({ "staff_id": staff.id, "role": "CHIEF" }))
+        .await;
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    let response = server
+        .post(&members)
+        .json(&json!({ "staff_id": staff.id, "role": "LEAD" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["role"], "LEAD");
+
+    let response = server
+        .put(&membership)
+        .json(&json!({ "role": "TRAINEE", "valid_to": "2025-06-30" }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["role"], "TRAINEE");
+    assert_eq!(body["data"]["valid_to"], "2025-06-30");
+
+    // A replacement without a role makes them an ordinary member again
+    let response = server.put(&membership).json(&json!({})).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["role"], "MEMBER");
+}
+
+#[tokio::test]
+async fn test_staff_can_rejoin_a_group_but_not_overlap_their_stays() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let group = create_sample_group(Uuid::new_v4(), "Ward A", None);
+    let server = setup_test_server_with_data(vec![staff.clone()], vec![group.clone()]).await;
+    let members = format!("/api/v1/groups/{}/members", group.id);
+    let membership = format!("/api/v1/groups/{}/members/{}", group.id, staff.id);
+
+    let response = server
+        .post(&members)
+        .json(&json!({ "staff_id": staff.id, "valid_to": "2025-03-31" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let first: serde_json::Value = response.json();
+
+    // Joining again on the last day of the first stay overlaps it
+    let response = server
+        .post(&members)
+        .json(&json!({ "staff_id": staff.id, "valid_from": "2025-03-31" }))
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    let response = server
+        .post(&members)
+        .json(&json!({ "staff_id": staff.id, "valid_from": "2025-06-01" }))
+        .await;
+    response.assert_status(StatusCode::CREATED);
+    let second: serde_json::Value = response.json();
+
+    // With two stays, an update has to say which one it changes
+    let response = server
+        .put(&membership)
+        .json(&json!({ "valid_to": "2025-04-30" }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let first_id = first["data"]["id"].as_str().unwrap();
+    let response = server
+        .put(&format!("{}?membership_id={}", membership, first_id))
+        .json(&json!({ "valid_to": "2025-06-15" }))
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    let response = server
+        .put(&format!("{}?membership_id={}", membership, first_id))
+        .json(&json!({ "valid_to": "2025-04-30" }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["valid_to"], "2025-04-30");
+
+    let response = server
+        .delete(&format!("{}?membership_id={}", membership, first_id))
+        .await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    // The second stay is now the only one, so it needs no membership_id
+    let response = server
+        .put(&membership)
+        .json(&json!({ "valid_from": "2025-05-01" }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["id"], second["data"]["id"]);
+    assert_eq!(body["data"]["valid_from"], "2025-05-01");
+}
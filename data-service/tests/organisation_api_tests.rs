@@ -0,0 +1,317 @@
+//! Organisation export and import API integration tests
+
+#[path = "common/mod.rs"]
+mod common;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use chrono::Utc;
+use common::{
+    create_mock_redis_pool, create_sample_group, create_sample_staff, MockGroupRepository,
+    MockMembershipRepository, MockStaffRepository, MockStore, MockUnitOfWork,
+};
+use data_service::api::{create_router, AppState};
+use data_service::domain::entities::GroupMembership;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn setup_test_server(store: MockStore) -> (TestServer, Arc<MockUnitOfWork>) {
+    let unit_of_work = Arc::new(MockUnitOfWork::with_store(store));
+    let state = AppState::new(
+        Arc::new(MockStaffRepository::new()),
+        Arc::new(MockGroupRepository::new()),
+        Arc::new(MockMembershipRepository::new()),
+        unit_of_work.clone(),
+        create_mock_redis_pool().await,
+    );
+
+    (TestServer::new(create_router(state)).unwrap(), unit_of_work)
+}
+
+fn membership(staff_id: Uuid, group_id: Uuid) -> GroupMembership {
+    GroupMembership {
+        id: Uuid::new_v4(),
+        staff_id,
+        group_id,
+        created_at: Utc::now(),
+        role: shared::MembershipRole::Member,
+        valid_from: None,
+        valid_to: None,
+    }
+}
+
+/// Two teams that share a name, each with one member
+fn sample_organisation() -> MockStore {
+    let engineering = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let sales = create_sample_group(Uuid::new_v4(), "Sales", None);
+    let eng_support = create_sample_group(Uuid::new_v4(), "Support", Some(engineering.id));
+    let sales_support = create_sample_group(Uuid::new_v4(), "Support", Some(sales.id));
+    let john = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let jane = create_sample_staff(Uuid::new_v4(), "Jane Smith", "jane@example.com");
+    let memberships = vec![
+        membership(john.id, sales_support.id),
+        membership(jane.id, eng_support.id),
+    ];
+
+    MockStore {
+        staff: vec![john, jane],
+        groups: vec![sales_support, eng_support, sales, engineering],
+        memberships,
+    }
+}
+
+#[tokio::test]
+async fn test_export_organisation_refers_to_groups_by_path() {
+    let (server, _) = setup_test_server(sample_organisation()).await;
+
+    let response = server.get("/api/v1/organisation/export").await;
+
+    response.assert_status_ok();
+    response.assert_header("content-type", "application/json");
+    let disposition = response.header("content-disposition");
+    assert!(disposition.to_str().unwrap().ends_with(".json\""));
+
+    let document: serde_json::Value = response.json();
+    assert_eq!(document["version"], 1);
+    assert_eq!(document["staff"][0]["email"], "jane@example.com");
+    let paths: Vec<&str> = document["groups"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|group| group["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "Engineering",
+            "Engineering/Support",
+            "Sales",
+            "Sales/Support"
+        ]
+    );
+    assert_eq!(
+        document["memberships"],
+        serde_json::json!([
+            { "staff_email": "jane@example.com", "group_path": "Engineering/Support", "role": "MEMBER" },
+            { "staff_email": "john@example.com", "group_path": "Sales/Support", "role": "MEMBER" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn test_yaml_export_imports_into_an_empty_service() {
+    let (source, _) = setup_test_server(sample_organisation()).await;
+    let export = source.get("/api/v1/organisation/export?format=yaml").await;
+    export.assert_status_ok();
+    export.assert_header("content-type", "application/yaml");
+    let yaml = export.text();
+    assert!(yaml.starts_with("version: 1\n"));
+
+    let (server, unit_of_work) = setup_test_server(MockStore::default()).await;
+    let response = server
+        .post("/api/v1/organisation/import")
+        .text(yaml.clone())
+        .content_type("application/yaml")
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["message"], "Organisation import completed");
+    assert_eq!(body["data"]["staff"]["created"], 2);
+    assert_eq!(body["data"]["groups"]["created"], 4);
+    assert_eq!(body["data"]["memberships"]["created"], 2);
+
+    let store = unit_of_work.store();
+    let john = store
+        .staff
+        .iter()
+        .find(|s| s.email == "john@example.com")
+        .unwrap();
+    let group = store
+        .groups
+        .iter()
+        .find(|g| {
+            g.id == store
+                .memberships
+                .iter()
+                .find(|m| m.staff_id == john.id)
+                .unwrap()
+                .group_id
+        })
+        .unwrap();
+    let parent = store
+        .groups
+        .iter()
+        .find(|g| Some(g.id) == group.parent_id)
+        .unwrap();
+    assert_eq!(
+        (group.name.as_str(), parent.name.as_str()),
+        ("Support", "Sales")
+    );
+
+    // Importing the same document again changes nothing
+    let response = server
+        .post("/api/v1/organisation/import")
+        .text(yaml)
+        .content_type("application/yaml")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["staff"]["unchanged"], 2);
+    assert_eq!(body["data"]["groups"]["unchanged"], 4);
+    assert_eq!(body["data"]["memberships"]["unchanged"], 2);
+    assert_eq!(unit_of_work.store().groups.len(), 4);
+}
+
+#[tokio::test]
+async fn test_import_organisation_replaces_membership_roles_and_dates() {
+    let mut store = sample_organisation();
+    store.memberships[0].valid_from = chrono::NaiveDate::from_ymd_opt(2025, 1, 1);
+    let (server, unit_of_work) = setup_test_server(store).await;
+
+    let export = server.get("/api/v1/organisation/export").await;
+    export.assert_status_ok();
+    let mut document: serde_json::Value = export.json();
+    let memberships = document["memberships"].as_array_mut().unwrap();
+    let john = memberships
+        .iter_mut()
+        .find(|m| m["staff_email"] == "john@example.com")
+        .unwrap();
+    assert_eq!(john["role"], "MEMBER");
+    assert_eq!(john["valid_from"], "2025-01-01");
+    assert!(john.get("valid_to").is_none());
+    john["role"] = serde_json::json!("BACKUP");
+    john["valid_to"] = serde_json::json!("2025-12-31");
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .json(&document)
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["memberships"]["updated"], 1);
+    assert_eq!(body["data"]["memberships"]["unchanged"], 1);
+    let store = unit_of_work.store();
+    let john = store
+        .staff
+        .iter()
+        .find(|s| s.email == "john@example.com")
+        .unwrap();
+    let membership = store
+        .memberships
+        .iter()
+        .find(|m| m.staff_id == john.id)
+        .unwrap();
+    assert_eq!(membership.role, shared::MembershipRole::Backup);
+    assert_eq!(
+        membership.valid_to,
+        chrono::NaiveDate::from_ymd_opt(2025, 12, 31)
+    );
+}
+
+#[tokio::test]
+async fn test_import_organisation_matches_each_stay_in_a_group_by_its_first_day() {
+    let mut store = sample_organisation();
+    let day = |m, d| chrono::NaiveDate::from_ymd_opt(2025, m, d);
+    store.memberships[0].valid_to = day(3, 31);
+    let mut rejoined = membership(store.memberships[0].staff_id, store.memberships[0].group_id);
+    rejoined.valid_from = day(6, 1);
+    store.memberships.push(rejoined);
+    let (server, unit_of_work) = setup_test_server(store).await;
+
+    let export = server.get("/api/v1/organisation/export").await;
+    export.assert_status_ok();
+    let mut document: serde_json::Value = export.json();
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .json(&document)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["memberships"]["unchanged"], 3);
+
+    // A stay overlapping one of John's two is refused
+    let memberships = document["memberships"].as_array_mut().unwrap();
+    let mut overlapping = memberships
+        .iter()
+        .find(|m| m["staff_email"] == "john@example.com")
+        .unwrap()
+        .clone();
+    overlapping["valid_from"] = serde_json::json!("2025-03-15");
+    overlapping["valid_to"] = serde_json::json!("2025-04-15");
+    memberships.push(overlapping);
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .json(&document)
+        .await;
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(unit_of_work.store().memberships.len(), 3);
+}
+
+#[tokio::test]
+async fn test_import_organisation_is_all_or_nothing() {
+    let (server, unit_of_work) = setup_test_server(MockStore::default()).await;
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .json(&serde_json::json!({
+            "version": 1,
+            "staff": [
+                { "name": "John Doe", "email": "john@example.com", "position": "Nurse" }
+            ],
+            "groups": [
+                { "path": "Wards/Ward A" },
+                { "path": "Clinics//Outpatients" }
+            ],
+            "memberships": [
+                { "staff_email": "john@example.com", "group_path": "Wards/Ward A" }
+            ]
+        }))
+        .await;
+
+    response.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+    let body: serde_json::Value = response.json();
+    assert!(body.get("data").is_none());
+    let data = &body["details"];
+    assert_eq!(body["message"], "Organisation import rolled back");
+    assert_eq!(data["committed"], false);
+    assert_eq!(data["error_count"], 3);
+    assert_eq!(data["errors"][0]["entry"], "groups[1]");
+    assert_eq!(data["errors"][1]["entry"], "groups[0]");
+    assert_eq!(
+        data["errors"][1]["message"],
+        "Parent group 'Wards' is not in the document"
+    );
+    assert_eq!(data["errors"][2]["entry"], "memberships[0]");
+
+    let store = unit_of_work.store();
+    assert!(store.staff.is_empty() && store.groups.is_empty());
+}
+
+#[tokio::test]
+async fn test_import_organisation_rejects_unreadable_documents() {
+    let (server, _) = setup_test_server(MockStore::default()).await;
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .json(&serde_json::json!({ "version": 2 }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    assert!(response.text().contains("Unsupported document version 2"));
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .json(&serde_json::json!({ "version": 1, "teams": [] }))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post("/api/v1/organisation/import")
+        .text("version,staff\n1,\n")
+        .content_type("text/csv")
+        .await;
+    response.assert_status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
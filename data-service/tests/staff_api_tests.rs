@@ -6,11 +6,14 @@ mod common;
 use axum::http::StatusCode;
 use axum_test::TestServer;
 use common::{
-    create_mock_redis_pool, create_sample_staff, create_test_app_state, MockGroupRepository,
+    cache_resolved_members, create_mock_redis_pool, create_sample_group, create_sample_staff,
+    create_test_app_state, is_resolved_members_cached, MockGroupRepository,
     MockMembershipRepository, MockStaffRepository,
 };
 use data_service::api::create_router;
+use data_service::domain::entities::GroupMembership;
 use serde_json::json;
+use shared::StaffStatus;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -158,6 +161,85 @@ async fn test_list_staff_with_pagination() {
     assert_eq!(body["data"].as_array().unwrap().len(), 2);
 }
 
+#[tokio::test]
+async fn test_list_staff_filters_and_sorts() {
+    let mut alice = create_sample_staff(Uuid::new_v4(), "Alice Smith", "alice@example.com");
+    alice.position = "Nurse".to_string();
+    let mut bob = create_sample_staff(Uuid::new_v4(), "Bob Jones", "bob@example.com");
+    bob.position = "nurse".to_string();
+    bob.status = StaffStatus::Inactive;
+    let carol = create_sample_staff(Uuid::new_v4(), "Carol Smith", "carol@example.com");
+    let server = setup_test_server_with_staff(vec![alice, bob, carol]).await;
+
+    let names = |body: serde_json::Value| -> Vec<String> {
+        body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let body = server
+        .get("/api/v1/staff?position=NURSE&sort=name")
+        .await
+        .json();
+    assert_eq!(names(body), vec!["Alice Smith", "Bob Jones"]);
+
+    let body = server
+        .get("/api/v1/staff?position=nurse&status=ACTIVE")
+        .await
+        .json();
+    assert_eq!(names(body), vec!["Alice Smith"]);
+
+    let body = server
+        .get("/api/v1/staff?q=SMITH&sort=name&order=desc")
+        .await
+        .json();
+    assert_eq!(names(body), vec!["Carol Smith", "Alice Smith"]);
+
+    server
+        .get("/api/v1/staff?sort=salary")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_list_staff_by_cursor() {
+    let staff: Vec<_> = (1..=5)
+        .map(|i| {
+            create_sample_staff(
+                Uuid::new_v4(),
+                &format!("Staff {}", i),
+                &format!("staff{}@example.com", i),
+            )
+        })
+        .collect();
+    let server = setup_test_server_with_staff(staff).await;
+
+    let mut seen = Vec::new();
+    let mut url = "/api/v1/staff?sort=email&page_size=2".to_string();
+    loop {
+        let body: serde_json::Value = server.get(&url).await.json();
+        assert_eq!(body["total"], 5);
+        for s in body["data"].as_array().unwrap() {
+            seen.push(s["email"].as_str().unwrap().to_string());
+        }
+        match body["next_cursor"].as_str() {
+            Some(cursor) => url = format!("/api/v1/staff?sort=email&page_size=2&cursor={}", cursor),
+            None => break,
+        }
+    }
+
+    let expected: Vec<String> = (1..=5).map(|i| format!("staff{}@example.com", i)).collect();
+    assert_eq!(seen, expected);
+
+    server
+        .get("/api/v1/staff?cursor=not-a-cursor")
+        .await
+        .assert_status(StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn test_update_staff_success() {
     let staff_id = Uuid::new_v4();
@@ -180,6 +262,40 @@ async fn test_update_staff_success() {
     assert_eq!(body["data"]["position"], "Senior Engineer");
 }
 
+#[tokio::test]
+async fn test_update_staff_sets_keeps_and_clears_external_id() {
+    let staff_id = Uuid::new_v4();
+    let staff = create_sample_staff(staff_id, "John Doe", "john@example.com");
+    let server = setup_test_server_with_staff(vec![staff]).await;
+    let path = format!("/api/v1/staff/{}", staff_id);
+
+    let response = server
+        .put(&path)
+        .json(&json!({ "external_id": "E-1" }))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(
+        response.json::<serde_json::Value>()["data"]["external_id"],
+        "E-1"
+    );
+
+    // Leaving the field out keeps the link
+    let response = server.put(&path).json(&json!({ "name": "John" })).await;
+    response.assert_status_ok();
+    assert_eq!(
+        response.json::<serde_json::Value>()["data"]["external_id"],
+        "E-1"
+    );
+
+    // `null` unlinks the staff member from the HR system
+    let response = server
+        .put(&path)
+        .json(&json!({ "external_id": null }))
+        .await;
+    response.assert_status_ok();
+    assert!(response.json::<serde_json::Value>()["data"]["external_id"].is_null());
+}
+
 #[tokio::test]
 async fn test_update_staff_not_found() {
     let server = setup_test_server().await;
@@ -212,6 +328,74 @@ async fn test_delete_staff_success() {
     get_response.assert_status(StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_deleted_staff_are_listed_on_request_and_restored() {
+    let staff_id = Uuid::new_v4();
+    let staff = create_sample_staff(staff_id, "John Doe", "john@example.com");
+    let other = create_sample_staff(Uuid::new_v4(), "Jane Doe", "jane@example.com");
+    let server = setup_test_server_with_staff(vec![staff, other]).await;
+
+    let response = server.delete(&format!("/api/v1/staff/{}", staff_id)).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    let body: serde_json::Value = server.get("/api/v1/staff").await.json();
+    assert_eq!(body["total"], 1);
+    let body: serde_json::Value = server
+        .get("/api/v1/staff?include_deleted=true")
+        .await
+        .json();
+    assert_eq!(body["total"], 2);
+
+    let response = server
+        .get(&format!("/api/v1/staff/{}?include_deleted=true", staff_id))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body["data"]["deleted_at"].is_string());
+
+    let response = server
+        .post(&format!("/api/v1/staff/{}/restore", staff_id))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body["data"]["deleted_at"].is_null());
+
+    server
+        .get(&format!("/api/v1/staff/{}", staff_id))
+        .await
+        .assert_status_ok();
+}
+
+#[tokio::test]
+async fn test_purge_staff_requires_a_soft_delete_first() {
+    let staff_id = Uuid::new_v4();
+    let staff = create_sample_staff(staff_id, "John Doe", "john@example.com");
+    let server = setup_test_server_with_staff(vec![staff]).await;
+
+    let response = server
+        .delete(&format!("/api/v1/admin/staff/{}", staff_id))
+        .await;
+    response.assert_status(StatusCode::CONFLICT);
+
+    server
+        .delete(&format!("/api/v1/staff/{}", staff_id))
+        .await
+        .assert_status(StatusCode::NO_CONTENT);
+    let response = server
+        .delete(&format!("/api/v1/admin/staff/{}", staff_id))
+        .await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    server
+        .get(&format!("/api/v1/staff/{}?include_deleted=true", staff_id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+    server
+        .post(&format!("/api/v1/staff/{}/restore", staff_id))
+        .await
+        .assert_status(StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 async fn test_delete_staff_not_found() {
     let server = setup_test_server().await;
@@ -223,3 +407,50 @@ async fn test_delete_staff_not_found() {
 
     response.assert_status(StatusCode::NOT_FOUND);
 }
+
+#[tokio::test]
+async fn test_staff_changes_invalidate_resolved_members_of_their_groups() {
+    let staff = create_sample_staff(Uuid::new_v4(), "John Doe", "john@example.com");
+    let root = create_sample_group(Uuid::new_v4(), "Engineering", None);
+    let team = create_sample_group(Uuid::new_v4(), "Backend", Some(root.id));
+    let other = create_sample_group(Uuid::new_v4(), "Sales", None);
+    let membership = GroupMembership {
+        id: Uuid::new_v4(),
+        staff_id: staff.id,
+        group_id: team.id,
+        created_at: chrono::Utc::now(),
+        role: shared::MembershipRole::Member,
+        valid_from: None,
+        valid_to: None,
+    };
+    let redis_pool = create_mock_redis_pool().await;
+    let state = create_test_app_state(
+        Arc::new(MockStaffRepository::with_staff(vec![staff.clone()])),
+        Arc::new(MockGroupRepository::with_groups(vec![
+            root.clone(),
+            team.clone(),
+            other.clone(),
+        ])),
+        Arc::new(MockMembershipRepository::with_memberships(vec![membership])),
+        redis_pool.clone(),
+    );
+    let server = TestServer::new(create_router(state)).unwrap();
+
+    cache_resolved_members(&redis_pool, &[root.id, team.id, other.id]).await;
+    let response = server
+        .put(&format!("/api/v1/staff/{}", staff.id))
+        .json(&json!({ "status": "INACTIVE" }))
+        .await;
+    response.assert_status_ok();
+
+    assert!(!is_resolved_members_cached(&redis_pool, team.id).await);
+    assert!(!is_resolved_members_cached(&redis_pool, root.id).await);
+    assert!(is_resolved_members_cached(&redis_pool, other.id).await);
+
+    cache_resolved_members(&redis_pool, &[root.id, team.id]).await;
+    let response = server.delete(&format!("/api/v1/staff/{}", staff.id)).await;
+    response.assert_status(StatusCode::NO_CONTENT);
+
+    assert!(!is_resolved_members_cached(&redis_pool, team.id).await);
+    assert!(!is_resolved_members_cached(&redis_pool, root.id).await);
+}
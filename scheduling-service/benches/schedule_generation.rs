@@ -0,0 +1,65 @@
+//! Measures 28-day schedule generation for increasingly large staff groups
+//! with the rule set and limits from `config/default.toml`.
+//!
+//! ```text
+//! cargo bench -p scheduling-service --bench schedule_generation
+//! ```
+
+use chrono::NaiveDate;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use scheduling_service::domain::fairness::FairnessLedger;
+use scheduling_service::domain::holiday_schedule::HolidaySchedule;
+use scheduling_service::domain::rules::{
+    MaxDaysOffRule, MinDaysOffRule, NoMorningAfterEveningRule, Rule, ShiftBalanceRule,
+};
+use scheduling_service::domain::schedule_generator::ScheduleGenerator;
+use scheduling_service::domain::staff_pool::{MembershipPeriod, StaffPool};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const STAFF_COUNTS: [usize; 3] = [100, 500, 1_000];
+
+fn generator() -> ScheduleGenerator {
+    let rules: Vec<Arc<dyn Rule>> = vec![
+        Arc::new(NoMorningAfterEveningRule::new()),
+        Arc::new(MinDaysOffRule::new(1)),
+        Arc::new(MaxDaysOffRule::new(2)),
+        Arc::new(ShiftBalanceRule::new(1)),
+    ];
+    ScheduleGenerator::new(rules)
+}
+
+fn bench_generation(c: &mut Criterion) {
+    let generator = generator();
+    let monday = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+    let job_id = Uuid::new_v4();
+    let history = FairnessLedger::new();
+    let holidays = HolidaySchedule::new();
+
+    let mut group = c.benchmark_group("schedule_generation");
+    group.sample_size(10);
+
+    for staff_count in STAFF_COUNTS {
+        let pool = StaffPool::new().with_group(
+            Uuid::new_v4(),
+            (0..staff_count).map(|_| (Uuid::new_v4(), MembershipPeriod::default())),
+        );
+        group.throughput(Throughput::Elements(staff_count as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(staff_count),
+            &pool,
+            |b, pool| {
+                b.iter(|| {
+                    generator
+                        .generate_schedule(pool, monday, job_id, 42, &history, &holidays)
+                        .unwrap()
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_generation);
+criterion_main!(benches);
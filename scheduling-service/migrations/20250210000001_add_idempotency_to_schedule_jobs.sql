@@ -0,0 +1,15 @@
+-- Jobs can be cancelled when a newer submission supersedes them
+ALTER TYPE job_status ADD VALUE IF NOT EXISTS 'CANCELLED';
+
+-- Idempotency key supplied by the client and a hash of the request it was used with
+ALTER TABLE schedule_jobs ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(255);
+ALTER TABLE schedule_jobs ADD COLUMN IF NOT EXISTS request_hash VARCHAR(64);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_schedule_jobs_idempotency_key
+    ON schedule_jobs(idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
+
+-- Active jobs per group and period, used for duplicate detection
+CREATE INDEX IF NOT EXISTS idx_schedule_jobs_active_group_period
+    ON schedule_jobs(staff_group_id, period_begin_date)
+    WHERE status IN ('PENDING', 'PROCESSING');
@@ -0,0 +1,7 @@
+-- Seed for the generator's random choices, so a job's roster can be reproduced
+ALTER TABLE schedule_jobs ADD COLUMN IF NOT EXISTS seed BIGINT;
+
+-- Earlier jobs were not generated from a seed; give them one so the column can be required
+UPDATE schedule_jobs SET seed = floor(random() * 9007199254740991)::BIGINT WHERE seed IS NULL;
+
+ALTER TABLE schedule_jobs ALTER COLUMN seed SET NOT NULL;
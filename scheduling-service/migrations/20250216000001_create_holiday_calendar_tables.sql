@@ -0,0 +1,37 @@
+-- Where a holiday entry came from
+CREATE TYPE holiday_source AS ENUM ('MANUAL', 'ICS');
+
+-- Create holiday_calendars table
+CREATE TABLE IF NOT EXISTS holiday_calendars (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    -- NULL applies the calendar to every group
+    staff_group_id UUID,
+    -- Share of the usual morning and evening staffing needed on a holiday
+    coverage_percent INTEGER NOT NULL DEFAULT 100 CHECK (coverage_percent BETWEEN 0 AND 100),
+    -- A day off on a holiday is paid leave and does not use up the weekly days off
+    paid_leave BOOLEAN NOT NULL DEFAULT FALSE,
+    -- How many holiday shifts a worked holiday counts as when rotating burden
+    premium_weight INTEGER NOT NULL DEFAULT 1 CHECK (premium_weight >= 1),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_holiday_calendars_staff_group_id ON holiday_calendars(staff_group_id);
+
+CREATE TRIGGER update_holiday_calendars_updated_at BEFORE UPDATE ON holiday_calendars
+FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
+
+-- Create holidays table (at most one entry per calendar and date)
+CREATE TABLE IF NOT EXISTS holidays (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    calendar_id UUID NOT NULL REFERENCES holiday_calendars(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    source holiday_source NOT NULL DEFAULT 'MANUAL',
+    -- UID of the imported VEVENT
+    external_uid VARCHAR(255),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX idx_holidays_calendar_date ON holidays(calendar_id, date);
@@ -0,0 +1,15 @@
+-- Further groups scheduled together with staff_group_id from one shared staff pool
+ALTER TABLE schedule_jobs ADD COLUMN IF NOT EXISTS additional_group_ids UUID[] NOT NULL DEFAULT '{}';
+
+-- Group a shift is worked for; staff in several groups work for one of them per shift
+ALTER TABLE shift_assignments ADD COLUMN IF NOT EXISTS staff_group_id UUID;
+
+-- Earlier jobs scheduled a single group
+UPDATE shift_assignments sa
+SET staff_group_id = sj.staff_group_id
+FROM schedule_jobs sj
+WHERE sa.schedule_job_id = sj.id AND sa.staff_group_id IS NULL;
+
+ALTER TABLE shift_assignments ALTER COLUMN staff_group_id SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_shift_assignments_job_group ON shift_assignments(schedule_job_id, staff_group_id);
@@ -0,0 +1,2 @@
+-- Per-subgroup minimum staffing, as a JSON array of {group_id, shift, min_staff}
+ALTER TABLE schedule_jobs ADD COLUMN IF NOT EXISTS coverage_targets JSONB NOT NULL DEFAULT '[]';
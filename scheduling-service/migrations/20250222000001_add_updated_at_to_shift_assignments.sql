@@ -0,0 +1,8 @@
+-- Track when an assignment last changed so calendar feeds can revise their events
+ALTER TABLE shift_assignments ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ;
+UPDATE shift_assignments SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE shift_assignments ALTER COLUMN updated_at SET NOT NULL;
+ALTER TABLE shift_assignments ALTER COLUMN updated_at SET DEFAULT NOW();
+
+CREATE TRIGGER update_shift_assignments_updated_at BEFORE UPDATE ON shift_assignments
+FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
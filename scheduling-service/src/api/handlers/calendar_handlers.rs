@@ -0,0 +1,98 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use shared::JobStatus;
+use uuid::Uuid;
+
+use crate::api::state::AppState;
+use crate::infrastructure::icalendar::write_roster;
+
+const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+fn calendar_response(body: String) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, CALENDAR_CONTENT_TYPE)],
+        body,
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/schedules/{schedule_id}/calendar.ics",
+    params(
+        ("schedule_id" = Uuid, Path, description = "Schedule job ID")
+    ),
+    responses(
+        (status = 200, description = "iCalendar feed with one event per assignment of the schedule", content_type = "text/calendar"),
+        (status = 404, description = "Schedule not found"),
+        (status = 400, description = "Schedule not completed yet"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "calendars"
+)]
+pub async fn get_schedule_calendar(
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let job = state
+        .job_repo
+        .find_by_id(schedule_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    if job.status != JobStatus::Completed {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Schedule is not completed yet. Current status: {:?}",
+                job.status
+            ),
+        ));
+    }
+
+    let assignments = state
+        .assignment_repo
+        .find_by_job_id(schedule_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let name = format!("Roster from {}", job.period_begin_date);
+    Ok(calendar_response(write_roster(
+        &name,
+        &assignments,
+        &state.calendar,
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/staff/{staff_id}/calendar.ics",
+    params(
+        ("staff_id" = Uuid, Path, description = "Staff member ID")
+    ),
+    responses(
+        (status = 200, description = "iCalendar feed of the staff member's shifts across all completed schedules", content_type = "text/calendar"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "calendars"
+)]
+pub async fn get_staff_calendar(
+    State(state): State<AppState>,
+    Path(staff_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let assignments = state
+        .assignment_repo
+        .find_published_for_staff(staff_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok(calendar_response(write_roster(
+        "My shifts",
+        &assignments,
+        &state.calendar,
+    )))
+}
@@ -0,0 +1,116 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use shared::JobStatus;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::api::requests::{ExportFormat, ScheduleExportQuery};
+use crate::api::state::AppState;
+use crate::domain::entities::ScheduleJob;
+use crate::domain::schedule_generator::period_end_date;
+use crate::infrastructure::roster_export::{write_csv, write_xlsx};
+use crate::infrastructure::roster_print::{write_html, write_pdf};
+use crate::presentation::RosterGrid;
+
+/// A completed schedule as a grid, with staff names from the data service
+async fn load_roster_grid(
+    state: &AppState,
+    schedule_id: Uuid,
+) -> Result<(ScheduleJob, RosterGrid), (StatusCode, String)> {
+    let job = state
+        .job_repo
+        .find_by_id(schedule_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    if job.status != JobStatus::Completed {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Schedule is not completed yet. Current status: {:?}",
+                job.status
+            ),
+        ));
+    }
+
+    let assignments = state
+        .assignment_repo
+        .find_by_job_id(schedule_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let mut names = HashMap::new();
+    let period_end = period_end_date(job.period_begin_date);
+    let group_ids = std::iter::once(job.staff_group_id).chain(job.additional_group_ids.clone());
+    for group_id in group_ids {
+        let members = state
+            .data_service_client
+            .get_group_members(group_id, job.period_begin_date, period_end)
+            .await
+            .map_err(|e| (StatusCode::BAD_GATEWAY, e.to_string()))?;
+        names.extend(members.into_iter().map(|staff| (staff.id, staff.name)));
+    }
+
+    let grid = RosterGrid::new(&assignments, &names);
+    Ok((job, grid))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/schedules/{schedule_id}/export",
+    params(
+        ("schedule_id" = Uuid, Path, description = "Schedule job ID"),
+        ScheduleExportQuery
+    ),
+    responses(
+        (status = 200, description = "Staff × date grid with per-staff and per-day totals as CSV or XLSX, or a colour-coded weekly roster for printing as HTML or PDF"),
+        (status = 404, description = "Schedule not found"),
+        (status = 400, description = "Schedule not completed yet or unknown format"),
+        (status = 502, description = "Staff names could not be fetched from the data service"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "schedules"
+)]
+pub async fn export_schedule(
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+    Query(query): Query<ScheduleExportQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let (job, grid) = load_roster_grid(&state, schedule_id).await?;
+
+    let title = format!("Roster from {}", job.period_begin_date);
+    let body = match query.format {
+        ExportFormat::Csv => write_csv(&grid),
+        ExportFormat::Xlsx => write_xlsx(&grid),
+        ExportFormat::Html => Ok(write_html(&title, &grid).into_bytes()),
+        ExportFormat::Pdf => write_pdf(&title, &grid),
+    }
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let disposition = format!(
+        "{}; filename=\"roster-{}.{}\"",
+        if query.format.is_printable() {
+            "inline"
+        } else {
+            "attachment"
+        },
+        job.period_begin_date,
+        query.format.extension()
+    );
+
+    Ok((
+        StatusCode::OK,
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    ))
+}
@@ -0,0 +1,405 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use shared::{ApiResponse, DomainError, PaginatedResponse, PaginationParams};
+use uuid::Uuid;
+
+use super::validate_pagination;
+use crate::api::requests::{
+    CreateHolidayCalendarRequest, CreateHolidayRequest, HolidayCalendarFilter, HolidayFilter,
+};
+use crate::api::state::AppState;
+use crate::domain::entities::{Holiday, HolidayCalendar, HolidaySource};
+use crate::infrastructure::icalendar;
+use crate::presentation::{HolidayCalendarSerializer, HolidayImportSerializer, HolidaySerializer};
+
+const DEFAULT_COVERAGE_PERCENT: i32 = 100;
+const DEFAULT_PREMIUM_WEIGHT: i32 = 1;
+
+async fn find_calendar(
+    state: &AppState,
+    calendar_id: Uuid,
+) -> Result<HolidayCalendar, (StatusCode, String)> {
+    state
+        .holiday_repo
+        .find_by_id(calendar_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Holiday calendar not found".to_string(),
+        ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/holiday-calendars",
+    request_body = CreateHolidayCalendarRequest,
+    responses(
+        (status = 201, description = "Holiday calendar created", body = ApiResponse<HolidayCalendarSerializer>),
+        (status = 400, description = "Invalid request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn create_holiday_calendar(
+    State(state): State<AppState>,
+    Json(request): Json<CreateHolidayCalendarRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "name must not be empty".to_string(),
+        ));
+    }
+
+    let coverage_percent = request.coverage_percent.unwrap_or(DEFAULT_COVERAGE_PERCENT);
+    if !(0..=100).contains(&coverage_percent) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "coverage_percent must be between 0 and 100".to_string(),
+        ));
+    }
+
+    let premium_weight = request.premium_weight.unwrap_or(DEFAULT_PREMIUM_WEIGHT);
+    if premium_weight < 1 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "premium_weight must be at least 1".to_string(),
+        ));
+    }
+
+    let now = Utc::now();
+    let calendar = HolidayCalendar {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        staff_group_id: request.staff_group_id,
+        coverage_percent,
+        paid_leave: request.paid_leave,
+        premium_weight,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let created = state
+        .holiday_repo
+        .create(calendar)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success(
+            "Holiday calendar created successfully",
+            HolidayCalendarSerializer::from(created),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/holiday-calendars",
+    params(PaginationParams, HolidayCalendarFilter),
+    responses(
+        (status = 200, description = "Holiday calendar list", body = ApiResponse<PaginatedResponse<HolidayCalendarSerializer>>),
+        (status = 400, description = "Invalid pagination parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn list_holiday_calendars(
+    State(state): State<AppState>,
+    Query(params): Query<PaginationParams>,
+    Query(filter): Query<HolidayCalendarFilter>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    validate_pagination(&params)?;
+
+    let (calendars, total) = state
+        .holiday_repo
+        .list(filter.staff_group_id, params.clone())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let serialized: Vec<HolidayCalendarSerializer> = calendars
+        .into_iter()
+        .map(HolidayCalendarSerializer::from)
+        .collect();
+
+    let data = PaginatedResponse::new(serialized, params.page, params.page_size, total);
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Holiday calendars retrieved successfully",
+            data,
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/holiday-calendars/{calendar_id}",
+    params(
+        ("calendar_id" = Uuid, Path, description = "Holiday calendar ID")
+    ),
+    responses(
+        (status = 200, description = "Holiday calendar retrieved", body = ApiResponse<HolidayCalendarSerializer>),
+        (status = 404, description = "Holiday calendar not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn get_holiday_calendar(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let calendar = find_calendar(&state, calendar_id).await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Holiday calendar retrieved successfully",
+            HolidayCalendarSerializer::from(calendar),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/holiday-calendars/{calendar_id}",
+    params(
+        ("calendar_id" = Uuid, Path, description = "Holiday calendar ID")
+    ),
+    responses(
+        (status = 204, description = "Holiday calendar and its holidays deleted"),
+        (status = 404, description = "Holiday calendar not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn delete_holiday_calendar(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    state
+        .holiday_repo
+        .delete(calendar_id)
+        .await
+        .map_err(|e| match e {
+            DomainError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/holiday-calendars/{calendar_id}/holidays",
+    params(
+        ("calendar_id" = Uuid, Path, description = "Holiday calendar ID"),
+        HolidayFilter
+    ),
+    responses(
+        (status = 200, description = "Holidays of the calendar, ordered by date", body = ApiResponse<Vec<HolidaySerializer>>),
+        (status = 400, description = "Invalid date range"),
+        (status = 404, description = "Holiday calendar not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn list_holidays(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Uuid>,
+    Query(filter): Query<HolidayFilter>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    if let (Some(from), Some(to)) = (filter.from, filter.to) {
+        if from > to {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "from must not be after to".to_string(),
+            ));
+        }
+    }
+
+    find_calendar(&state, calendar_id).await?;
+
+    let holidays = state
+        .holiday_repo
+        .list_holidays(calendar_id, filter.from, filter.to)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let serialized: Vec<HolidaySerializer> =
+        holidays.into_iter().map(HolidaySerializer::from).collect();
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Holidays retrieved successfully",
+            serialized,
+        )),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/holiday-calendars/{calendar_id}/holidays",
+    params(
+        ("calendar_id" = Uuid, Path, description = "Holiday calendar ID")
+    ),
+    request_body = CreateHolidayRequest,
+    responses(
+        (status = 201, description = "Holiday added; an existing entry on the same date is replaced", body = ApiResponse<HolidaySerializer>),
+        (status = 400, description = "Invalid request"),
+        (status = 404, description = "Holiday calendar not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn add_holiday(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Uuid>,
+    Json(request): Json<CreateHolidayRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "name must not be empty".to_string(),
+        ));
+    }
+
+    find_calendar(&state, calendar_id).await?;
+
+    let holiday = Holiday {
+        id: Uuid::new_v4(),
+        calendar_id,
+        date: request.date,
+        name: name.to_string(),
+        source: HolidaySource::Manual,
+        external_uid: None,
+        created_at: Utc::now(),
+    };
+
+    let stored = state
+        .holiday_repo
+        .upsert_holidays(vec![holiday])
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .pop()
+        .ok_or((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Holiday was not stored".to_string(),
+        ))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success(
+            "Holiday added successfully",
+            HolidaySerializer::from(stored),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/holiday-calendars/{calendar_id}/holidays/{holiday_id}",
+    params(
+        ("calendar_id" = Uuid, Path, description = "Holiday calendar ID"),
+        ("holiday_id" = Uuid, Path, description = "Holiday ID")
+    ),
+    responses(
+        (status = 204, description = "Holiday deleted"),
+        (status = 404, description = "Holiday not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn delete_holiday(
+    State(state): State<AppState>,
+    Path((calendar_id, holiday_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    state
+        .holiday_repo
+        .delete_holiday(calendar_id, holiday_id)
+        .await
+        .map_err(|e| match e {
+            DomainError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/holiday-calendars/{calendar_id}/import",
+    params(
+        ("calendar_id" = Uuid, Path, description = "Holiday calendar ID")
+    ),
+    request_body(content = String, content_type = "text/calendar", description = "iCalendar (.ics) file"),
+    responses(
+        (status = 200, description = "Holidays imported; entries on dates already in the calendar are replaced", body = ApiResponse<HolidayImportSerializer>),
+        (status = 400, description = "Body is not an iCalendar file"),
+        (status = 404, description = "Holiday calendar not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "holidays"
+)]
+pub async fn import_holidays(
+    State(state): State<AppState>,
+    Path(calendar_id): Path<Uuid>,
+    body: String,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    find_calendar(&state, calendar_id).await?;
+
+    let import = icalendar::parse_holidays(&body).map_err(|e| match e {
+        DomainError::InvalidInput(msg) => (StatusCode::BAD_REQUEST, msg),
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+    })?;
+
+    let now = Utc::now();
+    let holidays: Vec<Holiday> = import
+        .holidays
+        .into_iter()
+        .map(|holiday| Holiday {
+            id: Uuid::new_v4(),
+            calendar_id,
+            date: holiday.date,
+            name: holiday.name,
+            source: HolidaySource::Ics,
+            external_uid: holiday.uid,
+            created_at: now,
+        })
+        .collect();
+
+    let stored = state
+        .holiday_repo
+        .upsert_holidays(holidays)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    tracing::info!(
+        "Imported {} holidays into calendar {}, skipped {} events",
+        stored.len(),
+        calendar_id,
+        import.skipped.len()
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Holidays imported successfully",
+            HolidayImportSerializer {
+                imported: stored.into_iter().map(HolidaySerializer::from).collect(),
+                skipped: import.skipped,
+            },
+        )),
+    ))
+}
@@ -1,3 +1,33 @@
+pub mod calendar_handlers;
+pub mod export_handlers;
+pub mod holiday_handlers;
 pub mod schedule_handlers;
+pub mod webhook_handlers;
 
-pub use schedule_handlers::{get_schedule_result, get_schedule_status, submit_schedule};
+use axum::http::StatusCode;
+use shared::PaginationParams;
+
+pub use calendar_handlers::{get_schedule_calendar, get_staff_calendar};
+pub use export_handlers::export_schedule;
+pub use holiday_handlers::{
+    add_holiday, create_holiday_calendar, delete_holiday, delete_holiday_calendar,
+    get_holiday_calendar, import_holidays, list_holiday_calendars, list_holidays,
+};
+pub use schedule_handlers::{
+    get_schedule_result, get_schedule_status, list_schedules, stream_schedule_events,
+    submit_schedule,
+};
+pub use webhook_handlers::{
+    create_webhook, delete_webhook, get_webhook, list_webhook_deliveries, list_webhooks,
+};
+
+/// Rejects a zero page or page size before a list handler queries anything.
+pub(crate) fn validate_pagination(params: &PaginationParams) -> Result<(), (StatusCode, String)> {
+    if params.page == 0 || params.page_size == 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "page and page_size must be greater than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
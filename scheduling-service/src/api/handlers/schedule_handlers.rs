@@ -1,37 +1,115 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Json,
 };
 use chrono::{Datelike, Utc};
-use shared::{cache_keys, cache_ttl, get_cached, set_cached, ApiResponse, JobStatus};
+use futures::{future, stream, StreamExt};
+use rand::Rng;
+use shared::{
+    cache_keys, cache_ttl, get_cached, set_cached, ApiResponse, JobStatus, PaginatedResponse,
+    PaginationParams, ShiftType,
+};
+use std::collections::HashSet;
 use uuid::Uuid;
 
+use super::validate_pagination;
 use crate::api::requests::schedule_request::ScheduleJobRequest;
-use crate::api::requests::CreateScheduleRequest;
+use crate::api::requests::{CreateScheduleRequest, DuplicatePolicy, ScheduleJobFilter};
 use crate::api::state::AppState;
 use crate::domain::entities::ScheduleJob;
+use crate::domain::events::{JobEvent, JobEventKind};
+use crate::domain::repositories::ExclusiveCreate;
 use crate::presentation::{
     ScheduleJobSerializer, ScheduleResultSerializer, ScheduleStatusSerializer,
     ShiftAssignmentSerializer,
 };
 
+/// Header carrying the client-chosen idempotency key for job submission
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Response header set when a submission is answered from an earlier request
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+/// Upper bound (exclusive) for seeds chosen by the service: 2^53
+const MAX_GENERATED_SEED: i64 = 1 << 53;
+
+/// Extract and validate the optional idempotency key header
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, (StatusCode, String)> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+
+    let key = value
+        .to_str()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                "Idempotency-Key must be valid ASCII".to_string(),
+            )
+        })?
+        .trim();
+
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Idempotency-Key must be between 1 and {} characters",
+                MAX_IDEMPOTENCY_KEY_LENGTH
+            ),
+        ));
+    }
+
+    Ok(Some(key.to_string()))
+}
+
+/// Answer a retried submission with the job created by the original request
+fn replay_response(job: ScheduleJob, request_hash: &str) -> Result<Response, (StatusCode, String)> {
+    if job.request_hash.as_deref() != Some(request_hash) {
+        return Err((
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Idempotency-Key was already used with a different request payload".to_string(),
+        ));
+    }
+
+    Ok((
+        StatusCode::ACCEPTED,
+        [(IDEMPOTENT_REPLAYED_HEADER, "true")],
+        Json(ApiResponse::success(
+            "Schedule job accepted for processing",
+            ScheduleJobSerializer::from(job),
+        )),
+    )
+        .into_response())
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/schedules",
     request_body = CreateScheduleRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Client-chosen key; retries with the same key and payload return the original job")
+    ),
     responses(
         (status = 202, description = "Schedule job accepted for processing", body = ApiResponse<ScheduleJobSerializer>),
         (status = 400, description = "Invalid request"),
+        (status = 409, description = "An active job already exists for this group and period"),
+        (status = 422, description = "Idempotency-Key reused with a different payload"),
         (status = 500, description = "Internal server error")
     ),
     tag = "schedules"
 )]
 pub async fn submit_schedule(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(request): Json<CreateScheduleRequest>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+) -> Result<Response, (StatusCode, String)> {
     if request.period_begin_date.weekday().num_days_from_monday() != 0 {
         return Err((
             StatusCode::BAD_REQUEST,
@@ -39,11 +117,58 @@ pub async fn submit_schedule(
         ));
     }
 
-    let job_id = Uuid::new_v4();
+    let mut group_ids = HashSet::from([request.staff_group_id]);
+    if !request
+        .additional_group_ids
+        .iter()
+        .all(|group_id| group_ids.insert(*group_id))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "additional_group_ids must be distinct and must not repeat staff_group_id".to_string(),
+        ));
+    }
+
+    let mut covered = HashSet::new();
+    for target in &request.coverage_targets {
+        if target.shift == ShiftType::DayOff || target.min_staff == 0 {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "coverage_targets need a MORNING or EVENING shift and a min_staff of at least 1"
+                    .to_string(),
+            ));
+        }
+        if !covered.insert((target.group_id, target.shift)) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "coverage_targets must not repeat a group and shift".to_string(),
+            ));
+        }
+    }
+
+    let idempotency_key = idempotency_key(&headers)?;
+    let request_hash = request.request_hash();
+
+    if let Some(key) = &idempotency_key {
+        let existing = state
+            .job_repo
+            .find_by_idempotency_key(key)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        if let Some(job) = existing {
+            return replay_response(job, &request_hash);
+        }
+    }
+
     let now = Utc::now();
+    // Kept below 2^53 so the seed survives a round trip through JavaScript clients
+    let seed = request
+        .seed
+        .unwrap_or_else(|| rand::thread_rng().gen_range(0..MAX_GENERATED_SEED));
 
     let job = ScheduleJob {
-        id: job_id,
+        id: Uuid::new_v4(),
         staff_group_id: request.staff_group_id,
         period_begin_date: request.period_begin_date,
         status: JobStatus::Pending,
@@ -51,18 +176,75 @@ pub async fn submit_schedule(
         created_at: now,
         updated_at: now,
         completed_at: None,
+        idempotency_key: idempotency_key.clone(),
+        request_hash: Some(request_hash.clone()),
+        seed,
+        additional_group_ids: request.additional_group_ids.clone(),
+        coverage_targets: request.coverage_targets.clone(),
     };
 
-    let created_job = state
-        .job_repo
-        .create(job)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let created = match request.duplicate_policy {
+        DuplicatePolicy::Allow => {
+            state
+                .job_repo
+                .create(job)
+                .await
+                .map(|job| ExclusiveCreate::Created {
+                    job,
+                    superseded: Vec::new(),
+                })
+        }
+        policy => {
+            state
+                .job_repo
+                .create_exclusive(job, policy == DuplicatePolicy::Supersede)
+                .await
+        }
+    };
+
+    let created_job = match created {
+        Ok(ExclusiveCreate::Created { job, superseded }) => {
+            for cancelled_job in superseded {
+                state
+                    .event_publisher
+                    .publish(&JobEvent::status_changed(
+                        cancelled_job.id,
+                        cancelled_job.staff_group_id,
+                        cancelled_job.period_begin_date,
+                        JobStatus::Cancelled,
+                        cancelled_job.error_message,
+                    ))
+                    .await;
+            }
+            job
+        }
+        Ok(ExclusiveCreate::Conflict(active_job)) => {
+            return Err((
+                StatusCode::CONFLICT,
+                format!(
+                    "Schedule job {} is already active for this group and period",
+                    active_job.id
+                ),
+            ));
+        }
+        Err(e) => {
+            // A concurrent request with the same key may have won the insert
+            if let Some(key) = &idempotency_key {
+                if let Ok(Some(job)) = state.job_repo.find_by_idempotency_key(key).await {
+                    return replay_response(job, &request_hash);
+                }
+            }
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()));
+        }
+    };
 
     let schedule_request = ScheduleJobRequest {
         job_id: created_job.id,
         staff_group_id: created_job.staff_group_id,
         period_begin_date: created_job.period_begin_date,
+        seed: created_job.seed,
+        additional_group_ids: created_job.additional_group_ids.clone(),
+        coverage_targets: created_job.coverage_targets.clone(),
     };
 
     state
@@ -82,7 +264,8 @@ pub async fn submit_schedule(
             "Schedule job accepted for processing",
             ScheduleJobSerializer::from(created_job),
         )),
-    ))
+    )
+        .into_response())
 }
 
 #[utoipa::path(
@@ -192,3 +375,135 @@ pub async fn get_schedule_result(
 
     Ok((StatusCode::OK, Json(response)))
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/schedules",
+    params(PaginationParams, ScheduleJobFilter),
+    responses(
+        (status = 200, description = "Schedule job list", body = ApiResponse<PaginatedResponse<ScheduleStatusSerializer>>),
+        (status = 400, description = "Invalid filter or pagination parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "schedules"
+)]
+pub async fn list_schedules(
+    State(state): State<AppState>,
+    Query(params): Query<PaginationParams>,
+    Query(filter): Query<ScheduleJobFilter>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    validate_pagination(&params)?;
+
+    if let (Some(from), Some(to)) = (filter.period_from, filter.period_to) {
+        if from > to {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "period_from must not be after period_to".to_string(),
+            ));
+        }
+    }
+
+    let (jobs, total) = state
+        .job_repo
+        .list(&filter.into(), params.clone())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let serialized: Vec<ScheduleStatusSerializer> = jobs
+        .into_iter()
+        .map(ScheduleStatusSerializer::from)
+        .collect();
+
+    let data = PaginatedResponse::new(serialized, params.page, params.page_size, total);
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Schedule list retrieved successfully",
+            data,
+        )),
+    ))
+}
+
+/// Snapshot of a job's current status, sent first on every event stream
+fn current_status_event(job: ScheduleJob) -> JobEvent {
+    JobEvent {
+        schedule_id: job.id,
+        staff_group_id: job.staff_group_id,
+        period_begin_date: job.period_begin_date,
+        occurred_at: job.updated_at,
+        kind: JobEventKind::StatusChanged {
+            status: job.status,
+            error_message: job.error_message,
+        },
+    }
+}
+
+fn sse_event(event: &JobEvent) -> Result<Event, axum::Error> {
+    let name = match event.kind {
+        JobEventKind::StatusChanged { .. } => "status_changed",
+        JobEventKind::Progress { .. } => "progress",
+    };
+    Event::default().event(name).json_data(event)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/schedules/{schedule_id}/events",
+    params(
+        ("schedule_id" = Uuid, Path, description = "Schedule job ID")
+    ),
+    responses(
+        (status = 200, description = "Server-Sent Events stream of `status_changed` and `progress` events, starting with the current status and ending once the job is completed, failed or cancelled", content_type = "text/event-stream"),
+        (status = 404, description = "Schedule not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "schedules"
+)]
+pub async fn stream_schedule_events(
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let job = state
+        .job_repo
+        .find_by_id(schedule_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((StatusCode::NOT_FOUND, "Schedule not found".to_string()))?;
+
+    let current = current_status_event(job);
+
+    let events = if current.is_terminal() {
+        stream::once(future::ready(current)).boxed()
+    } else {
+        // Subscribe before re-reading the job so no transition falls in between
+        let updates = state
+            .event_subscriber
+            .subscribe(schedule_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+        let current = state
+            .job_repo
+            .find_by_id(schedule_id)
+            .await
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+            .map(current_status_event)
+            .unwrap_or(current);
+
+        stream::once(future::ready(current)).chain(updates).boxed()
+    };
+
+    // Forward events up to and including the first terminal status
+    let stream = events
+        .scan(false, |finished, event| {
+            if *finished {
+                return future::ready(None);
+            }
+            *finished = event.is_terminal();
+            future::ready(Some(event))
+        })
+        .map(|event| sse_event(&event));
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
@@ -0,0 +1,246 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use rand::{distributions::Alphanumeric, Rng};
+use shared::{ApiResponse, DomainError, PaginatedResponse, PaginationParams};
+use uuid::Uuid;
+
+use super::validate_pagination;
+use crate::api::requests::CreateWebhookRequest;
+use crate::api::state::AppState;
+use crate::domain::entities::WebhookSubscription;
+use crate::presentation::{WebhookDeliverySerializer, WebhookSubscriptionSerializer};
+
+const GENERATED_SECRET_LENGTH: usize = 32;
+const MIN_SECRET_LENGTH: usize = 16;
+
+fn generate_secret() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(GENERATED_SECRET_LENGTH)
+        .map(char::from)
+        .collect()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook subscription created; the secret is only returned here", body = ApiResponse<WebhookSubscriptionSerializer>),
+        (status = 400, description = "Invalid request"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "webhooks"
+)]
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let url = reqwest::Url::parse(&request.url)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Invalid url: {}", e)))?;
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "url must use http or https".to_string(),
+        ));
+    }
+
+    let secret = match request.secret {
+        Some(secret) if secret.len() < MIN_SECRET_LENGTH => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("secret must be at least {} characters", MIN_SECRET_LENGTH),
+            ));
+        }
+        Some(secret) => secret,
+        None => generate_secret(),
+    };
+
+    let now = Utc::now();
+    let subscription = WebhookSubscription {
+        id: Uuid::new_v4(),
+        url: url.to_string(),
+        secret,
+        staff_group_id: request.staff_group_id,
+        active: true,
+        created_at: now,
+        updated_at: now,
+    };
+
+    let created = state
+        .webhook_repo
+        .create(subscription)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(ApiResponse::success(
+            "Webhook subscription created successfully",
+            WebhookSubscriptionSerializer::with_secret(created),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks",
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Webhook subscription list", body = ApiResponse<PaginatedResponse<WebhookSubscriptionSerializer>>),
+        (status = 400, description = "Invalid pagination parameters"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "webhooks"
+)]
+pub async fn list_webhooks(
+    State(state): State<AppState>,
+    Query(params): Query<PaginationParams>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    validate_pagination(&params)?;
+
+    let (subscriptions, total) = state
+        .webhook_repo
+        .list(params.clone())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let serialized: Vec<WebhookSubscriptionSerializer> = subscriptions
+        .into_iter()
+        .map(WebhookSubscriptionSerializer::from)
+        .collect();
+
+    let data = PaginatedResponse::new(serialized, params.page, params.page_size, total);
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Webhook subscriptions retrieved successfully",
+            data,
+        )),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook subscription ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook subscription retrieved", body = ApiResponse<WebhookSubscriptionSerializer>),
+        (status = 404, description = "Webhook subscription not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "webhooks"
+)]
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let subscription = state
+        .webhook_repo
+        .find_by_id(webhook_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Webhook subscription not found".to_string(),
+        ))?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Webhook subscription retrieved successfully",
+            WebhookSubscriptionSerializer::from(subscription),
+        )),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/webhooks/{webhook_id}",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook subscription ID")
+    ),
+    responses(
+        (status = 204, description = "Webhook subscription deleted"),
+        (status = 404, description = "Webhook subscription not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "webhooks"
+)]
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    state
+        .webhook_repo
+        .delete(webhook_id)
+        .await
+        .map_err(|e| match e {
+            DomainError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/webhooks/{webhook_id}/deliveries",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook subscription ID"),
+        PaginationParams
+    ),
+    responses(
+        (status = 200, description = "Delivery attempts, newest first", body = ApiResponse<PaginatedResponse<WebhookDeliverySerializer>>),
+        (status = 400, description = "Invalid pagination parameters"),
+        (status = 404, description = "Webhook subscription not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "webhooks"
+)]
+pub async fn list_webhook_deliveries(
+    State(state): State<AppState>,
+    Path(webhook_id): Path<Uuid>,
+    Query(params): Query<PaginationParams>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    validate_pagination(&params)?;
+
+    state
+        .webhook_repo
+        .find_by_id(webhook_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .ok_or((
+            StatusCode::NOT_FOUND,
+            "Webhook subscription not found".to_string(),
+        ))?;
+
+    let (deliveries, total) = state
+        .webhook_repo
+        .list_deliveries(webhook_id, params.clone())
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+
+    let serialized: Vec<WebhookDeliverySerializer> = deliveries
+        .into_iter()
+        .map(WebhookDeliverySerializer::from)
+        .collect();
+
+    let data = PaginatedResponse::new(serialized, params.page, params.page_size, total);
+
+    Ok((
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            "Webhook deliveries retrieved successfully",
+            data,
+        )),
+    ))
+}
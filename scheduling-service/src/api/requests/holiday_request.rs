@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateHolidayCalendarRequest {
+    pub name: String,
+    /// Attach the calendar to one staff group; omit to apply it to every group
+    pub staff_group_id: Option<Uuid>,
+    /// Share of the usual morning and evening staffing needed on a holiday, 0-100 (default 100)
+    pub coverage_percent: Option<i32>,
+    /// Days off on holidays are paid leave and do not use up the weekly days off (default false)
+    #[serde(default)]
+    pub paid_leave: bool,
+    /// How many holiday shifts a worked holiday counts as when rotating burden, at least 1 (default 1)
+    pub premium_weight: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateHolidayRequest {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// Filters for listing the holidays of a calendar
+#[derive(Debug, Clone, Default, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct HolidayFilter {
+    /// Only holidays on or after this date
+    pub from: Option<NaiveDate>,
+    /// Only holidays on or before this date
+    pub to: Option<NaiveDate>,
+}
+
+/// Filters for listing holiday calendars
+#[derive(Debug, Clone, Default, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct HolidayCalendarFilter {
+    /// Only calendars attached to this staff group
+    pub staff_group_id: Option<Uuid>,
+}
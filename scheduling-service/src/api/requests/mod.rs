@@ -1,3 +1,11 @@
+pub mod holiday_request;
 pub mod schedule_request;
+pub mod webhook_request;
 
-pub use schedule_request::CreateScheduleRequest;
+pub use holiday_request::{
+    CreateHolidayCalendarRequest, CreateHolidayRequest, HolidayCalendarFilter, HolidayFilter,
+};
+pub use schedule_request::{
+    CreateScheduleRequest, DuplicatePolicy, ExportFormat, ScheduleExportQuery, ScheduleJobFilter,
+};
+pub use webhook_request::CreateWebhookRequest;
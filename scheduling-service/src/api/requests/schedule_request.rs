@@ -1,12 +1,57 @@
 use chrono::NaiveDate;
-use serde::Deserialize;
-use utoipa::ToSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared::JobStatus;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-#[derive(Debug, Deserialize, ToSchema)]
+use crate::domain::entities::CoverageTarget;
+use crate::domain::repositories::JobFilter;
+
+/// What to do when an active job already exists for the same group and period
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DuplicatePolicy {
+    /// Create the new job alongside the existing one
+    #[default]
+    Allow,
+    /// Refuse the submission with 409 Conflict
+    Reject,
+    /// Cancel the existing job and create the new one
+    Supersede,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct CreateScheduleRequest {
     pub staff_group_id: Uuid,
     pub period_begin_date: NaiveDate,
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy,
+    /// Seed for the generator. Submitting the same seed for the same staff
+    /// reproduces a roster exactly; a random seed is chosen when omitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<i64>,
+    /// Further groups to schedule in the same job. All groups share one staff
+    /// pool: someone in several of them gets one shift a day, worked for one group.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_group_ids: Vec<Uuid>,
+    /// Minimum daily staffing for subgroups of the scheduled groups, such as
+    /// one evening shift a day for a team. Filled from the subgroup's members
+    /// before the rest of the day is staffed.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coverage_targets: Vec<CoverageTarget>,
+}
+
+impl CreateScheduleRequest {
+    /// SHA-256 of the canonical JSON form of the request, used to detect
+    /// an idempotency key being reused with a different payload
+    pub fn request_hash(&self) -> String {
+        // serde_json::Value keeps object keys sorted, so the encoding is stable
+        let canonical = serde_json::to_value(self)
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        hex::encode(Sha256::digest(canonical.as_bytes()))
+    }
 }
 
 #[derive(Debug)]
@@ -14,4 +59,84 @@ pub struct ScheduleJobRequest {
     pub job_id: Uuid,
     pub staff_group_id: Uuid,
     pub period_begin_date: NaiveDate,
+    pub seed: i64,
+    pub additional_group_ids: Vec<Uuid>,
+    pub coverage_targets: Vec<CoverageTarget>,
+}
+
+impl ScheduleJobRequest {
+    /// Every group the job schedules, `staff_group_id` first
+    pub fn group_ids(&self) -> Vec<Uuid> {
+        std::iter::once(self.staff_group_id)
+            .chain(self.additional_group_ids.iter().copied())
+            .collect()
+    }
+}
+
+/// File format of a schedule export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+    /// Printable weekly roster
+    Html,
+    /// Printable weekly roster
+    Pdf,
+}
+
+impl ExportFormat {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv; charset=utf-8",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Html => "text/html; charset=utf-8",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Xlsx => "xlsx",
+            Self::Html => "html",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    /// Printable formats open in the browser instead of downloading
+    pub fn is_printable(self) -> bool {
+        matches!(self, Self::Html | Self::Pdf)
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleExportQuery {
+    pub format: ExportFormat,
+}
+
+/// Filters for listing schedule jobs. All filters are optional and combined with AND.
+#[derive(Debug, Clone, Default, Deserialize, IntoParams, ToSchema)]
+#[into_params(parameter_in = Query)]
+pub struct ScheduleJobFilter {
+    /// Only jobs for this staff group
+    pub staff_group_id: Option<Uuid>,
+    /// Only jobs in this status
+    pub status: Option<JobStatus>,
+    /// Only jobs whose period begins on or after this date
+    pub period_from: Option<NaiveDate>,
+    /// Only jobs whose period begins on or before this date
+    pub period_to: Option<NaiveDate>,
+}
+
+impl From<ScheduleJobFilter> for JobFilter {
+    fn from(filter: ScheduleJobFilter) -> Self {
+        Self {
+            staff_group_id: filter.staff_group_id,
+            status: filter.status,
+            period_from: filter.period_from,
+            period_to: filter.period_to,
+        }
+    }
 }
@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// HTTP(S) endpoint that receives the signed event payloads
+    pub url: String,
+    /// Restrict the subscription to one staff group; omit to receive events for all groups
+    pub staff_group_id: Option<Uuid>,
+    /// Signing secret; generated when omitted
+    pub secret: Option<String>,
+}
@@ -1,5 +1,5 @@
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, post},
     Router,
 };
 use tower_http::trace::TraceLayer;
@@ -8,11 +8,13 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::api::handlers;
 use crate::api::state::AppState;
+use crate::domain::entities::HolidaySource;
 use crate::presentation::{
-    ScheduleJobSerializer, ScheduleResultSerializer, ScheduleStatusSerializer,
-    ShiftAssignmentSerializer,
+    HolidayCalendarSerializer, HolidayImportSerializer, HolidaySerializer, ScheduleJobSerializer,
+    ScheduleResultSerializer, ScheduleStatusSerializer, ShiftAssignmentSerializer,
+    WebhookDeliverySerializer, WebhookSubscriptionSerializer,
 };
-use shared::{JobStatus, ShiftType};
+use shared::{JobStatus, PaginatedResponse, ShiftType};
 
 #[derive(OpenApi)]
 #[openapi(
@@ -25,12 +27,59 @@ use shared::{JobStatus, ShiftType};
         crate::api::handlers::schedule_handlers::submit_schedule,
         crate::api::handlers::schedule_handlers::get_schedule_status,
         crate::api::handlers::schedule_handlers::get_schedule_result,
+        crate::api::handlers::schedule_handlers::list_schedules,
+        crate::api::handlers::schedule_handlers::stream_schedule_events,
+        crate::api::handlers::export_handlers::export_schedule,
+        crate::api::handlers::calendar_handlers::get_schedule_calendar,
+        crate::api::handlers::calendar_handlers::get_staff_calendar,
+        crate::api::handlers::webhook_handlers::create_webhook,
+        crate::api::handlers::webhook_handlers::list_webhooks,
+        crate::api::handlers::webhook_handlers::get_webhook,
+        crate::api::handlers::webhook_handlers::delete_webhook,
+        crate::api::handlers::webhook_handlers::list_webhook_deliveries,
+        crate::api::handlers::holiday_handlers::create_holiday_calendar,
+        crate::api::handlers::holiday_handlers::list_holiday_calendars,
+        crate::api::handlers::holiday_handlers::get_holiday_calendar,
+        crate::api::handlers::holiday_handlers::delete_holiday_calendar,
+        crate::api::handlers::holiday_handlers::list_holidays,
+        crate::api::handlers::holiday_handlers::add_holiday,
+        crate::api::handlers::holiday_handlers::delete_holiday,
+        crate::api::handlers::holiday_handlers::import_holidays,
     ),
     components(schemas(
         crate::api::requests::CreateScheduleRequest,
+        crate::api::requests::DuplicatePolicy,
+        crate::domain::entities::CoverageTarget,
+        crate::api::requests::ScheduleJobFilter,
+        crate::api::requests::ExportFormat,
+        crate::api::requests::CreateWebhookRequest,
+        crate::api::requests::CreateHolidayCalendarRequest,
+        crate::api::requests::CreateHolidayRequest,
+        crate::api::requests::HolidayCalendarFilter,
+        crate::api::requests::HolidayFilter,
+        shared::PaginationParams,
         shared::ApiResponse<ScheduleJobSerializer>,
         shared::ApiResponse<ScheduleStatusSerializer>,
         shared::ApiResponse<ScheduleResultSerializer>,
+        shared::ApiResponse<PaginatedResponse<ScheduleStatusSerializer>>,
+        PaginatedResponse<ScheduleStatusSerializer>,
+        shared::ApiResponse<WebhookSubscriptionSerializer>,
+        shared::ApiResponse<PaginatedResponse<WebhookSubscriptionSerializer>>,
+        shared::ApiResponse<PaginatedResponse<WebhookDeliverySerializer>>,
+        PaginatedResponse<WebhookSubscriptionSerializer>,
+        PaginatedResponse<WebhookDeliverySerializer>,
+        WebhookSubscriptionSerializer,
+        WebhookDeliverySerializer,
+        shared::ApiResponse<HolidayCalendarSerializer>,
+        shared::ApiResponse<PaginatedResponse<HolidayCalendarSerializer>>,
+        shared::ApiResponse<Vec<HolidaySerializer>>,
+        shared::ApiResponse<HolidaySerializer>,
+        shared::ApiResponse<HolidayImportSerializer>,
+        PaginatedResponse<HolidayCalendarSerializer>,
+        HolidayCalendarSerializer,
+        HolidaySerializer,
+        HolidayImportSerializer,
+        HolidaySource,
         ScheduleJobSerializer,
         ScheduleStatusSerializer,
         ScheduleResultSerializer,
@@ -43,14 +92,65 @@ struct ApiDoc;
 
 pub fn create_router(state: AppState) -> Router {
     let api_router = Router::new()
-        .route("/schedules", post(handlers::submit_schedule))
+        .route(
+            "/schedules",
+            post(handlers::submit_schedule).get(handlers::list_schedules),
+        )
         .route(
             "/schedules/:schedule_id/status",
             get(handlers::get_schedule_status),
         )
+        .route(
+            "/schedules/:schedule_id/events",
+            get(handlers::stream_schedule_events),
+        )
         .route(
             "/schedules/:schedule_id",
             get(handlers::get_schedule_result),
+        )
+        .route(
+            "/schedules/:schedule_id/export",
+            get(handlers::export_schedule),
+        )
+        .route(
+            "/schedules/:schedule_id/calendar.ics",
+            get(handlers::get_schedule_calendar),
+        )
+        .route(
+            "/staff/:staff_id/calendar.ics",
+            get(handlers::get_staff_calendar),
+        )
+        .route(
+            "/webhooks",
+            post(handlers::create_webhook).get(handlers::list_webhooks),
+        )
+        .route(
+            "/webhooks/:webhook_id",
+            get(handlers::get_webhook).delete(handlers::delete_webhook),
+        )
+        .route(
+            "/webhooks/:webhook_id/deliveries",
+            get(handlers::list_webhook_deliveries),
+        )
+        .route(
+            "/holiday-calendars",
+            post(handlers::create_holiday_calendar).get(handlers::list_holiday_calendars),
+        )
+        .route(
+            "/holiday-calendars/:calendar_id",
+            get(handlers::get_holiday_calendar).delete(handlers::delete_holiday_calendar),
+        )
+        .route(
+            "/holiday-calendars/:calendar_id/holidays",
+            get(handlers::list_holidays).post(handlers::add_holiday),
+        )
+        .route(
+            "/holiday-calendars/:calendar_id/holidays/:holiday_id",
+            delete(handlers::delete_holiday),
+        )
+        .route(
+            "/holiday-calendars/:calendar_id/import",
+            post(handlers::import_holidays),
         );
 
     Router::new()
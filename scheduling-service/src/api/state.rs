@@ -1,5 +1,10 @@
 use crate::api::requests::schedule_request::ScheduleJobRequest;
-use crate::domain::repositories::{ScheduleJobRepository, ShiftAssignmentRepository};
+use crate::domain::events::{JobEventPublisher, JobEventSubscriber};
+use crate::domain::repositories::{
+    HolidayCalendarRepository, ScheduleJobRepository, ShiftAssignmentRepository, WebhookRepository,
+};
+use crate::infrastructure::config::CalendarSettings;
+use crate::infrastructure::http_client::DataServiceClientTrait;
 use crate::infrastructure::redis::RedisPool;
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -8,22 +13,47 @@ use tokio::sync::mpsc;
 pub struct AppState {
     pub job_repo: Arc<dyn ScheduleJobRepository>,
     pub assignment_repo: Arc<dyn ShiftAssignmentRepository>,
+    pub webhook_repo: Arc<dyn WebhookRepository>,
+    pub holiday_repo: Arc<dyn HolidayCalendarRepository>,
+    /// Looks up staff names for exports
+    pub data_service_client: Arc<dyn DataServiceClientTrait>,
+    pub event_publisher: Arc<dyn JobEventPublisher>,
+    pub event_subscriber: Arc<dyn JobEventSubscriber>,
     pub schedule_sender: mpsc::Sender<ScheduleJobRequest>,
     pub redis_pool: RedisPool,
+    pub calendar: CalendarSettings,
 }
 
 impl AppState {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         job_repo: Arc<dyn ScheduleJobRepository>,
         assignment_repo: Arc<dyn ShiftAssignmentRepository>,
+        webhook_repo: Arc<dyn WebhookRepository>,
+        holiday_repo: Arc<dyn HolidayCalendarRepository>,
+        data_service_client: Arc<dyn DataServiceClientTrait>,
+        event_publisher: Arc<dyn JobEventPublisher>,
+        event_subscriber: Arc<dyn JobEventSubscriber>,
         schedule_sender: mpsc::Sender<ScheduleJobRequest>,
         redis_pool: RedisPool,
     ) -> Self {
         Self {
             job_repo,
             assignment_repo,
+            webhook_repo,
+            holiday_repo,
+            data_service_client,
+            event_publisher,
+            event_subscriber,
             schedule_sender,
             redis_pool,
+            calendar: CalendarSettings::default(),
         }
     }
+
+    /// Shift times used in calendar feeds instead of the defaults
+    pub fn with_calendar_settings(mut self, calendar: CalendarSettings) -> Self {
+        self.calendar = calendar;
+        self
+    }
 }
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use shared::ShiftType;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Minimum staffing one group of the resolved hierarchy needs every day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CoverageTarget {
+    /// The scheduled group or any of its subgroups; members of its own
+    /// subgroups count towards the target
+    pub group_id: Uuid,
+    /// Morning or evening
+    pub shift: ShiftType,
+    /// Members of the group needed on the shift each day
+    pub min_staff: u32,
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{Identifiable, Timestamped};
+use sqlx::FromRow;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A set of public holidays and how scheduling treats them, for one staff group or all of them
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct HolidayCalendar {
+    pub id: Uuid,
+    pub name: String,
+    /// `None` applies the calendar to every group
+    pub staff_group_id: Option<Uuid>,
+    /// Share of the usual morning and evening staffing needed on a holiday
+    pub coverage_percent: i32,
+    /// A day off on a holiday is paid leave and does not use up the weekly days off
+    pub paid_leave: bool,
+    /// How many holiday shifts a worked holiday counts as when rotating burden
+    pub premium_weight: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::Type)]
+#[sqlx(type_name = "holiday_source", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HolidaySource {
+    /// Entered through the API
+    Manual,
+    /// Imported from an iCalendar file
+    Ics,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Holiday {
+    pub id: Uuid,
+    pub calendar_id: Uuid,
+    pub date: NaiveDate,
+    pub name: String,
+    pub source: HolidaySource,
+    /// UID of the imported VEVENT
+    pub external_uid: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Identifiable for HolidayCalendar {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+impl Timestamped for HolidayCalendar {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
@@ -1,5 +1,11 @@
+pub mod coverage_target;
+pub mod holiday;
 pub mod schedule_job;
 pub mod shift_assignment;
+pub mod webhook;
 
+pub use coverage_target::CoverageTarget;
+pub use holiday::{Holiday, HolidayCalendar, HolidaySource};
 pub use schedule_job::ScheduleJob;
 pub use shift_assignment::ShiftAssignment;
+pub use webhook::{WebhookDelivery, WebhookSubscription};
@@ -4,6 +4,8 @@ use shared::{Identifiable, JobStatus, Timestamped};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+use crate::domain::entities::CoverageTarget;
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ScheduleJob {
     pub id: Uuid,
@@ -14,6 +16,15 @@ pub struct ScheduleJob {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub idempotency_key: Option<String>,
+    pub request_hash: Option<String>,
+    /// Seed the roster was generated from; the same seed and staff give the same roster
+    pub seed: i64,
+    /// Groups scheduled together with `staff_group_id`, sharing one staff pool
+    pub additional_group_ids: Vec<Uuid>,
+    /// Minimum daily staffing of subgroups within the scheduled groups
+    #[sqlx(json)]
+    pub coverage_targets: Vec<CoverageTarget>,
 }
 
 impl Identifiable for ScheduleJob {
@@ -9,7 +9,11 @@ pub struct ShiftAssignment {
     pub id: Uuid,
     pub schedule_job_id: Uuid,
     pub staff_id: Uuid,
+    /// Group the shift is worked for
+    pub staff_group_id: Uuid,
     pub date: NaiveDate,
     pub shift: ShiftType,
     pub created_at: DateTime<Utc>,
+    /// Last change to the assignment; calendar feeds derive the event revision from it
+    pub updated_at: DateTime<Utc>,
 }
@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::{Identifiable, Timestamped};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A URL that receives signed job events, either for one staff group or for all of them
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookSubscription {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    /// `None` subscribes to events for every group
+    pub staff_group_id: Option<Uuid>,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One delivery attempt of an event to a subscription
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    /// Shared by every attempt at delivering the same event
+    pub delivery_id: Uuid,
+    pub subscription_id: Uuid,
+    pub schedule_job_id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Identifiable for WebhookSubscription {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+impl Timestamped for WebhookSubscription {
+    fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+}
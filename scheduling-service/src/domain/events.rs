@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+use shared::{DomainResult, JobStatus};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Something that happened to a schedule job while it was being processed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobEvent {
+    pub schedule_id: Uuid,
+    pub staff_group_id: Uuid,
+    pub period_begin_date: NaiveDate,
+    pub occurred_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub kind: JobEventKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum JobEventKind {
+    /// The job moved to a new status
+    StatusChanged {
+        status: JobStatus,
+        error_message: Option<String>,
+    },
+    /// The job reached a processing stage; `percent` is a rough estimate
+    Progress { stage: String, percent: u8 },
+}
+
+impl JobEvent {
+    pub fn status_changed(
+        schedule_id: Uuid,
+        staff_group_id: Uuid,
+        period_begin_date: NaiveDate,
+        status: JobStatus,
+        error_message: Option<String>,
+    ) -> Self {
+        Self {
+            schedule_id,
+            staff_group_id,
+            period_begin_date,
+            occurred_at: Utc::now(),
+            kind: JobEventKind::StatusChanged {
+                status,
+                error_message,
+            },
+        }
+    }
+
+    pub fn progress(
+        schedule_id: Uuid,
+        staff_group_id: Uuid,
+        period_begin_date: NaiveDate,
+        stage: impl Into<String>,
+        percent: u8,
+    ) -> Self {
+        Self {
+            schedule_id,
+            staff_group_id,
+            period_begin_date,
+            occurred_at: Utc::now(),
+            kind: JobEventKind::Progress {
+                stage: stage.into(),
+                percent,
+            },
+        }
+    }
+
+    /// Name used to identify the event to external consumers
+    pub fn event_type(&self) -> &'static str {
+        match self.kind {
+            JobEventKind::StatusChanged { .. } => "schedule.status_changed",
+            JobEventKind::Progress { .. } => "schedule.progress",
+        }
+    }
+
+    /// Whether this is the last event a job will ever produce
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.kind,
+            JobEventKind::StatusChanged {
+                status: JobStatus::Completed | JobStatus::Failed | JobStatus::Cancelled,
+                ..
+            }
+        )
+    }
+}
+
+#[async_trait]
+pub trait JobEventPublisher: Send + Sync {
+    /// Publish an event. Delivery problems are handled by the publisher and
+    /// never fail the job that raised the event.
+    async fn publish(&self, event: &JobEvent);
+}
+
+/// Fans each event out to a list of publishers, in order
+#[derive(Default)]
+pub struct CompositeJobEventPublisher {
+    publishers: Vec<Arc<dyn JobEventPublisher>>,
+}
+
+impl CompositeJobEventPublisher {
+    pub fn new(publishers: Vec<Arc<dyn JobEventPublisher>>) -> Self {
+        Self { publishers }
+    }
+}
+
+#[async_trait]
+impl JobEventPublisher for CompositeJobEventPublisher {
+    async fn publish(&self, event: &JobEvent) {
+        for publisher in &self.publishers {
+            publisher.publish(event).await;
+        }
+    }
+}
+
+pub type JobEventStream = BoxStream<'static, JobEvent>;
+
+#[async_trait]
+pub trait JobEventSubscriber: Send + Sync {
+    /// Stream the events published for one job from now on
+    async fn subscribe(&self, schedule_id: Uuid) -> DomainResult<JobEventStream>;
+}
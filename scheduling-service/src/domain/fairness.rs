@@ -0,0 +1,211 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use shared::ShiftType;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::ShiftAssignment;
+use crate::domain::holiday_schedule::HolidaySchedule;
+
+/// Kinds of shift that staff would rather not work and that should be shared out evenly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Burden {
+    /// Any evening shift
+    Evening,
+    /// A morning or evening shift on a Saturday or Sunday
+    Weekend,
+    /// A morning or evening shift on a public holiday, weighted by the
+    /// calendar's premium
+    Holiday,
+}
+
+/// How many shifts of each burden a staff member has worked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BurdenCounts {
+    pub evenings: u32,
+    pub weekends: u32,
+    pub holidays: u32,
+}
+
+impl BurdenCounts {
+    pub fn get(&self, burden: Burden) -> u32 {
+        match burden {
+            Burden::Evening => self.evenings,
+            Burden::Weekend => self.weekends,
+            Burden::Holiday => self.holidays,
+        }
+    }
+
+    fn add(&mut self, burden: Burden, amount: u32) {
+        match burden {
+            Burden::Evening => self.evenings += amount,
+            Burden::Weekend => self.weekends += amount,
+            Burden::Holiday => self.holidays += amount,
+        }
+    }
+}
+
+/// Cumulative burden per staff member.
+///
+/// Seeded from assignments of earlier periods and updated while a new period
+/// is generated, so the generator can hand the next undesirable shift to whoever
+/// has carried the least of it so far.
+#[derive(Debug, Clone, Default)]
+pub struct FairnessLedger {
+    holidays: HolidaySchedule,
+    counts: HashMap<Uuid, BurdenCounts>,
+}
+
+impl FairnessLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classify shifts on the given holidays as holiday shifts
+    pub fn with_holidays(mut self, holidays: HolidaySchedule) -> Self {
+        self.holidays = holidays;
+        self
+    }
+
+    /// Ledger seeded with the burden of earlier assignments
+    pub fn from_assignments<'a>(
+        holidays: HolidaySchedule,
+        assignments: impl IntoIterator<Item = &'a ShiftAssignment>,
+    ) -> Self {
+        let mut ledger = Self::new().with_holidays(holidays);
+        for assignment in assignments {
+            ledger.record(assignment.staff_id, assignment.date, assignment.shift);
+        }
+        ledger
+    }
+
+    pub fn is_weekend(date: NaiveDate) -> bool {
+        matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays.is_holiday(date)
+    }
+
+    /// Burdens a shift on the given date counts towards
+    pub fn burdens(&self, date: NaiveDate, shift: ShiftType) -> impl Iterator<Item = Burden> {
+        let works = shift != ShiftType::DayOff;
+        [
+            (shift == ShiftType::Evening).then_some(Burden::Evening),
+            (works && Self::is_weekend(date)).then_some(Burden::Weekend),
+            (works && self.is_holiday(date)).then_some(Burden::Holiday),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    pub fn record(&mut self, staff_id: Uuid, date: NaiveDate, shift: ShiftType) {
+        let burdens: Vec<Burden> = self.burdens(date, shift).collect();
+        if burdens.is_empty() {
+            return;
+        }
+        let premium_weight = self.holidays.premium_weight(date);
+        let counts = self.counts.entry(staff_id).or_default();
+        for burden in burdens {
+            let amount = match burden {
+                Burden::Holiday => premium_weight,
+                _ => 1,
+            };
+            counts.add(burden, amount);
+        }
+    }
+
+    pub fn counts(&self, staff_id: Uuid) -> BurdenCounts {
+        self.counts.get(&staff_id).copied().unwrap_or_default()
+    }
+
+    pub fn count(&self, staff_id: Uuid, burden: Burden) -> u32 {
+        self.counts(staff_id).get(burden)
+    }
+
+    /// Lowest count of a burden among the given staff
+    pub fn min_count(&self, staff_ids: &[Uuid], burden: Burden) -> u32 {
+        staff_ids
+            .iter()
+            .map(|id| self.count(*id, burden))
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::holiday_schedule::HolidayPolicy;
+
+    fn holidays(dates: &[NaiveDate], premium_weight: u32) -> HolidaySchedule {
+        let mut schedule = HolidaySchedule::new();
+        for date in dates {
+            schedule.add(
+                *date,
+                HolidayPolicy {
+                    coverage_percent: 100,
+                    paid_leave: false,
+                    premium_weight,
+                },
+            );
+        }
+        schedule
+    }
+
+    #[test]
+    fn test_shift_classification() {
+        let saturday = NaiveDate::from_ymd_opt(2024, 1, 20).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 22).unwrap();
+        let ledger = FairnessLedger::new().with_holidays(holidays(&[monday], 1));
+
+        let burdens = |date, shift| ledger.burdens(date, shift).collect::<Vec<_>>();
+
+        assert_eq!(
+            burdens(saturday, ShiftType::Evening),
+            vec![Burden::Evening, Burden::Weekend]
+        );
+        assert_eq!(burdens(saturday, ShiftType::DayOff), vec![]);
+        assert_eq!(burdens(monday, ShiftType::Morning), vec![Burden::Holiday]);
+        assert_eq!(
+            burdens(monday - chrono::Duration::days(6), ShiftType::Morning),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_counts_accumulate_per_staff() {
+        let staff_id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let sunday = NaiveDate::from_ymd_opt(2024, 1, 21).unwrap();
+        let mut ledger = FairnessLedger::new();
+
+        ledger.record(staff_id, sunday, ShiftType::Evening);
+        ledger.record(
+            staff_id,
+            sunday + chrono::Duration::days(1),
+            ShiftType::Evening,
+        );
+
+        assert_eq!(
+            ledger.counts(staff_id),
+            BurdenCounts {
+                evenings: 2,
+                weekends: 1,
+                holidays: 0
+            }
+        );
+        assert_eq!(ledger.min_count(&[staff_id, other], Burden::Evening), 0);
+    }
+
+    #[test]
+    fn test_holiday_shifts_carry_premium_weight() {
+        let staff_id = Uuid::new_v4();
+        let christmas = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        let mut ledger = FairnessLedger::new().with_holidays(holidays(&[christmas], 3));
+
+        ledger.record(staff_id, christmas, ShiftType::Morning);
+        ledger.record(staff_id, christmas, ShiftType::DayOff);
+
+        assert_eq!(ledger.count(staff_id, Burden::Holiday), 3);
+    }
+}
@@ -0,0 +1,134 @@
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::{Holiday, HolidayCalendar};
+
+/// How scheduling treats one holiday
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HolidayPolicy {
+    /// Share of the usual morning and evening staffing needed, 0-100
+    pub coverage_percent: u32,
+    /// A day off does not count towards the weekly days-off limits
+    pub paid_leave: bool,
+    /// Holiday shifts a worked shift counts as for fairness
+    pub premium_weight: u32,
+}
+
+impl From<&HolidayCalendar> for HolidayPolicy {
+    fn from(calendar: &HolidayCalendar) -> Self {
+        Self {
+            coverage_percent: calendar.coverage_percent.clamp(0, 100) as u32,
+            paid_leave: calendar.paid_leave,
+            premium_weight: calendar.premium_weight.max(1) as u32,
+        }
+    }
+}
+
+impl HolidayPolicy {
+    /// Combine two calendars that list the same date: the lowest coverage,
+    /// paid leave if either grants it, and the higher premium
+    fn merge(self, other: Self) -> Self {
+        Self {
+            coverage_percent: self.coverage_percent.min(other.coverage_percent),
+            paid_leave: self.paid_leave || other.paid_leave,
+            premium_weight: self.premium_weight.max(other.premium_weight),
+        }
+    }
+}
+
+/// Holidays that apply to a schedule, merged from every calendar of the group
+#[derive(Debug, Clone, Default)]
+pub struct HolidaySchedule {
+    days: HashMap<NaiveDate, HolidayPolicy>,
+}
+
+impl HolidaySchedule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build from calendars and their holidays; holidays of unknown calendars are ignored
+    pub fn from_calendars(calendars: &[HolidayCalendar], holidays: &[Holiday]) -> Self {
+        let policies: HashMap<Uuid, HolidayPolicy> = calendars
+            .iter()
+            .map(|calendar| (calendar.id, HolidayPolicy::from(calendar)))
+            .collect();
+
+        let mut schedule = Self::new();
+        for holiday in holidays {
+            if let Some(policy) = policies.get(&holiday.calendar_id) {
+                schedule.add(holiday.date, *policy);
+            }
+        }
+        schedule
+    }
+
+    pub fn add(&mut self, date: NaiveDate, policy: HolidayPolicy) {
+        self.days
+            .entry(date)
+            .and_modify(|existing| *existing = existing.merge(policy))
+            .or_insert(policy);
+    }
+
+    pub fn get(&self, date: NaiveDate) -> Option<&HolidayPolicy> {
+        self.days.get(&date)
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.days.contains_key(&date)
+    }
+
+    pub fn is_paid_leave(&self, date: NaiveDate) -> bool {
+        self.get(date).is_some_and(|policy| policy.paid_leave)
+    }
+
+    /// Share of the usual staffing needed on a date; 100 on ordinary days
+    pub fn coverage_percent(&self, date: NaiveDate) -> u32 {
+        self.get(date).map_or(100, |policy| policy.coverage_percent)
+    }
+
+    /// Holiday shifts a worked shift on the date counts as; 0 on ordinary days
+    pub fn premium_weight(&self, date: NaiveDate) -> u32 {
+        self.get(date).map_or(0, |policy| policy.premium_weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_calendars_merge_to_strictest_policy() {
+        let date = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        let mut schedule = HolidaySchedule::new();
+
+        schedule.add(
+            date,
+            HolidayPolicy {
+                coverage_percent: 50,
+                paid_leave: false,
+                premium_weight: 3,
+            },
+        );
+        schedule.add(
+            date,
+            HolidayPolicy {
+                coverage_percent: 80,
+                paid_leave: true,
+                premium_weight: 2,
+            },
+        );
+
+        assert_eq!(
+            schedule.get(date),
+            Some(&HolidayPolicy {
+                coverage_percent: 50,
+                paid_leave: true,
+                premium_weight: 3,
+            })
+        );
+        assert_eq!(schedule.coverage_percent(date.succ_opt().unwrap()), 100);
+        assert_eq!(schedule.premium_weight(date.succ_opt().unwrap()), 0);
+    }
+}
@@ -1,4 +1,9 @@
 pub mod entities;
+pub mod events;
+pub mod fairness;
+pub mod holiday_schedule;
 pub mod repositories;
 pub mod rules;
 pub mod schedule_generator;
+pub mod schedule_state;
+pub mod staff_pool;
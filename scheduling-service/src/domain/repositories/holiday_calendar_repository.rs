@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use shared::{DomainResult, PaginationParams};
+use uuid::Uuid;
+
+use crate::domain::entities::{Holiday, HolidayCalendar};
+
+#[async_trait]
+pub trait HolidayCalendarRepository: Send + Sync {
+    /// Create a new holiday calendar
+    async fn create(&self, calendar: HolidayCalendar) -> DomainResult<HolidayCalendar>;
+
+    /// Find calendar by ID
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<HolidayCalendar>>;
+
+    /// List calendars with pagination, optionally only those attached to a group
+    async fn list(
+        &self,
+        staff_group_id: Option<Uuid>,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<HolidayCalendar>, u64)>;
+
+    /// Delete calendar by ID, together with its holidays
+    async fn delete(&self, id: Uuid) -> DomainResult<()>;
+
+    /// Find the calendars that apply to a group, including global ones
+    async fn find_for_group(&self, staff_group_id: Uuid) -> DomainResult<Vec<HolidayCalendar>>;
+
+    /// Insert holidays, replacing the name and source of any entry the
+    /// calendar already has on the same date. Returns the stored holidays.
+    async fn upsert_holidays(&self, holidays: Vec<Holiday>) -> DomainResult<Vec<Holiday>>;
+
+    /// List the holidays of a calendar in `[from, to]`, ordered by date
+    async fn list_holidays(
+        &self,
+        calendar_id: Uuid,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> DomainResult<Vec<Holiday>>;
+
+    /// Delete one holiday of a calendar
+    async fn delete_holiday(&self, calendar_id: Uuid, holiday_id: Uuid) -> DomainResult<()>;
+
+    /// Holidays of any of the given calendars dated in `[from, to)`
+    async fn find_holidays(
+        &self,
+        calendar_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<Holiday>>;
+}
@@ -1,5 +1,11 @@
+pub mod holiday_calendar_repository;
 pub mod schedule_job_repository;
 pub mod shift_assignment_repository;
+pub mod unit_of_work;
+pub mod webhook_repository;
 
-pub use schedule_job_repository::ScheduleJobRepository;
+pub use holiday_calendar_repository::HolidayCalendarRepository;
+pub use schedule_job_repository::{ExclusiveCreate, JobFilter, ScheduleJobRepository};
 pub use shift_assignment_repository::ShiftAssignmentRepository;
+pub use unit_of_work::{Transaction, UnitOfWork};
+pub use webhook_repository::WebhookRepository;
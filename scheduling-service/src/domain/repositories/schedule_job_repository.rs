@@ -1,9 +1,33 @@
 use async_trait::async_trait;
-use shared::{DomainResult, JobStatus};
+use chrono::NaiveDate;
+use shared::{DomainResult, JobStatus, PaginationParams};
 use uuid::Uuid;
 
 use crate::domain::entities::ScheduleJob;
 
+/// Criteria for listing schedule jobs. Unset fields match every job.
+#[derive(Debug, Clone, Default)]
+pub struct JobFilter {
+    pub staff_group_id: Option<Uuid>,
+    pub status: Option<JobStatus>,
+    /// Earliest period begin date, inclusive
+    pub period_from: Option<NaiveDate>,
+    /// Latest period begin date, inclusive
+    pub period_to: Option<NaiveDate>,
+}
+
+/// Outcome of creating a job that must be the only active one
+#[derive(Debug)]
+pub enum ExclusiveCreate {
+    /// The job was created after cancelling these active jobs
+    Created {
+        job: ScheduleJob,
+        superseded: Vec<ScheduleJob>,
+    },
+    /// Nothing was created because this job is already active
+    Conflict(ScheduleJob),
+}
+
 #[async_trait]
 pub trait ScheduleJobRepository: Send + Sync {
     /// Create a new schedule job
@@ -12,17 +36,32 @@ pub trait ScheduleJobRepository: Send + Sync {
     /// Find job by ID
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<ScheduleJob>>;
 
-    /// Update job status
-    async fn update_status(
+    /// Find the job created with the given idempotency key
+    async fn find_by_idempotency_key(&self, key: &str) -> DomainResult<Option<ScheduleJob>>;
+
+    /// Create a job that must be the only pending or processing one for its
+    /// group and period. Submissions for the same group and period are
+    /// serialised. Active jobs are cancelled in the same transaction when
+    /// `supersede` is set; otherwise nothing is created while one exists.
+    async fn create_exclusive(
+        &self,
+        job: ScheduleJob,
+        supersede: bool,
+    ) -> DomainResult<ExclusiveCreate>;
+
+    /// List jobs matching the filter, newest first, with pagination.
+    /// Returns (jobs, total_matching).
+    async fn list(
         &self,
-        id: Uuid,
-        status: JobStatus,
-        error_message: Option<String>,
-    ) -> DomainResult<()>;
+        filter: &JobFilter,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<ScheduleJob>, u64)>;
 
-    /// Mark job as completed
-    async fn mark_completed(&self, id: Uuid) -> DomainResult<()>;
+    /// Start processing a pending job, or a retried one that is still
+    /// processing. Returns `false` when it has finished or been cancelled.
+    async fn mark_processing(&self, id: Uuid) -> DomainResult<bool>;
 
-    /// Mark job as failed
-    async fn mark_failed(&self, id: Uuid, error_message: String) -> DomainResult<()>;
+    /// Mark a pending or processing job as failed. Returns `false` when it
+    /// has finished or been cancelled.
+    async fn mark_failed(&self, id: Uuid, error_message: String) -> DomainResult<bool>;
 }
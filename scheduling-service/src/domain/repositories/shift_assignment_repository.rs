@@ -1,14 +1,28 @@
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use shared::DomainResult;
 use uuid::Uuid;
 
 use crate::domain::entities::ShiftAssignment;
 
+/// Read access to assignments; they are written through a `UnitOfWork`
 #[async_trait]
 pub trait ShiftAssignmentRepository: Send + Sync {
-    /// Create shift assignments in batch
-    async fn create_batch(&self, assignments: Vec<ShiftAssignment>) -> DomainResult<()>;
-
     /// Find all assignments for a schedule job
     async fn find_by_job_id(&self, job_id: Uuid) -> DomainResult<Vec<ShiftAssignment>>;
+
+    /// Assignments from completed jobs for the given staff dated in `[from, to)`.
+    /// When several jobs cover the same staff member and day, only the most
+    /// recently completed one counts.
+    async fn find_history(
+        &self,
+        staff_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<ShiftAssignment>>;
+
+    /// Assignments of one staff member from every completed job, ordered by
+    /// date. As with [`find_history`](Self::find_history), the most recently
+    /// completed job wins when several cover the same day.
+    async fn find_published_for_staff(&self, staff_id: Uuid) -> DomainResult<Vec<ShiftAssignment>>;
 }
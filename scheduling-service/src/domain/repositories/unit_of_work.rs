@@ -0,0 +1,31 @@
+use async_trait::async_trait;
+use shared::DomainResult;
+use uuid::Uuid;
+
+use crate::domain::entities::ShiftAssignment;
+
+/// Starts transactions spanning several repositories
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    /// Begin a new transaction
+    async fn begin(&self) -> DomainResult<Box<dyn Transaction>>;
+}
+
+/// Writes made through a transaction only become visible on `commit`.
+/// Dropping it without committing rolls everything back.
+#[async_trait]
+pub trait Transaction: Send {
+    /// Delete assignments left behind by an earlier attempt at the job.
+    /// Returns the number of deleted rows.
+    async fn delete_assignments_for_job(&mut self, job_id: Uuid) -> DomainResult<u64>;
+
+    /// Insert shift assignments
+    async fn create_assignments(&mut self, assignments: &[ShiftAssignment]) -> DomainResult<()>;
+
+    /// Mark job as completed. Only a job that is still processing is
+    /// completed; returns `false` when it was cancelled in the meantime.
+    async fn mark_completed(&mut self, job_id: Uuid) -> DomainResult<bool>;
+
+    /// Commit all writes made through this transaction
+    async fn commit(self: Box<Self>) -> DomainResult<()>;
+}
@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use shared::{DomainResult, PaginationParams};
+use uuid::Uuid;
+
+use crate::domain::entities::{WebhookDelivery, WebhookSubscription};
+
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    /// Create a new webhook subscription
+    async fn create(&self, subscription: WebhookSubscription) -> DomainResult<WebhookSubscription>;
+
+    /// Find subscription by ID
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<WebhookSubscription>>;
+
+    /// List all subscriptions with pagination
+    async fn list(&self, params: PaginationParams)
+        -> DomainResult<(Vec<WebhookSubscription>, u64)>;
+
+    /// Delete subscription by ID
+    async fn delete(&self, id: Uuid) -> DomainResult<()>;
+
+    /// Find active subscriptions for a group, including global ones
+    async fn find_active_for_group(
+        &self,
+        staff_group_id: Uuid,
+    ) -> DomainResult<Vec<WebhookSubscription>>;
+
+    /// Record a delivery attempt
+    async fn record_delivery(&self, delivery: WebhookDelivery) -> DomainResult<()>;
+
+    /// List delivery attempts for a subscription, newest first
+    async fn list_deliveries(
+        &self,
+        subscription_id: Uuid,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<WebhookDelivery>, u64)>;
+}
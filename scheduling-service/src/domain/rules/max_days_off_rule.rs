@@ -1,5 +1,4 @@
 use super::{AssignmentContext, Rule};
-use chrono::{Datelike, NaiveDate};
 use shared::{DomainError, DomainResult, ShiftType};
 
 pub struct MaxDaysOffRule {
@@ -10,33 +9,6 @@ impl MaxDaysOffRule {
     pub fn new(max_days_off: usize) -> Self {
         Self { max_days_off }
     }
-
-    /// Get the Monday of the week containing the given date
-    fn get_week_start(&self, date: NaiveDate) -> NaiveDate {
-        let weekday = date.weekday().num_days_from_monday();
-        date.checked_sub_signed(chrono::Duration::days(weekday as i64))
-            .unwrap_or(date)
-    }
-
-    /// Count days off for a staff member in a given week
-    fn count_days_off_in_week(&self, context: &AssignmentContext, week_start: NaiveDate) -> usize {
-        let staff_assignments = match context.assignments.get(&context.staff_id) {
-            Some(assignments) => assignments,
-            None => return 0,
-        };
-
-        let mut count = 0;
-        for day_offset in 0..7 {
-            if let Some(date) = week_start.checked_add_signed(chrono::Duration::days(day_offset)) {
-                if let Some(shift) = staff_assignments.get(&date) {
-                    if *shift == ShiftType::DayOff {
-                        count += 1;
-                    }
-                }
-            }
-        }
-        count
-    }
 }
 
 impl Rule for MaxDaysOffRule {
@@ -46,8 +18,14 @@ impl Rule for MaxDaysOffRule {
             return Ok(());
         }
 
-        let week_start = self.get_week_start(context.date);
-        let current_days_off = self.count_days_off_in_week(context, week_start);
+        // A day off on a paid-leave holiday is leave, not one of the weekly days off
+        if context.state.is_paid_leave(context.date) {
+            return Ok(());
+        }
+
+        let current_days_off = context
+            .state
+            .days_off_in_week(context.staff_id, context.date);
 
         if current_days_off + 1 > self.max_days_off {
             return Err(DomainError::InvalidInput(format!(
@@ -63,7 +41,8 @@ impl Rule for MaxDaysOffRule {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::domain::schedule_state::ScheduleState;
+    use chrono::NaiveDate;
     use uuid::Uuid;
 
     #[test]
@@ -76,16 +55,14 @@ mod tests {
             .unwrap();
 
         // Staff already has 2 days off
-        let mut assignments = HashMap::new();
-        let mut staff_assignments = HashMap::new();
-        staff_assignments.insert(monday, ShiftType::DayOff);
+        let mut state = ScheduleState::new(&[staff_id], monday, 7);
+        state.assign(staff_id, monday, ShiftType::DayOff).unwrap();
         let tuesday = monday.succ_opt().unwrap();
-        staff_assignments.insert(tuesday, ShiftType::DayOff);
-        assignments.insert(staff_id, staff_assignments);
+        state.assign(staff_id, tuesday, ShiftType::DayOff).unwrap();
 
         // Try to assign 3rd day off - should fail
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id,
             date: wednesday,
             shift: ShiftType::DayOff,
@@ -102,14 +79,12 @@ mod tests {
         let tuesday = monday.succ_opt().unwrap();
 
         // Staff has 1 day off
-        let mut assignments = HashMap::new();
-        let mut staff_assignments = HashMap::new();
-        staff_assignments.insert(monday, ShiftType::DayOff);
-        assignments.insert(staff_id, staff_assignments);
+        let mut state = ScheduleState::new(&[staff_id], monday, 7);
+        state.assign(staff_id, monday, ShiftType::DayOff).unwrap();
 
         // Assigning 2nd day off is OK
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id,
             date: tuesday,
             shift: ShiftType::DayOff,
@@ -117,4 +92,38 @@ mod tests {
 
         assert!(rule.validate(&context).is_ok());
     }
+
+    #[test]
+    fn test_max_days_off_ignores_paid_leave_holidays() {
+        use crate::domain::holiday_schedule::{HolidayPolicy, HolidaySchedule};
+
+        let rule = MaxDaysOffRule::new(2);
+        let staff_id = Uuid::new_v4();
+        let monday = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+        let christmas = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        let mut holidays = HolidaySchedule::new();
+        holidays.add(
+            christmas,
+            HolidayPolicy {
+                coverage_percent: 100,
+                paid_leave: true,
+                premium_weight: 1,
+            },
+        );
+
+        // Staff already has both weekly days off
+        let mut state = ScheduleState::new(&[staff_id], monday, 7).with_holidays(&holidays);
+        state.assign(staff_id, monday, ShiftType::DayOff).unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+        state.assign(staff_id, tuesday, ShiftType::DayOff).unwrap();
+
+        let context = AssignmentContext {
+            state: &state,
+            staff_id,
+            date: christmas,
+            shift: ShiftType::DayOff,
+        };
+
+        assert!(rule.validate(&context).is_ok());
+    }
 }
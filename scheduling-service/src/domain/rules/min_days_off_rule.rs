@@ -18,38 +18,24 @@ impl MinDaysOffRule {
             .unwrap_or(date)
     }
 
-    /// Count days off for a staff member in a given week
-    fn count_days_off_in_week(&self, context: &AssignmentContext, week_start: NaiveDate) -> usize {
-        let staff_assignments = match context.assignments.get(&context.staff_id) {
-            Some(assignments) => assignments,
-            None => return 0,
-        };
-
-        let mut count = 0;
-        for day_offset in 0..7 {
-            if let Some(date) = week_start.checked_add_signed(chrono::Duration::days(day_offset)) {
-                if let Some(shift) = staff_assignments.get(&date) {
-                    if *shift == ShiftType::DayOff {
-                        count += 1;
-                    }
-                }
-            }
-        }
-        count
-    }
-
-    /// Count remaining unassigned days in the week after the given date
-    fn count_remaining_days_in_week(&self, date: NaiveDate, week_start: NaiveDate) -> usize {
+    /// Count the days after the given date left in the week on which a day
+    /// off would count, i.e. excluding paid-leave holidays
+    fn count_remaining_days_in_week(
+        &self,
+        context: &AssignmentContext,
+        week_start: NaiveDate,
+    ) -> usize {
         let week_end = week_start
             .checked_add_signed(chrono::Duration::days(6))
-            .unwrap_or(date);
-
-        if date > week_end {
-            return 0;
-        }
-
-        let days_diff = (week_end - date).num_days();
-        days_diff.max(0) as usize
+            .unwrap_or(context.date);
+
+        context
+            .date
+            .iter_days()
+            .skip(1)
+            .take_while(|date| *date <= week_end)
+            .filter(|date| !context.state.is_paid_leave(*date))
+            .count()
     }
 }
 
@@ -61,8 +47,10 @@ impl Rule for MinDaysOffRule {
         }
 
         let week_start = self.get_week_start(context.date);
-        let current_days_off = self.count_days_off_in_week(context, week_start);
-        let remaining_days = self.count_remaining_days_in_week(context.date, week_start);
+        let current_days_off = context
+            .state
+            .days_off_in_week(context.staff_id, context.date);
+        let remaining_days = self.count_remaining_days_in_week(context, week_start);
         let max_possible_days_off = current_days_off + remaining_days;
 
         if max_possible_days_off < self.min_days_off {
@@ -79,7 +67,7 @@ impl Rule for MinDaysOffRule {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::domain::schedule_state::ScheduleState;
     use uuid::Uuid;
 
     #[test]
@@ -92,19 +80,17 @@ mod tests {
             .unwrap();
 
         // Staff has worked all days except Sunday
-        let mut assignments = HashMap::new();
-        let mut staff_assignments = HashMap::new();
+        let mut state = ScheduleState::new(&[staff_id], monday, 7);
         for day_offset in 0..6 {
             let date = monday
                 .checked_add_signed(chrono::Duration::days(day_offset))
                 .unwrap();
-            staff_assignments.insert(date, ShiftType::Morning);
+            state.assign(staff_id, date, ShiftType::Morning).unwrap();
         }
-        assignments.insert(staff_id, staff_assignments);
 
         // Try to assign work on Sunday - should fail (only 0 days off possible, need 2)
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id,
             date: sunday,
             shift: ShiftType::Morning,
@@ -119,11 +105,11 @@ mod tests {
         let staff_id = Uuid::new_v4();
         let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
 
-        let assignments = HashMap::new();
+        let state = ScheduleState::new(&[staff_id], monday, 7);
 
         // Assigning work on Monday is OK (6 days remaining, can still get 2 days off)
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id,
             date: monday,
             shift: ShiftType::Morning,
@@ -131,4 +117,36 @@ mod tests {
 
         assert!(rule.validate(&context).is_ok());
     }
+
+    #[test]
+    fn test_min_days_off_skips_paid_leave_holidays() {
+        use crate::domain::holiday_schedule::{HolidayPolicy, HolidaySchedule};
+
+        let rule = MinDaysOffRule::new(2);
+        let staff_id = Uuid::new_v4();
+        let monday = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+        let friday = NaiveDate::from_ymd_opt(2024, 12, 27).unwrap();
+        let mut holidays = HolidaySchedule::new();
+        for day in [28, 29] {
+            holidays.add(
+                NaiveDate::from_ymd_opt(2024, 12, day).unwrap(),
+                HolidayPolicy {
+                    coverage_percent: 100,
+                    paid_leave: true,
+                    premium_weight: 1,
+                },
+            );
+        }
+
+        // Only the weekend is left, and both days are paid leave rather than days off
+        let state = ScheduleState::new(&[staff_id], monday, 7).with_holidays(&holidays);
+        let context = AssignmentContext {
+            state: &state,
+            staff_id,
+            date: friday,
+            shift: ShiftType::Morning,
+        };
+
+        assert!(rule.validate(&context).is_err());
+    }
 }
@@ -5,22 +5,31 @@ pub mod shift_balance_rule;
 
 use chrono::NaiveDate;
 use shared::{DomainResult, ShiftType};
-use std::collections::HashMap;
 use uuid::Uuid;
 
+use crate::domain::schedule_state::ScheduleState;
+
 pub use max_days_off_rule::MaxDaysOffRule;
 pub use min_days_off_rule::MinDaysOffRule;
 pub use no_morning_after_evening_rule::NoMorningAfterEveningRule;
 pub use shift_balance_rule::ShiftBalanceRule;
 
-#[derive(Debug, Clone)]
-pub struct AssignmentContext {
-    pub assignments: HashMap<Uuid, HashMap<NaiveDate, ShiftType>>,
+/// A proposed assignment, checked against the schedule built so far
+#[derive(Debug, Clone, Copy)]
+pub struct AssignmentContext<'a> {
+    pub state: &'a ScheduleState,
     pub staff_id: Uuid,
     pub date: NaiveDate,
     pub shift: ShiftType,
 }
 
+impl AssignmentContext<'_> {
+    /// Shift already assigned to this staff member on another date
+    pub fn shift_on(&self, date: NaiveDate) -> Option<ShiftType> {
+        self.state.shift(self.staff_id, date)
+    }
+}
+
 pub trait Rule: Send + Sync {
     /// Check if the assignment violates this rule
     fn validate(&self, context: &AssignmentContext) -> DomainResult<()>;
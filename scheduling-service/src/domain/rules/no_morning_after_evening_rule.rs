@@ -11,22 +11,12 @@ impl NoMorningAfterEveningRule {
 
     /// Check if previous day was evening shift
     fn get_previous_shift(&self, context: &AssignmentContext) -> Option<ShiftType> {
-        let previous_date = context.date.pred_opt()?;
-        context
-            .assignments
-            .get(&context.staff_id)?
-            .get(&previous_date)
-            .copied()
+        context.shift_on(context.date.pred_opt()?)
     }
 
     /// Check if next day is morning shift
     fn get_next_shift(&self, context: &AssignmentContext) -> Option<ShiftType> {
-        let next_date = context.date.succ_opt()?;
-        context
-            .assignments
-            .get(&context.staff_id)?
-            .get(&next_date)
-            .copied()
+        context.shift_on(context.date.succ_opt()?)
     }
 }
 
@@ -63,8 +53,8 @@ impl Rule for NoMorningAfterEveningRule {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::schedule_state::ScheduleState;
     use chrono::NaiveDate;
-    use std::collections::HashMap;
     use uuid::Uuid;
 
     #[test]
@@ -74,13 +64,11 @@ mod tests {
         let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
         let tuesday = monday.succ_opt().unwrap();
 
-        let mut assignments = HashMap::new();
-        let mut staff_assignments = HashMap::new();
-        staff_assignments.insert(monday, ShiftType::Evening);
-        assignments.insert(staff_id, staff_assignments);
+        let mut state = ScheduleState::new(&[staff_id], monday, 7);
+        state.assign(staff_id, monday, ShiftType::Evening).unwrap();
 
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id,
             date: tuesday,
             shift: ShiftType::Morning,
@@ -96,13 +84,11 @@ mod tests {
         let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
         let tuesday = monday.succ_opt().unwrap();
 
-        let mut assignments = HashMap::new();
-        let mut staff_assignments = HashMap::new();
-        staff_assignments.insert(monday, ShiftType::Morning);
-        assignments.insert(staff_id, staff_assignments);
+        let mut state = ScheduleState::new(&[staff_id], monday, 7);
+        state.assign(staff_id, monday, ShiftType::Morning).unwrap();
 
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id,
             date: tuesday,
             shift: ShiftType::Morning,
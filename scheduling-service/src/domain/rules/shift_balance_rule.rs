@@ -1,6 +1,5 @@
 use super::{AssignmentContext, Rule};
 use shared::{DomainError, DomainResult, ShiftType};
-use std::collections::HashMap;
 
 pub struct ShiftBalanceRule {
     max_daily_shift_difference: usize,
@@ -12,22 +11,6 @@ impl ShiftBalanceRule {
             max_daily_shift_difference,
         }
     }
-
-    /// Count how many staff are assigned to each shift type on a given date
-    fn count_shifts_on_date(&self, context: &AssignmentContext) -> HashMap<ShiftType, usize> {
-        let mut counts = HashMap::new();
-        counts.insert(ShiftType::Morning, 0);
-        counts.insert(ShiftType::Evening, 0);
-        counts.insert(ShiftType::DayOff, 0);
-
-        for staff_assignments in context.assignments.values() {
-            if let Some(shift) = staff_assignments.get(&context.date) {
-                *counts.entry(*shift).or_insert(0) += 1;
-            }
-        }
-
-        counts
-    }
 }
 
 impl Rule for ShiftBalanceRule {
@@ -37,13 +20,15 @@ impl Rule for ShiftBalanceRule {
             return Ok(());
         }
 
-        let mut counts = self.count_shifts_on_date(context);
+        let mut morning_count = context.state.count_on(context.date, ShiftType::Morning);
+        let mut evening_count = context.state.count_on(context.date, ShiftType::Evening);
 
         // Simulate adding this assignment
-        *counts.entry(context.shift).or_insert(0) += 1;
-
-        let morning_count = *counts.get(&ShiftType::Morning).unwrap_or(&0);
-        let evening_count = *counts.get(&ShiftType::Evening).unwrap_or(&0);
+        match context.shift {
+            ShiftType::Morning => morning_count += 1,
+            ShiftType::Evening => evening_count += 1,
+            ShiftType::DayOff => {}
+        }
 
         let diff = morning_count.abs_diff(evening_count);
 
@@ -69,8 +54,8 @@ impl Rule for ShiftBalanceRule {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::schedule_state::ScheduleState;
     use chrono::NaiveDate;
-    use std::collections::HashMap;
     use uuid::Uuid;
 
     #[test]
@@ -79,22 +64,16 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
 
         // Two staff already assigned to morning shift
-        let mut assignments = HashMap::new();
-
         let staff1 = Uuid::new_v4();
-        let mut staff1_assignments = HashMap::new();
-        staff1_assignments.insert(date, ShiftType::Morning);
-        assignments.insert(staff1, staff1_assignments);
-
         let staff2 = Uuid::new_v4();
-        let mut staff2_assignments = HashMap::new();
-        staff2_assignments.insert(date, ShiftType::Morning);
-        assignments.insert(staff2, staff2_assignments);
+        let staff3 = Uuid::new_v4();
+        let mut state = ScheduleState::new(&[staff1, staff2, staff3], date, 7);
+        state.assign(staff1, date, ShiftType::Morning).unwrap();
+        state.assign(staff2, date, ShiftType::Morning).unwrap();
 
         // Try to assign 3rd morning shift - would be 3 morning vs 0 evening (diff = 3 > 1)
-        let staff3 = Uuid::new_v4();
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id: staff3,
             date,
             shift: ShiftType::Morning,
@@ -109,22 +88,16 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
 
         // Two staff: one morning, one evening
-        let mut assignments = HashMap::new();
-
         let staff1 = Uuid::new_v4();
-        let mut staff1_assignments = HashMap::new();
-        staff1_assignments.insert(date, ShiftType::Morning);
-        assignments.insert(staff1, staff1_assignments);
-
         let staff2 = Uuid::new_v4();
-        let mut staff2_assignments = HashMap::new();
-        staff2_assignments.insert(date, ShiftType::Evening);
-        assignments.insert(staff2, staff2_assignments);
+        let staff3 = Uuid::new_v4();
+        let mut state = ScheduleState::new(&[staff1, staff2, staff3], date, 7);
+        state.assign(staff1, date, ShiftType::Morning).unwrap();
+        state.assign(staff2, date, ShiftType::Evening).unwrap();
 
         // Assigning evening is OK (would be 1 morning vs 2 evening, diff = 1)
-        let staff3 = Uuid::new_v4();
         let context = AssignmentContext {
-            assignments,
+            state: &state,
             staff_id: staff3,
             date,
             shift: ShiftType::Evening,
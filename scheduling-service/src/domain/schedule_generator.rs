@@ -1,27 +1,93 @@
 use chrono::{Datelike, NaiveDate, Utc};
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use shared::{DomainError, DomainResult, ShiftType};
-use std::collections::HashMap;
+use std::cmp::Reverse;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::domain::entities::ShiftAssignment;
+use crate::domain::fairness::{Burden, FairnessLedger};
+use crate::domain::holiday_schedule::HolidaySchedule;
 use crate::domain::rules::{AssignmentContext, Rule};
+use crate::domain::schedule_state::ScheduleState;
+use crate::domain::staff_pool::StaffPool;
+
+/// Schedule being built together with the running fairness counts
+struct Roster {
+    state: ScheduleState,
+    ledger: FairnessLedger,
+}
+
+impl Roster {
+    fn assign(&mut self, staff_id: Uuid, date: NaiveDate, shift: ShiftType) -> DomainResult<()> {
+        self.state.assign(staff_id, date, shift)?;
+        self.ledger.record(staff_id, date, shift);
+        Ok(())
+    }
+}
+
+/// Length of every generated schedule
+pub const SCHEDULE_PERIOD_DAYS: usize = 28;
+
+/// Last day of the schedule starting on `period_begin_date`
+pub fn period_end_date(period_begin_date: NaiveDate) -> NaiveDate {
+    period_begin_date + chrono::Duration::days(SCHEDULE_PERIOD_DAYS as i64 - 1)
+}
+
+/// Default for how far apart cumulative burden counts may drift between staff
+pub const DEFAULT_FAIRNESS_TOLERANCE: u32 = 2;
+
+/// Staffing needed out of `target` when a day needs `coverage` percent of it
+fn scale(target: usize, coverage: usize) -> usize {
+    (target * coverage + 50) / 100
+}
 
 pub struct ScheduleGenerator {
     rules: Vec<Arc<dyn Rule>>,
+    fairness_tolerance: u32,
 }
 
 impl ScheduleGenerator {
     pub fn new(rules: Vec<Arc<dyn Rule>>) -> Self {
-        Self { rules }
+        Self {
+            rules,
+            fairness_tolerance: DEFAULT_FAIRNESS_TOLERANCE,
+        }
     }
 
-    /// Generate a 28-day schedule for staff members
+    /// Keep each staff member's evening, weekend and holiday counts within
+    /// `tolerance` of the lowest count in the group wherever the rules allow
+    pub fn with_fairness_tolerance(mut self, tolerance: u32) -> Self {
+        self.fairness_tolerance = tolerance;
+        self
+    }
+
+    /// Generate a 28-day schedule for the staff of a pool.
+    ///
+    /// Everyone in the pool gets one shift a day, worked for one of their
+    /// groups. Staff get a day off on days they belong to none of the groups,
+    /// such as before they join or after they leave mid-period. The roster depends only on the staff set, start date and
+    /// seed: the order staff were added in is ignored, and the seed decides the
+    /// order in which they are offered shifts. Assignment ids are always fresh,
+    /// so a job resubmitted with the same seed can store its own copy.
+    ///
+    /// The pool's coverage targets are filled first each day, from the members
+    /// of each target group, as far as the rules allow.
+    ///
+    /// Evening, weekend and holiday shifts are rotated using `history`, the
+    /// burden each staff member has carried in earlier periods. `holidays`
+    /// lowers staffing targets, turns days off into paid leave and weights
+    /// holiday shifts according to each calendar's policy.
     pub fn generate_schedule(
         &self,
-        staff_ids: Vec<Uuid>,
+        pool: &StaffPool,
         start_date: NaiveDate,
         job_id: Uuid,
+        seed: i64,
+        history: &FairnessLedger,
+        holidays: &HolidaySchedule,
     ) -> DomainResult<Vec<ShiftAssignment>> {
         if start_date.weekday().num_days_from_monday() != 0 {
             return Err(DomainError::InvalidInput(
@@ -29,38 +95,49 @@ impl ScheduleGenerator {
             ));
         }
 
-        if staff_ids.is_empty() {
+        if pool.is_empty() {
             return Err(DomainError::InvalidInput(
                 "At least one staff member is required".to_string(),
             ));
         }
 
-        let mut assignments: HashMap<Uuid, HashMap<NaiveDate, ShiftType>> = HashMap::new();
-        let period_days = 28;
+        let mut rng = ChaCha8Rng::seed_from_u64(seed as u64);
+        let mut staff_ids = pool.staff_ids().to_vec();
+        staff_ids.sort_unstable();
+        staff_ids.shuffle(&mut rng);
+
+        let period_days = SCHEDULE_PERIOD_DAYS;
+        let mut roster = Roster {
+            state: ScheduleState::new(&staff_ids, start_date, period_days).with_holidays(holidays),
+            ledger: history.clone().with_holidays(holidays.clone()),
+        };
 
         for day_offset in 0..period_days {
             let current_date = start_date
-                .checked_add_signed(chrono::Duration::days(day_offset))
+                .checked_add_signed(chrono::Duration::days(day_offset as i64))
                 .ok_or_else(|| DomainError::InvalidInput("Invalid date".to_string()))?;
 
-            self.assign_shifts_for_day(&mut assignments, &staff_ids, current_date)?;
+            self.assign_shifts_for_day(&mut roster, pool, &staff_ids, current_date, holidays)?;
         }
 
-        let mut result = Vec::new();
-        for (staff_id, staff_assignments) in assignments {
-            for (date, shift) in staff_assignments {
-                result.push(ShiftAssignment {
-                    id: Uuid::new_v4(),
-                    schedule_job_id: job_id,
-                    staff_id,
-                    date,
-                    shift,
-                    created_at: Utc::now(),
-                });
-            }
-        }
+        let mut entries: Vec<(Uuid, NaiveDate, ShiftType)> = roster.state.assignments().collect();
+        entries.sort_unstable_by_key(|(staff_id, date, _)| (*date, *staff_id));
 
-        result.sort_by_key(|a| (a.date, a.staff_id));
+        let created_at = Utc::now();
+        let mut result: Vec<ShiftAssignment> = entries
+            .into_iter()
+            .map(|(staff_id, date, shift)| ShiftAssignment {
+                id: Uuid::new_v4(),
+                schedule_job_id: job_id,
+                staff_id,
+                staff_group_id: Uuid::nil(),
+                date,
+                shift,
+                created_at,
+                updated_at: created_at,
+            })
+            .collect();
+        pool.assign_groups(&mut result);
 
         Ok(result)
     }
@@ -73,81 +150,221 @@ impl ScheduleGenerator {
         Ok(())
     }
 
+    /// Check a candidate shift against the rules without recording it
+    fn is_valid(
+        &self,
+        state: &ScheduleState,
+        staff_id: Uuid,
+        date: NaiveDate,
+        shift: ShiftType,
+    ) -> bool {
+        let context = AssignmentContext {
+            state,
+            staff_id,
+            date,
+            shift,
+        };
+        self.validate_assignment(&context).is_ok()
+    }
+
     /// Assign shifts for a single day using greedy strategy
     fn assign_shifts_for_day(
         &self,
-        assignments: &mut HashMap<Uuid, HashMap<NaiveDate, ShiftType>>,
+        roster: &mut Roster,
+        pool: &StaffPool,
         staff_ids: &[Uuid],
         date: NaiveDate,
+        holidays: &HolidaySchedule,
     ) -> DomainResult<()> {
+        // Staff outside every group today cannot work, whatever the rules say
+        for staff_id in staff_ids {
+            if roster.state.shift(*staff_id, date).is_none()
+                && pool.groups_on(*staff_id, date).is_empty()
+            {
+                roster.assign(*staff_id, date, ShiftType::DayOff)?;
+            }
+        }
+
         let mut unassigned_staff: Vec<Uuid> = staff_ids
             .iter()
-            .filter(|id| {
-                !assignments
-                    .get(id)
-                    .map(|m| m.contains_key(&date))
-                    .unwrap_or(false)
-            })
+            .filter(|id| roster.state.shift(**id, date).is_none())
             .copied()
             .collect();
 
-        // Try to balance morning and evening shifts
+        // Try to balance morning and evening shifts, scaled down on holidays
+        // that need less than full coverage
+        let coverage = holidays.coverage_percent(date) as usize;
         let target_morning = unassigned_staff.len() / 3;
-        let target_evening = (unassigned_staff.len() - target_morning) / 2;
+        let target_evening = scale((unassigned_staff.len() - target_morning) / 2, coverage);
+        let target_morning = scale(target_morning, coverage);
 
-        self.assign_shift_type(
-            assignments,
+        // Offer shifts to whoever has carried the least of the day's burden so
+        // far; the sorts are stable, so ties keep the seeded order. Evenings are
+        // handed out first, to those who have worked the fewest, and mornings
+        // then go to those who have worked the most.
+        let premium_count = |ledger: &FairnessLedger, staff_id: Uuid| {
+            let counts = ledger.counts(staff_id);
+            let weekends = if FairnessLedger::is_weekend(date) {
+                counts.weekends
+            } else {
+                0
+            };
+            let holidays = if ledger.is_holiday(date) {
+                counts.holidays
+            } else {
+                0
+            };
+            weekends + holidays
+        };
+
+        // Assign evening shifts
+        let ledger = &roster.ledger;
+        unassigned_staff.sort_by_key(|id| {
+            (
+                premium_count(ledger, *id),
+                ledger.count(*id, Burden::Evening),
+            )
+        });
+        self.cover_subgroups(
+            roster,
+            pool,
             &mut unassigned_staff,
             date,
-            ShiftType::Morning,
-            target_morning,
+            ShiftType::Evening,
+            coverage,
         )?;
-
-        // Assign evening shifts
+        let target_evening =
+            target_evening.saturating_sub(roster.state.count_on(date, ShiftType::Evening));
         self.assign_shift_type(
-            assignments,
+            roster,
+            staff_ids,
             &mut unassigned_staff,
             date,
             ShiftType::Evening,
             target_evening,
         )?;
 
+        // Assign morning shifts
+        let ledger = &roster.ledger;
+        unassigned_staff.sort_by_key(|id| {
+            (
+                premium_count(ledger, *id),
+                Reverse(ledger.count(*id, Burden::Evening)),
+            )
+        });
+        self.cover_subgroups(
+            roster,
+            pool,
+            &mut unassigned_staff,
+            date,
+            ShiftType::Morning,
+            coverage,
+        )?;
+        let target_morning =
+            target_morning.saturating_sub(roster.state.count_on(date, ShiftType::Morning));
+        self.assign_shift_type(
+            roster,
+            staff_ids,
+            &mut unassigned_staff,
+            date,
+            ShiftType::Morning,
+            target_morning,
+        )?;
+
         // Remaining staff get day off
         for staff_id in unassigned_staff {
-            self.try_assign(assignments, staff_id, date, ShiftType::DayOff)?;
+            self.try_assign(roster, staff_id, date, ShiftType::DayOff)?;
         }
         Ok(())
     }
 
-    /// Try to assign a specific shift type to staff members
+    /// Fill the pool's coverage targets for `shift` from the members of each
+    /// target group, scaled like the rest of the day on reduced-coverage
+    /// holidays. Members already on the shift count towards the target, and
+    /// those assigned here are removed from `unassigned_staff`.
+    fn cover_subgroups(
+        &self,
+        roster: &mut Roster,
+        pool: &StaffPool,
+        unassigned_staff: &mut Vec<Uuid>,
+        date: NaiveDate,
+        shift: ShiftType,
+        coverage: usize,
+    ) -> DomainResult<()> {
+        for target in pool.coverage_targets().iter().filter(|t| t.shift == shift) {
+            let staffed = pool
+                .staff_ids()
+                .iter()
+                .filter(|id| {
+                    roster.state.shift(**id, date) == Some(shift)
+                        && pool.is_member(target.group_id, **id, date)
+                })
+                .count();
+            let needed = scale(target.min_staff as usize, coverage).saturating_sub(staffed);
+            if needed == 0 {
+                continue;
+            }
+
+            let mut members: Vec<Uuid> = unassigned_staff
+                .iter()
+                .filter(|id| pool.is_member(target.group_id, **id, date))
+                .copied()
+                .collect();
+            self.assign_shift_type(roster, pool.staff_ids(), &mut members, date, shift, needed)?;
+            unassigned_staff.retain(|id| roster.state.shift(*id, date).is_none());
+        }
+        Ok(())
+    }
+
+    /// Try to assign a specific shift type to staff members, leaving those
+    /// that could not take it in `unassigned_staff` in their original order.
+    ///
+    /// Staff whose burden is already `fairness_tolerance` above the group's
+    /// lowest are only used once nobody else can take the shift.
     fn assign_shift_type(
         &self,
-        assignments: &mut HashMap<Uuid, HashMap<NaiveDate, ShiftType>>,
+        roster: &mut Roster,
+        staff_ids: &[Uuid],
         unassigned_staff: &mut Vec<Uuid>,
         date: NaiveDate,
         shift: ShiftType,
         target_count: usize,
     ) -> DomainResult<()> {
+        let ledger = &roster.ledger;
+        let limits: Vec<(Burden, u32)> = ledger
+            .burdens(date, shift)
+            .map(|burden| {
+                let limit = ledger.min_count(staff_ids, burden) + self.fairness_tolerance;
+                (burden, limit)
+            })
+            .collect();
+
         let mut assigned_count = 0;
-        let mut i = 0;
 
-        while i < unassigned_staff.len() && assigned_count < target_count {
-            let staff_id = unassigned_staff[i];
+        for within_tolerance_only in [true, false] {
+            if within_tolerance_only && limits.is_empty() {
+                continue;
+            }
 
-            let context = AssignmentContext {
-                assignments: assignments.clone(),
-                staff_id,
-                date,
-                shift,
-            };
+            let mut remaining = Vec::with_capacity(unassigned_staff.len());
 
-            if self.validate_assignment(&context).is_ok() {
-                assignments.entry(staff_id).or_default().insert(date, shift);
-                unassigned_staff.remove(i);
-                assigned_count += 1;
-            } else {
-                i += 1;
+            for staff_id in unassigned_staff.drain(..) {
+                let within_tolerance = limits
+                    .iter()
+                    .all(|(burden, limit)| roster.ledger.count(staff_id, *burden) < *limit);
+
+                if assigned_count < target_count
+                    && (within_tolerance || !within_tolerance_only)
+                    && self.is_valid(&roster.state, staff_id, date, shift)
+                {
+                    roster.assign(staff_id, date, shift)?;
+                    assigned_count += 1;
+                } else {
+                    remaining.push(staff_id);
+                }
             }
+
+            *unassigned_staff = remaining;
         }
 
         Ok(())
@@ -156,56 +373,23 @@ impl ScheduleGenerator {
     /// Try to assign a shift to a staff member, with fallback options
     fn try_assign(
         &self,
-        assignments: &mut HashMap<Uuid, HashMap<NaiveDate, ShiftType>>,
+        roster: &mut Roster,
         staff_id: Uuid,
         date: NaiveDate,
         preferred_shift: ShiftType,
     ) -> DomainResult<()> {
-        // Try preferred shift first
-        let context = AssignmentContext {
-            assignments: assignments.clone(),
-            staff_id,
-            date,
-            shift: preferred_shift,
-        };
-
-        if self.validate_assignment(&context).is_ok() {
-            assignments
-                .entry(staff_id)
-                .or_default()
-                .insert(date, preferred_shift);
-            return Ok(());
-        }
-
         // Try alternative shifts if preferred fails
-        let alternatives = match preferred_shift {
-            ShiftType::DayOff => vec![ShiftType::Morning, ShiftType::Evening],
-            _ => vec![ShiftType::DayOff],
+        let alternatives: &[ShiftType] = match preferred_shift {
+            ShiftType::DayOff => &[ShiftType::Morning, ShiftType::Evening],
+            _ => &[ShiftType::DayOff],
         };
 
-        for alt_shift in alternatives {
-            let context = AssignmentContext {
-                assignments: assignments.clone(),
-                staff_id,
-                date,
-                shift: alt_shift,
-            };
-
-            if self.validate_assignment(&context).is_ok() {
-                assignments
-                    .entry(staff_id)
-                    .or_default()
-                    .insert(date, alt_shift);
-                return Ok(());
-            }
-        }
-
-        // If all else fails, assign anyway (best effort)
-        assignments
-            .entry(staff_id)
-            .or_default()
-            .insert(date, preferred_shift);
+        let shift = std::iter::once(preferred_shift)
+            .chain(alternatives.iter().copied())
+            .find(|shift| self.is_valid(&roster.state, staff_id, date, *shift))
+            // If all else fails, assign anyway (best effort)
+            .unwrap_or(preferred_shift);
 
-        Ok(())
+        roster.assign(staff_id, date, shift)
     }
 }
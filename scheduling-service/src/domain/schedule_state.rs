@@ -0,0 +1,252 @@
+use chrono::{Datelike, NaiveDate};
+use shared::{DomainError, DomainResult, ShiftType};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::holiday_schedule::HolidaySchedule;
+
+const SHIFT_TYPES: usize = 3;
+
+fn shift_slot(shift: ShiftType) -> usize {
+    match shift {
+        ShiftType::Morning => 0,
+        ShiftType::Evening => 1,
+        ShiftType::DayOff => 2,
+    }
+}
+
+/// Assignments made so far during generation, indexed so rules can be
+/// checked in constant time.
+///
+/// Shifts live in a flat staff × day grid. Counters per date and shift type
+/// and per staff and week are updated on every assignment, so rules never
+/// have to scan the schedule. Days off on paid-leave holidays are kept out of
+/// the weekly counters.
+#[derive(Debug, Clone)]
+pub struct ScheduleState {
+    start_date: NaiveDate,
+    days: usize,
+    /// Offset of `start_date` from the Monday of its week
+    start_weekday: usize,
+    weeks: usize,
+    staff_ids: Vec<Uuid>,
+    staff_index: HashMap<Uuid, usize>,
+    grid: Vec<Option<ShiftType>>,
+    paid_leave: Vec<bool>,
+    date_counts: Vec<[usize; SHIFT_TYPES]>,
+    week_days_off: Vec<usize>,
+}
+
+impl ScheduleState {
+    /// Empty state covering `days` days from `start_date` for the given staff
+    pub fn new(staff_ids: &[Uuid], start_date: NaiveDate, days: usize) -> Self {
+        let start_weekday = start_date.weekday().num_days_from_monday() as usize;
+        let weeks = (start_weekday + days).div_ceil(7);
+        let staff_index: HashMap<Uuid, usize> = staff_ids
+            .iter()
+            .enumerate()
+            .map(|(index, id)| (*id, index))
+            .collect();
+        let staff_count = staff_ids.len();
+
+        Self {
+            start_date,
+            days,
+            start_weekday,
+            weeks,
+            staff_ids: staff_ids.to_vec(),
+            staff_index,
+            grid: vec![None; staff_count * days],
+            paid_leave: vec![false; days],
+            date_counts: vec![[0; SHIFT_TYPES]; days],
+            week_days_off: vec![0; staff_count * weeks],
+        }
+    }
+
+    /// Mark the paid-leave holidays of the period. Call before assigning shifts.
+    pub fn with_holidays(mut self, holidays: &HolidaySchedule) -> Self {
+        for day in 0..self.days {
+            let date = self.start_date + chrono::Duration::days(day as i64);
+            self.paid_leave[day] = holidays.is_paid_leave(date);
+        }
+        self
+    }
+
+    /// Whether a day off on `date` is paid leave rather than a weekly day off
+    pub fn is_paid_leave(&self, date: NaiveDate) -> bool {
+        self.day(date).is_some_and(|day| self.paid_leave[day])
+    }
+
+    /// Day offset of `date` from the start, if it falls inside the period
+    fn day(&self, date: NaiveDate) -> Option<usize> {
+        let offset = (date - self.start_date).num_days();
+        (0..self.days as i64)
+            .contains(&offset)
+            .then_some(offset as usize)
+    }
+
+    fn week(&self, day: usize) -> usize {
+        (self.start_weekday + day) / 7
+    }
+
+    /// Record a shift, replacing any earlier assignment for the same day
+    pub fn assign(
+        &mut self,
+        staff_id: Uuid,
+        date: NaiveDate,
+        shift: ShiftType,
+    ) -> DomainResult<()> {
+        let staff = *self.staff_index.get(&staff_id).ok_or_else(|| {
+            DomainError::InvalidInput(format!("Staff {} is not part of the schedule", staff_id))
+        })?;
+        let day = self.day(date).ok_or_else(|| {
+            DomainError::InvalidInput(format!("Date {} is outside the schedule period", date))
+        })?;
+        let week_slot = staff * self.weeks + self.week(day);
+        let counts_as_day_off = !self.paid_leave[day];
+        let cell = &mut self.grid[staff * self.days + day];
+
+        if let Some(previous) = cell.replace(shift) {
+            self.date_counts[day][shift_slot(previous)] -= 1;
+            if previous == ShiftType::DayOff && counts_as_day_off {
+                self.week_days_off[week_slot] -= 1;
+            }
+        }
+
+        self.date_counts[day][shift_slot(shift)] += 1;
+        if shift == ShiftType::DayOff && counts_as_day_off {
+            self.week_days_off[week_slot] += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Shift assigned to a staff member on a date, if any
+    pub fn shift(&self, staff_id: Uuid, date: NaiveDate) -> Option<ShiftType> {
+        let staff = *self.staff_index.get(&staff_id)?;
+        let day = self.day(date)?;
+        self.grid[staff * self.days + day]
+    }
+
+    /// Number of staff assigned the given shift on a date
+    pub fn count_on(&self, date: NaiveDate, shift: ShiftType) -> usize {
+        self.day(date)
+            .map(|day| self.date_counts[day][shift_slot(shift)])
+            .unwrap_or(0)
+    }
+
+    /// Days off a staff member already has in the Monday-to-Sunday week
+    /// containing `date`, not counting paid-leave holidays
+    pub fn days_off_in_week(&self, staff_id: Uuid, date: NaiveDate) -> usize {
+        match (self.staff_index.get(&staff_id), self.day(date)) {
+            (Some(staff), Some(day)) => self.week_days_off[staff * self.weeks + self.week(day)],
+            _ => 0,
+        }
+    }
+
+    /// All recorded assignments as (staff, date, shift), in the order staff
+    /// were given and then by date
+    pub fn assignments(&self) -> impl Iterator<Item = (Uuid, NaiveDate, ShiftType)> + '_ {
+        self.staff_ids
+            .iter()
+            .enumerate()
+            .flat_map(move |(staff, staff_id)| {
+                (0..self.days).filter_map(move |day| {
+                    self.grid[staff * self.days + day].map(|shift| {
+                        (
+                            *staff_id,
+                            self.start_date + chrono::Duration::days(day as i64),
+                            shift,
+                        )
+                    })
+                })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_follow_reassignment() {
+        let staff_id = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let mut state = ScheduleState::new(&[staff_id, other], monday, 28);
+
+        state.assign(staff_id, monday, ShiftType::DayOff).unwrap();
+        state.assign(other, monday, ShiftType::Morning).unwrap();
+        assert_eq!(state.days_off_in_week(staff_id, monday), 1);
+        assert_eq!(state.count_on(monday, ShiftType::Morning), 1);
+
+        state.assign(staff_id, monday, ShiftType::Morning).unwrap();
+        assert_eq!(state.days_off_in_week(staff_id, monday), 0);
+        assert_eq!(state.count_on(monday, ShiftType::DayOff), 0);
+        assert_eq!(state.count_on(monday, ShiftType::Morning), 2);
+        assert_eq!(state.shift(staff_id, monday), Some(ShiftType::Morning));
+    }
+
+    #[test]
+    fn test_weeks_are_monday_aligned() {
+        let staff_id = Uuid::new_v4();
+        let wednesday = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
+        let sunday = NaiveDate::from_ymd_opt(2024, 1, 21).unwrap();
+        let next_monday = NaiveDate::from_ymd_opt(2024, 1, 22).unwrap();
+        let mut state = ScheduleState::new(&[staff_id], wednesday, 14);
+
+        state
+            .assign(staff_id, wednesday, ShiftType::DayOff)
+            .unwrap();
+        state.assign(staff_id, sunday, ShiftType::DayOff).unwrap();
+        state
+            .assign(staff_id, next_monday, ShiftType::DayOff)
+            .unwrap();
+
+        assert_eq!(state.days_off_in_week(staff_id, sunday), 2);
+        assert_eq!(state.days_off_in_week(staff_id, next_monday), 1);
+    }
+
+    #[test]
+    fn test_out_of_period_lookups() {
+        let staff_id = Uuid::new_v4();
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let mut state = ScheduleState::new(&[staff_id], monday, 7);
+        let before = monday.pred_opt().unwrap();
+
+        assert_eq!(state.shift(staff_id, before), None);
+        assert_eq!(state.count_on(before, ShiftType::Morning), 0);
+        assert!(state.assign(staff_id, before, ShiftType::Morning).is_err());
+        assert!(state
+            .assign(Uuid::new_v4(), monday, ShiftType::Morning)
+            .is_err());
+    }
+
+    #[test]
+    fn test_paid_leave_holidays_are_not_weekly_days_off() {
+        use crate::domain::holiday_schedule::HolidayPolicy;
+
+        let staff_id = Uuid::new_v4();
+        let monday = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+        let christmas = NaiveDate::from_ymd_opt(2024, 12, 25).unwrap();
+        let mut holidays = HolidaySchedule::new();
+        holidays.add(
+            christmas,
+            HolidayPolicy {
+                coverage_percent: 50,
+                paid_leave: true,
+                premium_weight: 1,
+            },
+        );
+        let mut state = ScheduleState::new(&[staff_id], monday, 7).with_holidays(&holidays);
+
+        state.assign(staff_id, monday, ShiftType::DayOff).unwrap();
+        state
+            .assign(staff_id, christmas, ShiftType::DayOff)
+            .unwrap();
+
+        assert!(state.is_paid_leave(christmas));
+        assert_eq!(state.days_off_in_week(staff_id, christmas), 1);
+        assert_eq!(state.count_on(christmas, ShiftType::DayOff), 1);
+    }
+}
@@ -0,0 +1,335 @@
+use chrono::NaiveDate;
+use shared::ShiftType;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::entities::{CoverageTarget, ShiftAssignment};
+
+/// Days a membership is in effect, both ends inclusive. `None` leaves that
+/// end open, so the default period covers every day.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MembershipPeriod {
+    pub valid_from: Option<NaiveDate>,
+    pub valid_to: Option<NaiveDate>,
+}
+
+impl MembershipPeriod {
+    pub fn new(valid_from: Option<NaiveDate>, valid_to: Option<NaiveDate>) -> Self {
+        Self {
+            valid_from,
+            valid_to,
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.valid_from.is_none_or(|from| from <= date) && self.valid_to.is_none_or(|to| date <= to)
+    }
+}
+
+/// Periods a staff member belongs to one group; they overlap or leave gaps
+/// when the staff member reaches the group through several subgroups
+type Periods = Vec<MembershipPeriod>;
+
+fn covers(periods: &Periods, date: NaiveDate) -> bool {
+    periods.iter().any(|period| period.contains(date))
+}
+
+/// Staff of every group in a job, each person listed once.
+///
+/// Someone who belongs to several groups (or to several subgroups that the
+/// data service resolves separately) is scheduled once and works each shift
+/// for exactly one of their groups. Subgroups within the hierarchy of each
+/// group are tracked separately so their coverage targets can be filled from
+/// their own members.
+///
+/// Every membership carries the days it is in effect, so someone transferred
+/// or joining mid-period only counts towards a group on their days in it.
+#[derive(Debug, Clone, Default)]
+pub struct StaffPool {
+    staff_ids: Vec<Uuid>,
+    memberships: HashMap<Uuid, Vec<Uuid>>,
+    periods: HashMap<(Uuid, Uuid), Periods>,
+    subgroups: HashMap<Uuid, HashMap<Uuid, Periods>>,
+    coverage_targets: Vec<CoverageTarget>,
+}
+
+impl StaffPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a group and its members with the days each of them belongs to it;
+    /// members already in the pool just gain the group. A member listed more
+    /// than once belongs to the group on the days of any of their periods.
+    pub fn with_group(
+        mut self,
+        group_id: Uuid,
+        members: impl IntoIterator<Item = (Uuid, MembershipPeriod)>,
+    ) -> Self {
+        for (staff_id, period) in members {
+            let groups = self.memberships.entry(staff_id).or_insert_with(|| {
+                self.staff_ids.push(staff_id);
+                Vec::new()
+            });
+            if !groups.contains(&group_id) {
+                groups.push(group_id);
+            }
+            self.periods
+                .entry((staff_id, group_id))
+                .or_default()
+                .push(period);
+        }
+        self
+    }
+
+    /// Record the members of one resolved subgroup with the days each of
+    /// them belongs to it. `path` runs from the scheduled group down to the
+    /// subgroup, and the members count towards every group along it.
+    pub fn with_subgroup_members(
+        mut self,
+        path: &[Uuid],
+        members: impl IntoIterator<Item = (Uuid, MembershipPeriod)>,
+    ) -> Self {
+        let members: Vec<(Uuid, MembershipPeriod)> = members.into_iter().collect();
+        for group_id in path {
+            let subgroup = self.subgroups.entry(*group_id).or_default();
+            for (staff_id, period) in &members {
+                subgroup.entry(*staff_id).or_default().push(*period);
+            }
+        }
+        self
+    }
+
+    /// Minimum daily staffing of groups in the pool, filled in the given order
+    pub fn with_coverage_targets(mut self, targets: Vec<CoverageTarget>) -> Self {
+        self.coverage_targets = targets;
+        self
+    }
+
+    pub fn coverage_targets(&self) -> &[CoverageTarget] {
+        &self.coverage_targets
+    }
+
+    /// Whether a group was added, or is a subgroup with recorded members
+    pub fn contains_group(&self, group_id: Uuid) -> bool {
+        self.subgroups.contains_key(&group_id)
+            || self
+                .memberships
+                .values()
+                .any(|groups| groups.contains(&group_id))
+    }
+
+    /// Whether a staff member belongs to a group or, through its hierarchy, a
+    /// subgroup on `date`
+    pub fn is_member(&self, group_id: Uuid, staff_id: Uuid, date: NaiveDate) -> bool {
+        self.periods
+            .get(&(staff_id, group_id))
+            .is_some_and(|periods| covers(periods, date))
+            || self
+                .subgroups
+                .get(&group_id)
+                .and_then(|members| members.get(&staff_id))
+                .is_some_and(|periods| covers(periods, date))
+    }
+
+    /// Distinct staff in the order they were first added
+    pub fn staff_ids(&self) -> &[Uuid] {
+        &self.staff_ids
+    }
+
+    /// Groups a staff member belongs to on any day, in the order the groups
+    /// were added
+    pub fn groups_of(&self, staff_id: Uuid) -> &[Uuid] {
+        self.memberships
+            .get(&staff_id)
+            .map_or(&[], |groups| groups.as_slice())
+    }
+
+    /// Groups a staff member belongs to on `date`, in the order the groups
+    /// were added
+    pub fn groups_on(&self, staff_id: Uuid, date: NaiveDate) -> Vec<Uuid> {
+        self.groups_of(staff_id)
+            .iter()
+            .filter(|group_id| self.is_member(**group_id, staff_id, date))
+            .copied()
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.staff_ids.is_empty()
+    }
+
+    /// Decide which group each shift is worked for.
+    ///
+    /// Shifts only go to groups the staff member belongs to on that day.
+    /// Staff with a single such group work for it. Shared staff are then
+    /// placed, shift by shift, in whichever of their groups has the fewest
+    /// people on that shift so far, ties going to the group added first. Days
+    /// off are booked to the staff member's first group that day, or to their
+    /// first group at all on days they belong to none.
+    pub fn assign_groups(&self, assignments: &mut [ShiftAssignment]) {
+        let mut staffed: HashMap<(NaiveDate, ShiftType, Uuid), usize> = HashMap::new();
+        let groups: Vec<Vec<Uuid>> = assignments
+            .iter()
+            .map(|assignment| self.groups_on(assignment.staff_id, assignment.date))
+            .collect();
+        let (single, shared): (Vec<usize>, Vec<usize>) =
+            (0..assignments.len()).partition(|index| groups[*index].len() <= 1);
+
+        for index in single.into_iter().chain(shared) {
+            let assignment = &mut assignments[index];
+            let Some(first) = groups[index]
+                .first()
+                .or_else(|| self.groups_of(assignment.staff_id).first())
+            else {
+                continue;
+            };
+
+            let group_id = if assignment.shift == ShiftType::DayOff {
+                *first
+            } else {
+                *groups[index]
+                    .iter()
+                    .min_by_key(|group_id| {
+                        staffed
+                            .get(&(assignment.date, assignment.shift, **group_id))
+                            .copied()
+                            .unwrap_or(0)
+                    })
+                    .unwrap_or(first)
+            };
+
+            *staffed
+                .entry((assignment.date, assignment.shift, group_id))
+                .or_insert(0) += 1;
+            assignment.staff_group_id = group_id;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn always(staff_ids: &[Uuid]) -> Vec<(Uuid, MembershipPeriod)> {
+        staff_ids
+            .iter()
+            .map(|id| (*id, MembershipPeriod::default()))
+            .collect()
+    }
+
+    fn assignment(staff_id: Uuid, shift: ShiftType) -> ShiftAssignment {
+        ShiftAssignment {
+            id: Uuid::new_v4(),
+            schedule_job_id: Uuid::nil(),
+            staff_id,
+            staff_group_id: Uuid::nil(),
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            shift,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_shared_staff_are_listed_once() {
+        let (backend, frontend) = (Uuid::from_u128(10), Uuid::from_u128(20));
+        let (alice, jane, bob) = (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let pool = StaffPool::new()
+            .with_group(backend, always(&[alice, jane, jane]))
+            .with_group(frontend, always(&[jane, bob]));
+
+        assert_eq!(pool.staff_ids(), &[alice, jane, bob]);
+        assert_eq!(pool.groups_of(jane), &[backend, frontend]);
+        assert_eq!(pool.groups_of(bob), &[frontend]);
+    }
+
+    #[test]
+    fn test_shared_staff_fill_the_less_staffed_group() {
+        let (backend, frontend) = (Uuid::from_u128(10), Uuid::from_u128(20));
+        let (alice, jane, bob) = (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let pool = StaffPool::new()
+            .with_group(backend, always(&[alice, jane]))
+            .with_group(frontend, always(&[jane, bob]));
+
+        // Jane comes first but is only placed once the single-group staff are
+        let mut assignments = vec![
+            assignment(jane, ShiftType::Evening),
+            assignment(alice, ShiftType::Evening),
+            assignment(bob, ShiftType::Morning),
+        ];
+        pool.assign_groups(&mut assignments);
+
+        assert_eq!(assignments[0].staff_group_id, frontend);
+        assert_eq!(assignments[1].staff_group_id, backend);
+        assert_eq!(assignments[2].staff_group_id, frontend);
+
+        let mut day_off = vec![assignment(jane, ShiftType::DayOff)];
+        pool.assign_groups(&mut day_off);
+        assert_eq!(day_off[0].staff_group_id, backend);
+    }
+
+    #[test]
+    fn test_subgroup_members_belong_to_every_group_on_their_path() {
+        let (engineering, backend, oncall) = (
+            Uuid::from_u128(10),
+            Uuid::from_u128(11),
+            Uuid::from_u128(12),
+        );
+        let (alice, jane, bob) = (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let pool = StaffPool::new()
+            .with_group(engineering, always(&[alice, jane, bob]))
+            .with_subgroup_members(&[engineering], always(&[alice]))
+            .with_subgroup_members(&[engineering, backend], always(&[jane]))
+            .with_subgroup_members(&[engineering, backend, oncall], always(&[bob]));
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert!(pool.is_member(backend, jane, date));
+        assert!(pool.is_member(backend, bob, date));
+        assert!(!pool.is_member(backend, alice, date));
+        assert!(pool.is_member(oncall, bob, date));
+        assert!(!pool.is_member(oncall, jane, date));
+        assert!(pool.contains_group(oncall));
+        assert!(!pool.contains_group(Uuid::from_u128(99)));
+    }
+
+    #[test]
+    fn test_members_only_belong_to_a_group_on_their_days_in_it() {
+        let (backend, frontend) = (Uuid::from_u128(10), Uuid::from_u128(20));
+        let jane = Uuid::from_u128(2);
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let pool = StaffPool::new()
+            .with_group(
+                backend,
+                [(jane, MembershipPeriod::new(None, Some(day(16))))],
+            )
+            .with_group(
+                frontend,
+                [(jane, MembershipPeriod::new(Some(day(18)), None))],
+            );
+
+        assert_eq!(pool.staff_ids(), &[jane]);
+        assert_eq!(pool.groups_on(jane, day(16)), vec![backend]);
+        assert!(pool.groups_on(jane, day(17)).is_empty());
+        assert_eq!(pool.groups_on(jane, day(18)), vec![frontend]);
+
+        let mut assignments = vec![
+            ShiftAssignment {
+                date: day(16),
+                ..assignment(jane, ShiftType::Morning)
+            },
+            ShiftAssignment {
+                date: day(17),
+                ..assignment(jane, ShiftType::DayOff)
+            },
+            ShiftAssignment {
+                date: day(18),
+                ..assignment(jane, ShiftType::Morning)
+            },
+        ];
+        pool.assign_groups(&mut assignments);
+        let groups: Vec<Uuid> = assignments.iter().map(|a| a.staff_group_id).collect();
+        assert_eq!(groups, vec![backend, backend, frontend]);
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveTime;
 use config::{Config, ConfigError, File};
 use serde::Deserialize;
 
@@ -8,6 +9,10 @@ pub struct Settings {
     pub redis: RedisSettings,
     pub data_service: DataServiceSettings,
     pub scheduling: SchedulingConfig,
+    #[serde(default)]
+    pub webhooks: WebhookSettings,
+    #[serde(default)]
+    pub calendar: CalendarSettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -38,6 +43,61 @@ pub struct SchedulingConfig {
     pub min_days_off_per_week: usize,
     pub max_days_off_per_week: usize,
     pub max_daily_shift_difference: usize,
+    /// How far evening, weekend and holiday counts may drift between staff
+    #[serde(default = "default_fairness_tolerance")]
+    pub fairness_tolerance: u32,
+    /// How many days of earlier assignments count towards fairness
+    #[serde(default = "default_fairness_lookback_days")]
+    pub fairness_lookback_days: u32,
+}
+
+fn default_fairness_tolerance() -> u32 {
+    crate::domain::schedule_generator::DEFAULT_FAIRNESS_TOLERANCE
+}
+
+fn default_fairness_lookback_days() -> u32 {
+    84
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WebhookSettings {
+    /// Total attempts per event, including the first one
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each failed attempt
+    pub initial_backoff_ms: u64,
+    pub request_timeout_secs: u64,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff_ms: 500,
+            request_timeout_secs: 10,
+        }
+    }
+}
+
+/// Local times of each shift in calendar feeds. A shift that ends at or
+/// before its start time finishes the next day.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CalendarSettings {
+    pub morning_start: NaiveTime,
+    pub morning_end: NaiveTime,
+    pub evening_start: NaiveTime,
+    pub evening_end: NaiveTime,
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).expect("valid time");
+        Self {
+            morning_start: time(6),
+            morning_end: time(14),
+            evening_start: time(14),
+            evening_end: time(22),
+        }
+    }
 }
 
 impl Settings {
@@ -1,9 +1,12 @@
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use shared::{ApiResponse, DomainResult, StaffStatus};
+use std::collections::HashSet;
 use uuid::Uuid;
 
+use crate::domain::staff_pool::MembershipPeriod;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StaffResponse {
     pub id: Uuid,
@@ -13,20 +16,53 @@ pub struct StaffResponse {
     pub status: StaffStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// First day of the membership that lists the staff member; `None` when
+    /// it has always applied
+    #[serde(default)]
+    pub valid_from: Option<NaiveDate>,
+    /// Last day of that membership; `None` when it never ends
+    #[serde(default)]
+    pub valid_to: Option<NaiveDate>,
+}
+
+impl StaffResponse {
+    /// Days the membership that lists the staff member is in effect
+    pub fn membership_period(&self) -> MembershipPeriod {
+        MembershipPeriod::new(self.valid_from, self.valid_to)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResolvedGroupResponse {
     pub group_id: Uuid,
     pub group_name: String,
+    /// Groups from the resolved group down to this one, both included
+    pub path: Vec<Uuid>,
     pub members: Vec<StaffResponse>,
 }
 
 /// Trait for data service client operations - allows mocking in tests
 #[async_trait]
 pub trait DataServiceClientTrait: Send + Sync {
-    /// Get all active staff members in a group (including descendants)
-    async fn get_group_members(&self, group_id: Uuid) -> DomainResult<Vec<StaffResponse>>;
+    /// Get all active staff members in a group (including descendants) whose
+    /// membership is in effect on any day from `from` to `to`. Members of
+    /// several subgroups are listed once for each distinct membership period,
+    /// so the days they belong to the group are kept.
+    async fn get_group_members(
+        &self,
+        group_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<StaffResponse>>;
+
+    /// Get the active members of a group and of each of its descendants,
+    /// one entry per group of the hierarchy, for the days from `from` to `to`
+    async fn get_resolved_groups(
+        &self,
+        group_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<ResolvedGroupResponse>>;
 }
 
 pub struct DataServiceClient {
@@ -46,10 +82,34 @@ impl DataServiceClient {
 #[async_trait]
 impl DataServiceClientTrait for DataServiceClient {
     /// Get all active staff members in a group (including descendants)
-    async fn get_group_members(&self, group_id: Uuid) -> DomainResult<Vec<StaffResponse>> {
+    async fn get_group_members(
+        &self,
+        group_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<StaffResponse>> {
+        let groups = self.get_resolved_groups(group_id, from, to).await?;
+
+        // Members of several subgroups appear once per subgroup
+        let mut seen = HashSet::new();
+        let staff_list: Vec<StaffResponse> = groups
+            .into_iter()
+            .flat_map(|group| group.members)
+            .filter(|staff| seen.insert((staff.id, staff.valid_from, staff.valid_to)))
+            .collect();
+
+        Ok(staff_list)
+    }
+
+    async fn get_resolved_groups(
+        &self,
+        group_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<ResolvedGroupResponse>> {
         let url = format!(
-            "{}/api/v1/groups/{}/resolved-members",
-            self.base_url, group_id
+            "{}/api/v1/groups/{}/resolved-members?from={}&to={}",
+            self.base_url, group_id, from, to
         );
 
         let response = self
@@ -73,12 +133,6 @@ impl DataServiceClientTrait for DataServiceClient {
             .await
             .map_err(|e| shared::DomainError::ExternalServiceError(e.to_string()))?;
 
-        let staff_list: Vec<StaffResponse> = api_response
-            .data
-            .into_iter()
-            .flat_map(|group| group.members)
-            .collect();
-
-        Ok(staff_list)
+        Ok(api_response.data)
     }
 }
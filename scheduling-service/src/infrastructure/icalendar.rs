@@ -0,0 +1,676 @@
+//! Minimal iCalendar (RFC 5545) support: importing holiday calendars and
+//! publishing rosters as calendar feeds.
+//!
+//! Only what public holiday feeds use is understood: all-day or timed
+//! `VEVENT`s with `DTSTART`, an optional exclusive `DTEND`, `SUMMARY`, `UID`,
+//! `EXDATE` and yearly `RRULE`s. Events that cannot be expanded into dates are
+//! reported back instead of failing the whole import.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use shared::{DomainError, DomainResult, ShiftType};
+use std::collections::HashMap;
+
+use crate::domain::entities::ShiftAssignment;
+use crate::infrastructure::config::CalendarSettings;
+
+/// Occurrences taken from a yearly rule that has neither `COUNT` nor `UNTIL`
+pub const DEFAULT_YEARLY_OCCURRENCES: u32 = 10;
+
+/// Upper bound on the occurrences taken from any yearly rule
+const MAX_YEARLY_OCCURRENCES: u32 = 100;
+
+/// Longest event, in days, that is expanded into individual holidays
+const MAX_EVENT_DAYS: i64 = 31;
+
+/// One holiday date taken from a calendar file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IcsHoliday {
+    pub date: NaiveDate,
+    pub name: String,
+    pub uid: Option<String>,
+}
+
+/// Result of parsing a calendar file
+#[derive(Debug, Default)]
+pub struct IcsImport {
+    pub holidays: Vec<IcsHoliday>,
+    /// Events that were ignored, with the reason
+    pub skipped: Vec<String>,
+}
+
+struct ContentLine {
+    name: String,
+    params: HashMap<String, String>,
+    value: String,
+}
+
+/// Join folded lines: a line starting with a space or tab continues the previous one
+fn unfold(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in input.split('\n') {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ if line.is_empty() => {}
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_content_line(line: &str) -> Option<ContentLine> {
+    // The value starts at the first colon outside a quoted parameter value
+    let mut in_quotes = false;
+    let split = line.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(index),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..split], &line[split + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.trim().to_ascii_uppercase(),
+                value.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+
+    Some(ContentLine {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push(' '),
+            Some(escaped) => text.push(escaped),
+            None => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Date part of a `DATE` or `DATE-TIME` value; times and zones are ignored
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let digits = value.get(..8)?;
+    NaiveDate::parse_from_str(digits, "%Y%m%d").ok()
+}
+
+#[derive(Default)]
+struct EventBuilder {
+    uid: Option<String>,
+    summary: Option<String>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    rrule: Option<String>,
+    exdates: Vec<NaiveDate>,
+    cancelled: bool,
+    invalid: Option<String>,
+}
+
+impl EventBuilder {
+    fn apply(&mut self, line: ContentLine) {
+        match line.name.as_str() {
+            "UID" => self.uid = Some(line.value.trim().to_string()),
+            "SUMMARY" => self.summary = Some(unescape_text(&line.value)),
+            "DTSTART" | "DTEND" => {
+                let date = parse_date(&line.value);
+                if date.is_none() {
+                    self.invalid = Some(format!("invalid {} '{}'", line.name, line.value));
+                }
+                // A timed end still covers its own date
+                let all_day =
+                    line.params.get("VALUE").is_some_and(|v| v == "DATE") || line.value.len() == 8;
+                if line.name == "DTSTART" {
+                    self.start = date;
+                } else {
+                    self.end = date.and_then(|d| if all_day { Some(d) } else { d.succ_opt() });
+                }
+            }
+            "RRULE" => self.rrule = Some(line.value),
+            "EXDATE" => self
+                .exdates
+                .extend(line.value.split(',').filter_map(parse_date)),
+            "STATUS" => self.cancelled = line.value.eq_ignore_ascii_case("CANCELLED"),
+            _ => {}
+        }
+    }
+
+    fn label(&self) -> String {
+        match (&self.summary, &self.uid) {
+            (Some(summary), _) => format!("'{}'", summary),
+            (None, Some(uid)) => format!("UID {}", uid),
+            (None, None) => "unnamed event".to_string(),
+        }
+    }
+
+    /// Dates the event covers, or why it cannot be imported
+    fn dates(&self) -> Result<Vec<NaiveDate>, String> {
+        if let Some(reason) = &self.invalid {
+            return Err(reason.clone());
+        }
+        if self.cancelled {
+            return Err("event is cancelled".to_string());
+        }
+        let start = self.start.ok_or("missing DTSTART")?;
+        let length = match self.end {
+            Some(end) if end <= start => 1,
+            Some(end) => (end - start).num_days(),
+            None => 1,
+        };
+        if length > MAX_EVENT_DAYS {
+            return Err(format!("event spans more than {} days", MAX_EVENT_DAYS));
+        }
+
+        let starts = match &self.rrule {
+            Some(rule) => expand_yearly(start, rule)?,
+            None => vec![start],
+        };
+
+        Ok(starts
+            .into_iter()
+            .filter(|date| !self.exdates.contains(date))
+            .flat_map(|first| first.iter_days().take(length as usize))
+            .collect())
+    }
+}
+
+/// Start dates of a yearly recurrence. Other frequencies and `BY*` parts are
+/// not supported because holiday feeds do not need them.
+fn expand_yearly(start: NaiveDate, rule: &str) -> Result<Vec<NaiveDate>, String> {
+    let mut count = None;
+    let mut until = None;
+    let mut interval = 1;
+
+    for part in rule.split(';').filter(|part| !part.is_empty()) {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| format!("invalid RRULE part '{}'", part))?;
+        match key.to_ascii_uppercase().as_str() {
+            "FREQ" if value.eq_ignore_ascii_case("YEARLY") => {}
+            "FREQ" => return Err(format!("unsupported RRULE frequency {}", value)),
+            "COUNT" => {
+                count = Some(
+                    value
+                        .parse::<u32>()
+                        .map_err(|_| format!("invalid RRULE COUNT '{}'", value))?,
+                )
+            }
+            "UNTIL" => {
+                until = Some(
+                    parse_date(value).ok_or_else(|| format!("invalid RRULE UNTIL '{}'", value))?,
+                )
+            }
+            "INTERVAL" => {
+                interval = value
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|interval| *interval > 0)
+                    .ok_or_else(|| format!("invalid RRULE INTERVAL '{}'", value))?
+            }
+            "BYMONTH" if value.parse::<u32>().ok() == Some(start.month()) => {}
+            "BYMONTHDAY" if value.parse::<u32>().ok() == Some(start.day()) => {}
+            "WKST" => {}
+            _ => return Err(format!("unsupported RRULE part {}", part)),
+        }
+    }
+
+    let limit = match (count, until) {
+        (Some(count), _) => count.min(MAX_YEARLY_OCCURRENCES),
+        (None, Some(_)) => MAX_YEARLY_OCCURRENCES,
+        (None, None) => DEFAULT_YEARLY_OCCURRENCES,
+    };
+
+    // Feb 29 only recurs in leap years; the other years are skipped
+    let mut dates = Vec::new();
+    for step in 0..MAX_YEARLY_OCCURRENCES as i32 {
+        if dates.len() as u32 >= limit {
+            break;
+        }
+        let year = step
+            .checked_mul(interval)
+            .and_then(|offset| start.year().checked_add(offset))
+            .ok_or_else(|| format!("RRULE INTERVAL {} runs past the last year", interval))?;
+        let Some(date) = start.with_year(year) else {
+            continue;
+        };
+        if until.is_some_and(|until| date > until) {
+            break;
+        }
+        dates.push(date);
+    }
+
+    Ok(dates)
+}
+
+/// Parse the holidays of a calendar file.
+///
+/// Fails only when the input is not an iCalendar object at all; events that
+/// cannot be used are listed in [`IcsImport::skipped`].
+pub fn parse_holidays(input: &str) -> DomainResult<IcsImport> {
+    let lines = unfold(input);
+    let is_calendar = lines
+        .first()
+        .and_then(|line| parse_content_line(line))
+        .is_some_and(|line| line.name == "BEGIN" && line.value.eq_ignore_ascii_case("VCALENDAR"));
+    if !is_calendar {
+        return Err(DomainError::InvalidInput(
+            "Calendar must start with BEGIN:VCALENDAR".to_string(),
+        ));
+    }
+
+    let mut import = IcsImport::default();
+    let mut event: Option<EventBuilder> = None;
+
+    for line in lines.iter().filter_map(|line| parse_content_line(line)) {
+        let component = line.value.to_ascii_uppercase();
+        match (line.name.as_str(), component.as_str(), event.as_mut()) {
+            ("BEGIN", "VEVENT", _) => event = Some(EventBuilder::default()),
+            ("END", "VEVENT", Some(_)) => {
+                let finished = event.take().expect("event in progress");
+                match finished.dates() {
+                    Ok(dates) => {
+                        let name = finished
+                            .summary
+                            .clone()
+                            .filter(|summary| !summary.is_empty())
+                            .unwrap_or_else(|| "Holiday".to_string());
+                        import
+                            .holidays
+                            .extend(dates.into_iter().map(|date| IcsHoliday {
+                                date,
+                                name: name.clone(),
+                                uid: finished.uid.clone(),
+                            }));
+                    }
+                    Err(reason) => import
+                        .skipped
+                        .push(format!("{}: {}", finished.label(), reason)),
+                }
+            }
+            (_, _, Some(builder)) => builder.apply(line),
+            _ => {}
+        }
+    }
+
+    Ok(import)
+}
+
+/// Identifies this service as the producer of generated calendars
+const PRODUCT_ID: &str = "-//Shift Scheduler//Scheduling Service//EN";
+
+/// Longest content line, in octets, before it is folded
+const MAX_LINE_OCTETS: usize = 75;
+
+fn escape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' | ';' | ',' => {
+                text.push('\\');
+                text.push(c);
+            }
+            '\n' => text.push_str("\\n"),
+            '\r' => {}
+            _ => text.push(c),
+        }
+    }
+    text
+}
+
+/// Append a content line, folding it onto continuation lines where needed
+fn push_line(output: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            output.push_str("\r\n ");
+            octets = 1;
+        }
+        output.push(c);
+        octets += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+fn format_local(value: NaiveDateTime) -> String {
+    value.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn format_utc(value: DateTime<Utc>) -> String {
+    value.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Start and end of a worked shift in local time
+fn shift_span(date: NaiveDate, start: NaiveTime, end: NaiveTime) -> (NaiveDateTime, NaiveDateTime) {
+    let end_date = if end <= start {
+        date + Duration::days(1)
+    } else {
+        date
+    };
+    (date.and_time(start), end_date.and_time(end))
+}
+
+/// UID of the event for a staff member's day. It depends only on the staff
+/// member and date, so a regenerated schedule updates the event in calendar
+/// clients instead of adding a second one.
+pub fn assignment_uid(assignment: &ShiftAssignment) -> String {
+    format!(
+        "{}-{}@shift-scheduler",
+        assignment.date.format("%Y%m%d"),
+        assignment.staff_id
+    )
+}
+
+/// Revision of an assignment's event.
+///
+/// A regenerated shift keeps its UID, so clients only replace the event when
+/// the sequence grows; seconds since the Unix epoch of the last change do.
+fn event_sequence(assignment: &ShiftAssignment) -> i64 {
+    assignment.updated_at.timestamp().max(0)
+}
+
+/// Render assignments as a calendar feed named `name`.
+///
+/// Worked shifts become events at the configured local times, without a time
+/// zone so clients show them in the site's own time. Days off are all-day
+/// events that do not mark the staff member as busy.
+pub fn write_roster(
+    name: &str,
+    assignments: &[ShiftAssignment],
+    times: &CalendarSettings,
+) -> String {
+    let mut output = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        &format!("PRODID:{}", PRODUCT_ID),
+        "CALSCALE:GREGORIAN",
+        "METHOD:PUBLISH",
+        &format!("X-WR-CALNAME:{}", escape_text(name)),
+    ] {
+        push_line(&mut output, line);
+    }
+
+    for assignment in assignments {
+        push_line(&mut output, "BEGIN:VEVENT");
+        push_line(&mut output, &format!("UID:{}", assignment_uid(assignment)));
+        push_line(
+            &mut output,
+            &format!("DTSTAMP:{}", format_utc(assignment.updated_at)),
+        );
+        push_line(
+            &mut output,
+            &format!("LAST-MODIFIED:{}", format_utc(assignment.updated_at)),
+        );
+        push_line(
+            &mut output,
+            &format!("SEQUENCE:{}", event_sequence(assignment)),
+        );
+
+        let summary = match assignment.shift {
+            ShiftType::Morning | ShiftType::Evening => {
+                let (start, end) = match assignment.shift {
+                    ShiftType::Morning => (times.morning_start, times.morning_end),
+                    _ => (times.evening_start, times.evening_end),
+                };
+                let (start, end) = shift_span(assignment.date, start, end);
+                push_line(&mut output, &format!("DTSTART:{}", format_local(start)));
+                push_line(&mut output, &format!("DTEND:{}", format_local(end)));
+                if assignment.shift == ShiftType::Morning {
+                    "Morning shift"
+                } else {
+                    "Evening shift"
+                }
+            }
+            ShiftType::DayOff => {
+                let next_day = assignment.date + Duration::days(1);
+                push_line(
+                    &mut output,
+                    &format!("DTSTART;VALUE=DATE:{}", assignment.date.format("%Y%m%d")),
+                );
+                push_line(
+                    &mut output,
+                    &format!("DTEND;VALUE=DATE:{}", next_day.format("%Y%m%d")),
+                );
+                push_line(&mut output, "TRANSP:TRANSPARENT");
+                "Day off"
+            }
+        };
+
+        push_line(&mut output, &format!("SUMMARY:{}", summary));
+        let description = format!(
+            "Staff {}, schedule {}",
+            assignment.staff_id, assignment.schedule_job_id
+        );
+        push_line(
+            &mut output,
+            &format!("DESCRIPTION:{}", escape_text(&description)),
+        );
+        push_line(&mut output, "END:VEVENT");
+    }
+
+    push_line(&mut output, "END:VCALENDAR");
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn calendar(events: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//EN\r\n{}END:VCALENDAR\r\n",
+            events
+        )
+    }
+
+    #[test]
+    fn test_all_day_event_with_folded_summary() {
+        let input = calendar(
+            "BEGIN:VEVENT\r\nUID:xmas@example.com\r\nDTSTART;VALUE=DATE:20241225\r\nDTEND;VALUE=DATE:20241227\r\nSUMMARY:Christmas Day\\, \r\n and Boxing Day\r\nEND:VEVENT\r\n",
+        );
+
+        let import = parse_holidays(&input).unwrap();
+
+        assert!(import.skipped.is_empty());
+        assert_eq!(
+            import.holidays,
+            vec![
+                IcsHoliday {
+                    date: date(2024, 12, 25),
+                    name: "Christmas Day, and Boxing Day".to_string(),
+                    uid: Some("xmas@example.com".to_string()),
+                },
+                IcsHoliday {
+                    date: date(2024, 12, 26),
+                    name: "Christmas Day, and Boxing Day".to_string(),
+                    uid: Some("xmas@example.com".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yearly_rule_with_count_and_exdate() {
+        let input = calendar(
+            "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20240101\nRRULE:FREQ=YEARLY;COUNT=3\nEXDATE;VALUE=DATE:20250101\nSUMMARY:New Year\nEND:VEVENT\n",
+        );
+
+        let dates: Vec<NaiveDate> = parse_holidays(&input)
+            .unwrap()
+            .holidays
+            .into_iter()
+            .map(|holiday| holiday.date)
+            .collect();
+
+        assert_eq!(dates, vec![date(2024, 1, 1), date(2026, 1, 1)]);
+    }
+
+    #[test]
+    fn test_yearly_rule_until_and_default_cap() {
+        let until = calendar(
+            "BEGIN:VEVENT\nDTSTART:20240501T000000Z\nRRULE:FREQ=YEARLY;UNTIL=20260501\nSUMMARY:Labour Day\nEND:VEVENT\n",
+        );
+        let open = calendar(
+            "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20240501\nRRULE:FREQ=YEARLY\nSUMMARY:Labour Day\nEND:VEVENT\n",
+        );
+
+        assert_eq!(parse_holidays(&until).unwrap().holidays.len(), 3);
+        assert_eq!(
+            parse_holidays(&open).unwrap().holidays.len(),
+            DEFAULT_YEARLY_OCCURRENCES as usize
+        );
+    }
+
+    #[test]
+    fn test_yearly_rule_with_overflowing_interval_is_skipped() {
+        let input = calendar(
+            "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20240101\nRRULE:FREQ=YEARLY;INTERVAL=2147483647\nSUMMARY:New Year\nEND:VEVENT\n",
+        );
+
+        let import = parse_holidays(&input).unwrap();
+
+        assert!(import.holidays.is_empty());
+        assert_eq!(
+            import.skipped,
+            vec!["'New Year': RRULE INTERVAL 2147483647 runs past the last year".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_events_are_skipped() {
+        let input = calendar(
+            "BEGIN:VEVENT\nDTSTART;VALUE=DATE:20240101\nRRULE:FREQ=MONTHLY\nSUMMARY:Payday\nEND:VEVENT\nBEGIN:VEVENT\nSUMMARY:No date\nEND:VEVENT\nBEGIN:VEVENT\nDTSTART;VALUE=DATE:20240704\nSUMMARY:Independence Day\nEND:VEVENT\n",
+        );
+
+        let import = parse_holidays(&input).unwrap();
+
+        assert_eq!(import.holidays.len(), 1);
+        assert_eq!(import.holidays[0].date, date(2024, 7, 4));
+        assert_eq!(
+            import.skipped,
+            vec![
+                "'Payday': unsupported RRULE frequency MONTHLY".to_string(),
+                "'No date': missing DTSTART".to_string(),
+            ]
+        );
+    }
+
+    fn roster_assignment(date: NaiveDate, shift: ShiftType) -> ShiftAssignment {
+        ShiftAssignment {
+            id: uuid::Uuid::new_v4(),
+            schedule_job_id: uuid::Uuid::from_u128(2),
+            staff_id: uuid::Uuid::from_u128(1),
+            staff_group_id: uuid::Uuid::from_u128(3),
+            date,
+            shift,
+            created_at: date.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            updated_at: date.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+        }
+    }
+
+    #[test]
+    fn test_roster_events_use_configured_times_and_stable_uids() {
+        let times = CalendarSettings {
+            evening_end: NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+            ..CalendarSettings::default()
+        };
+        let assignments = [
+            roster_assignment(date(2024, 1, 15), ShiftType::Morning),
+            roster_assignment(date(2024, 1, 16), ShiftType::Evening),
+            roster_assignment(date(2024, 1, 17), ShiftType::DayOff),
+        ];
+
+        let output = write_roster("Roster, week 3", &assignments, &times);
+
+        assert!(output.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(output.contains("X-WR-CALNAME:Roster\\, week 3\r\n"));
+        assert!(output.contains("DTSTART:20240115T060000\r\nDTEND:20240115T140000\r\n"));
+        // Evenings ending after midnight finish the next day
+        assert!(output.contains("DTSTART:20240116T140000\r\nDTEND:20240117T020000\r\n"));
+        assert!(output.contains(
+            "DTSTART;VALUE=DATE:20240117\r\nDTEND;VALUE=DATE:20240118\r\nTRANSP:TRANSPARENT\r\n"
+        ));
+        assert!(output.contains(&format!(
+            "UID:20240115-{}@shift-scheduler\r\n",
+            uuid::Uuid::from_u128(1)
+        )));
+        assert!(output.ends_with("END:VCALENDAR\r\n"));
+
+        // Regenerated assignments keep their UIDs
+        let regenerated = roster_assignment(date(2024, 1, 15), ShiftType::Evening);
+        assert_eq!(
+            assignment_uid(&regenerated),
+            assignment_uid(&assignments[0])
+        );
+    }
+
+    #[test]
+    fn test_changed_shift_gets_higher_sequence() {
+        let times = CalendarSettings::default();
+        let published = roster_assignment(date(2024, 1, 15), ShiftType::Morning);
+        let changed = ShiftAssignment {
+            shift: ShiftType::Evening,
+            updated_at: published.updated_at + Duration::hours(3),
+            ..roster_assignment(date(2024, 1, 15), ShiftType::Evening)
+        };
+
+        let sequence = |output: &str| -> i64 {
+            output
+                .split("\r\n")
+                .find_map(|line| line.strip_prefix("SEQUENCE:"))
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let before = write_roster("Roster", &[published], &times);
+        let after = write_roster("Roster", &[changed], &times);
+
+        assert!(sequence(&after) > sequence(&before));
+        assert!(after.contains("LAST-MODIFIED:20240115T030000Z\r\n"));
+        assert!(after.contains("DTSTAMP:20240115T030000Z\r\n"));
+    }
+
+    #[test]
+    fn test_long_lines_are_folded() {
+        let mut output = String::new();
+        push_line(&mut output, &format!("SUMMARY:{}", "x".repeat(100)));
+
+        let lines: Vec<&str> = output.split("\r\n").collect();
+        assert_eq!(lines[0].len(), 75);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(
+            unfold(&output),
+            vec![format!("SUMMARY:{}", "x".repeat(100))]
+        );
+    }
+
+    #[test]
+    fn test_rejects_non_calendar_input() {
+        assert!(matches!(
+            parse_holidays("date,name\n2024-12-25,Christmas"),
+            Err(DomainError::InvalidInput(_))
+        ));
+    }
+}
@@ -1,7 +1,15 @@
 use crate::api::requests::schedule_request::ScheduleJobRequest;
-use crate::domain::repositories::{ScheduleJobRepository, ShiftAssignmentRepository};
-use crate::domain::schedule_generator::ScheduleGenerator;
+use crate::domain::entities::HolidayCalendar;
+use crate::domain::events::{CompositeJobEventPublisher, JobEvent, JobEventPublisher};
+use crate::domain::fairness::FairnessLedger;
+use crate::domain::holiday_schedule::HolidaySchedule;
+use crate::domain::repositories::{
+    HolidayCalendarRepository, ScheduleJobRepository, ShiftAssignmentRepository, UnitOfWork,
+};
+use crate::domain::schedule_generator::{period_end_date, ScheduleGenerator, SCHEDULE_PERIOD_DAYS};
+use crate::domain::staff_pool::{MembershipPeriod, StaffPool};
 use crate::infrastructure::http_client::DataServiceClientTrait;
+use chrono::{Duration, NaiveDate};
 use shared::{DomainError, DomainResult, JobStatus};
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -9,24 +17,208 @@ use uuid::Uuid;
 
 pub struct JobProcessor {
     job_repo: Arc<dyn ScheduleJobRepository>,
-    assignment_repo: Arc<dyn ShiftAssignmentRepository>,
+    unit_of_work: Arc<dyn UnitOfWork>,
     data_service_client: Arc<dyn DataServiceClientTrait>,
     scheduler: Arc<ScheduleGenerator>,
+    event_publisher: Arc<dyn JobEventPublisher>,
+    history: Option<AssignmentHistory>,
+    holiday_repo: Option<Arc<dyn HolidayCalendarRepository>>,
+}
+
+/// Where earlier assignments are read from to balance burden across periods
+struct AssignmentHistory {
+    assignment_repo: Arc<dyn ShiftAssignmentRepository>,
+    lookback_days: u32,
 }
 
 impl JobProcessor {
     pub fn new(
         job_repo: Arc<dyn ScheduleJobRepository>,
-        assignment_repo: Arc<dyn ShiftAssignmentRepository>,
+        unit_of_work: Arc<dyn UnitOfWork>,
         data_service_client: Arc<dyn DataServiceClientTrait>,
         scheduler: Arc<ScheduleGenerator>,
     ) -> Self {
         Self {
             job_repo,
-            assignment_repo,
+            unit_of_work,
             data_service_client,
             scheduler,
+            event_publisher: Arc::new(CompositeJobEventPublisher::default()),
+            history: None,
+            holiday_repo: None,
+        }
+    }
+
+    /// Publish job status transitions through the given publisher
+    pub fn with_event_publisher(mut self, event_publisher: Arc<dyn JobEventPublisher>) -> Self {
+        self.event_publisher = event_publisher;
+        self
+    }
+
+    /// Rotate undesirable shifts using the last `lookback_days` of completed
+    /// assignments; without this every period starts from zero
+    pub fn with_assignment_history(
+        mut self,
+        assignment_repo: Arc<dyn ShiftAssignmentRepository>,
+        lookback_days: u32,
+    ) -> Self {
+        self.history = Some(AssignmentHistory {
+            assignment_repo,
+            lookback_days,
+        });
+        self
+    }
+
+    /// Make generation follow the holiday calendars of each group and the
+    /// global ones; without this every date is scheduled as an ordinary day
+    pub fn with_holiday_calendars(
+        mut self,
+        holiday_repo: Arc<dyn HolidayCalendarRepository>,
+    ) -> Self {
+        self.holiday_repo = Some(holiday_repo);
+        self
+    }
+
+    /// Staff of the groups during the requested period, with the members of
+    /// each subgroup when the job has coverage targets to fill
+    async fn load_staff_pool(
+        &self,
+        request: &ScheduleJobRequest,
+        group_ids: &[Uuid],
+    ) -> DomainResult<StaffPool> {
+        let mut pool = StaffPool::new();
+        let from = request.period_begin_date;
+        let to = period_end_date(from);
+
+        for group_id in group_ids {
+            if request.coverage_targets.is_empty() {
+                let staff_members = self
+                    .data_service_client
+                    .get_group_members(*group_id, from, to)
+                    .await
+                    .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+                pool = pool.with_group(
+                    *group_id,
+                    staff_members.iter().map(|s| (s.id, s.membership_period())),
+                );
+                continue;
+            }
+
+            let groups = self
+                .data_service_client
+                .get_resolved_groups(*group_id, from, to)
+                .await
+                .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+            for group in groups {
+                let members: Vec<(Uuid, MembershipPeriod)> = group
+                    .members
+                    .iter()
+                    .map(|s| (s.id, s.membership_period()))
+                    .collect();
+                pool = pool
+                    .with_group(*group_id, members.iter().copied())
+                    .with_subgroup_members(&group.path, members);
+            }
+        }
+
+        if let Some(target) = request
+            .coverage_targets
+            .iter()
+            .find(|target| !pool.contains_group(target.group_id))
+        {
+            return Err(DomainError::InvalidInput(format!(
+                "Coverage target group {} has no active members within the scheduled groups",
+                target.group_id
+            )));
         }
+
+        Ok(pool.with_coverage_targets(request.coverage_targets.clone()))
+    }
+
+    /// Holidays of the groups from the start of the fairness lookback to the
+    /// end of the requested period
+    async fn load_holidays(
+        &self,
+        group_ids: &[Uuid],
+        period_begin_date: NaiveDate,
+    ) -> DomainResult<HolidaySchedule> {
+        let Some(holiday_repo) = &self.holiday_repo else {
+            return Ok(HolidaySchedule::new());
+        };
+
+        // Global calendars are returned for every group
+        let mut calendars = Vec::new();
+        for group_id in group_ids {
+            for calendar in holiday_repo.find_for_group(*group_id).await? {
+                if !calendars
+                    .iter()
+                    .any(|c: &HolidayCalendar| c.id == calendar.id)
+                {
+                    calendars.push(calendar);
+                }
+            }
+        }
+        if calendars.is_empty() {
+            return Ok(HolidaySchedule::new());
+        }
+
+        let lookback_days = self.history.as_ref().map_or(0, |h| h.lookback_days);
+        let from = period_begin_date - Duration::days(lookback_days as i64);
+        let to = period_begin_date + Duration::days(SCHEDULE_PERIOD_DAYS as i64);
+        let calendar_ids: Vec<Uuid> = calendars.iter().map(|c| c.id).collect();
+        let holidays = holiday_repo.find_holidays(&calendar_ids, from, to).await?;
+
+        Ok(HolidaySchedule::from_calendars(&calendars, &holidays))
+    }
+
+    /// Burden carried by the staff before the requested period
+    async fn load_fairness_ledger(
+        &self,
+        staff_ids: &[Uuid],
+        period_begin_date: NaiveDate,
+        holidays: &HolidaySchedule,
+    ) -> DomainResult<FairnessLedger> {
+        let Some(history) = &self.history else {
+            return Ok(FairnessLedger::new());
+        };
+
+        let from = period_begin_date - Duration::days(history.lookback_days as i64);
+        let assignments = history
+            .assignment_repo
+            .find_history(staff_ids, from, period_begin_date)
+            .await?;
+
+        Ok(FairnessLedger::from_assignments(
+            holidays.clone(),
+            &assignments,
+        ))
+    }
+
+    async fn publish_status(
+        &self,
+        request: &ScheduleJobRequest,
+        status: JobStatus,
+        error_message: Option<String>,
+    ) {
+        let event = JobEvent::status_changed(
+            request.job_id,
+            request.staff_group_id,
+            request.period_begin_date,
+            status,
+            error_message,
+        );
+        self.event_publisher.publish(&event).await;
+    }
+
+    async fn publish_progress(&self, request: &ScheduleJobRequest, stage: &str, percent: u8) {
+        let event = JobEvent::progress(
+            request.job_id,
+            request.staff_group_id,
+            request.period_begin_date,
+            stage,
+            percent,
+        );
+        self.event_publisher.publish(&event).await;
     }
 
     pub fn start(
@@ -52,61 +244,118 @@ impl JobProcessor {
     async fn process_job(&self, request: ScheduleJobRequest) -> DomainResult<()> {
         tracing::info!("Processing schedule job {}", request.job_id);
 
-        self.job_repo
-            .update_status(request.job_id, JobStatus::Processing, None)
-            .await?;
+        // The job may have been superseded while it was waiting in the queue
+        if !self.job_repo.mark_processing(request.job_id).await? {
+            tracing::info!("Skipping job {} that is no longer pending", request.job_id);
+            return Ok(());
+        }
+        self.publish_status(&request, JobStatus::Processing, None)
+            .await;
 
         match self.execute_scheduling(&request).await {
-            Ok(()) => {
-                self.job_repo.mark_completed(request.job_id).await?;
+            Ok(true) => {
+                self.publish_status(&request, JobStatus::Completed, None)
+                    .await;
                 tracing::info!("Successfully completed job {}", request.job_id);
                 Ok(())
             }
+            Ok(false) => {
+                tracing::info!(
+                    "Job {} was cancelled while it ran; discarding its schedule",
+                    request.job_id
+                );
+                Ok(())
+            }
             Err(e) => {
                 let error_message = format!("Scheduling failed: {:?}", e);
-                self.job_repo
+                if !self
+                    .job_repo
                     .mark_failed(request.job_id, error_message.clone())
-                    .await?;
+                    .await?
+                {
+                    tracing::info!(
+                        "Job {} was cancelled while it ran; not marking it failed",
+                        request.job_id
+                    );
+                    return Ok(());
+                }
+                self.publish_status(&request, JobStatus::Failed, Some(error_message.clone()))
+                    .await;
                 tracing::error!("Job {} failed: {}", request.job_id, error_message);
                 Err(e)
             }
         }
     }
 
-    /// Execute the actual scheduling logic
-    async fn execute_scheduling(&self, request: &ScheduleJobRequest) -> DomainResult<()> {
-        let staff_members = self
-            .data_service_client
-            .get_group_members(request.staff_group_id)
-            .await
-            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+    /// Execute the actual scheduling logic and complete the job. Returns
+    /// `false` when the job was cancelled before it could be completed, in
+    /// which case nothing is saved.
+    async fn execute_scheduling(&self, request: &ScheduleJobRequest) -> DomainResult<bool> {
+        self.publish_progress(request, "fetching_staff", 10).await;
 
-        if staff_members.is_empty() {
+        let group_ids = request.group_ids();
+        let pool = self.load_staff_pool(request, &group_ids).await?;
+
+        if pool.is_empty() {
             return Err(DomainError::InvalidInput(
                 "No active staff members found in the group".to_string(),
             ));
         }
 
-        let staff_ids: Vec<Uuid> = staff_members.iter().map(|s| s.id).collect();
+        let staff_ids = pool.staff_ids();
 
         tracing::info!(
-            "Generating schedule for {} staff members starting {}",
+            "Generating schedule for {} staff members in {} groups starting {}",
             staff_ids.len(),
+            group_ids.len(),
             request.period_begin_date
         );
 
+        self.publish_progress(request, "generating_schedule", 40)
+            .await;
+
+        let holidays = self
+            .load_holidays(&group_ids, request.period_begin_date)
+            .await?;
+        let history = self
+            .load_fairness_ledger(staff_ids, request.period_begin_date, &holidays)
+            .await?;
+
         // Generate the schedule
         let assignments = self.scheduler.generate_schedule(
-            staff_ids,
+            &pool,
             request.period_begin_date,
             request.job_id,
+            request.seed,
+            &history,
+            &holidays,
         )?;
 
         tracing::info!("Generated {} shift assignments", assignments.len());
 
-        // Save assignments to database
-        self.assignment_repo.create_batch(assignments).await?;
+        self.publish_progress(request, "saving_assignments", 80)
+            .await;
+
+        // Save assignments and complete the job atomically. Anything left by an
+        // earlier failed attempt is replaced so retries don't hit the unique index.
+        let mut tx = self.unit_of_work.begin().await?;
+
+        let stale = tx.delete_assignments_for_job(request.job_id).await?;
+        if stale > 0 {
+            tracing::warn!(
+                "Replacing {} assignments left by an earlier attempt of job {}",
+                stale,
+                request.job_id
+            );
+        }
+
+        tx.create_assignments(&assignments).await?;
+        if !tx.mark_completed(request.job_id).await? {
+            // Superseded while running; dropping the transaction rolls back
+            return Ok(false);
+        }
+        tx.commit().await?;
 
-        Ok(())
+        Ok(true)
     }
 }
@@ -1,8 +1,15 @@
 pub mod config;
 pub mod database;
 pub mod http_client;
+pub mod icalendar;
 pub mod job_processor;
 pub mod redis;
+pub mod redis_events;
 pub mod repositories;
+pub mod roster_export;
+pub mod roster_print;
+pub mod webhook_dispatcher;
 
 pub use job_processor::JobProcessor;
+pub use redis_events::{RedisJobEventPublisher, RedisJobEventSubscriber};
+pub use webhook_dispatcher::WebhookDispatcher;
@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use redis::AsyncCommands;
+use shared::{DomainError, DomainResult};
+use uuid::Uuid;
+
+use crate::domain::events::{JobEvent, JobEventPublisher, JobEventStream, JobEventSubscriber};
+use crate::infrastructure::redis::RedisPool;
+
+/// Redis pub/sub channel carrying the events of one schedule job
+pub fn schedule_events_channel(schedule_id: Uuid) -> String {
+    format!("schedule:events:{}", schedule_id)
+}
+
+/// Publishes job events to Redis so API instances other than the one running
+/// the job can stream them
+pub struct RedisJobEventPublisher {
+    redis_pool: RedisPool,
+}
+
+impl RedisJobEventPublisher {
+    pub fn new(redis_pool: RedisPool) -> Self {
+        Self { redis_pool }
+    }
+}
+
+#[async_trait]
+impl JobEventPublisher for RedisJobEventPublisher {
+    async fn publish(&self, event: &JobEvent) {
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize job event: {:?}", e);
+                return;
+            }
+        };
+
+        let mut conn = self.redis_pool.clone();
+        if let Err(e) = conn
+            .publish::<_, _, ()>(schedule_events_channel(event.schedule_id), payload)
+            .await
+        {
+            tracing::warn!("Failed to publish job event to Redis: {:?}", e);
+        }
+    }
+}
+
+/// Subscribes to job events through Redis pub/sub.
+///
+/// Every subscription opens its own connection, since a connection in
+/// subscriber mode cannot be shared with regular commands.
+pub struct RedisJobEventSubscriber {
+    client: redis::Client,
+}
+
+impl RedisJobEventSubscriber {
+    pub fn new(client: redis::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl JobEventSubscriber for RedisJobEventSubscriber {
+    async fn subscribe(&self, schedule_id: Uuid) -> DomainResult<JobEventStream> {
+        let mut pubsub = self
+            .client
+            .get_async_pubsub()
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+
+        pubsub
+            .subscribe(schedule_events_channel(schedule_id))
+            .await
+            .map_err(|e| DomainError::ExternalServiceError(e.to_string()))?;
+
+        let stream = pubsub.into_on_message().filter_map(|message| async move {
+            let payload: String = message.get_payload().ok()?;
+            match serde_json::from_str::<JobEvent>(&payload) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    tracing::warn!("Ignoring malformed job event: {:?}", e);
+                    None
+                }
+            }
+        });
+
+        Ok(stream.boxed())
+    }
+}
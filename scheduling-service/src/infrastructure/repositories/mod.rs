@@ -1,5 +1,12 @@
+pub mod pg_copy;
+pub mod postgres_holiday_calendar_repository;
 pub mod postgres_schedule_job_repository;
 pub mod postgres_shift_assignment_repository;
+pub mod postgres_unit_of_work;
+pub mod postgres_webhook_repository;
 
+pub use postgres_holiday_calendar_repository::PostgresHolidayCalendarRepository;
 pub use postgres_schedule_job_repository::PostgresScheduleJobRepository;
 pub use postgres_shift_assignment_repository::PostgresShiftAssignmentRepository;
+pub use postgres_unit_of_work::PostgresUnitOfWork;
+pub use postgres_webhook_repository::PostgresWebhookRepository;
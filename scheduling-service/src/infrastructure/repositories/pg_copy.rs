@@ -0,0 +1,150 @@
+//! Encoder for PostgreSQL's binary `COPY ... FROM STDIN` format.
+//!
+//! See <https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9.4>.
+//! Values use each type's binary send format: uuids are 16 raw bytes, dates are
+//! days and timestamps microseconds since 2000-01-01, and enums are their label.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use shared::ShiftType;
+use uuid::Uuid;
+
+use crate::domain::entities::ShiftAssignment;
+
+const SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+const TRAILER: i16 = -1;
+
+/// Column list matching the tuples produced by [`encode_assignment`]
+pub const ASSIGNMENT_COPY_STATEMENT: &str =
+    "COPY shift_assignments (id, schedule_job_id, staff_id, staff_group_id, date, shift, created_at, updated_at) FROM STDIN WITH (FORMAT binary)";
+
+const ASSIGNMENT_FIELD_COUNT: i16 = 8;
+
+fn postgres_epoch_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid date")
+}
+
+/// Header that starts every binary COPY stream: signature, flags and an empty extension area
+pub fn write_header(buf: &mut Vec<u8>) {
+    buf.extend_from_slice(SIGNATURE);
+    buf.extend_from_slice(&0i32.to_be_bytes());
+    buf.extend_from_slice(&0i32.to_be_bytes());
+}
+
+/// Marker that ends a binary COPY stream
+pub fn write_trailer(buf: &mut Vec<u8>) {
+    buf.extend_from_slice(&TRAILER.to_be_bytes());
+}
+
+fn write_field(buf: &mut Vec<u8>, value: &[u8]) {
+    buf.extend_from_slice(&(value.len() as i32).to_be_bytes());
+    buf.extend_from_slice(value);
+}
+
+fn write_uuid(buf: &mut Vec<u8>, value: Uuid) {
+    write_field(buf, value.as_bytes());
+}
+
+fn write_date(buf: &mut Vec<u8>, value: NaiveDate) {
+    let days = (value - postgres_epoch_date()).num_days() as i32;
+    write_field(buf, &days.to_be_bytes());
+}
+
+fn write_timestamptz(buf: &mut Vec<u8>, value: DateTime<Utc>) {
+    let epoch = postgres_epoch_date()
+        .and_hms_opt(0, 0, 0)
+        .expect("valid time")
+        .and_utc();
+    let micros = (value - epoch)
+        .num_microseconds()
+        .expect("timestamp within PostgreSQL range");
+    write_field(buf, &micros.to_be_bytes());
+}
+
+fn shift_label(shift: ShiftType) -> &'static [u8] {
+    match shift {
+        ShiftType::Morning => b"MORNING",
+        ShiftType::Evening => b"EVENING",
+        ShiftType::DayOff => b"DAY_OFF",
+    }
+}
+
+/// Append one `shift_assignments` tuple
+pub fn encode_assignment(buf: &mut Vec<u8>, assignment: &ShiftAssignment) {
+    buf.extend_from_slice(&ASSIGNMENT_FIELD_COUNT.to_be_bytes());
+    write_uuid(buf, assignment.id);
+    write_uuid(buf, assignment.schedule_job_id);
+    write_uuid(buf, assignment.staff_id);
+    write_uuid(buf, assignment.staff_group_id);
+    write_date(buf, assignment.date);
+    write_field(buf, shift_label(assignment.shift));
+    write_timestamptz(buf, assignment.created_at);
+    write_timestamptz(buf, assignment.updated_at);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_assignment() -> ShiftAssignment {
+        ShiftAssignment {
+            id: Uuid::from_u128(1),
+            schedule_job_id: Uuid::from_u128(2),
+            staff_id: Uuid::from_u128(3),
+            staff_group_id: Uuid::from_u128(4),
+            date: NaiveDate::from_ymd_opt(2000, 1, 3).unwrap(),
+            shift: ShiftType::DayOff,
+            created_at: NaiveDate::from_ymd_opt(2000, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 1)
+                .unwrap()
+                .and_utc(),
+            updated_at: NaiveDate::from_ymd_opt(2000, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 2)
+                .unwrap()
+                .and_utc(),
+        }
+    }
+
+    #[test]
+    fn test_header_and_trailer() {
+        let mut buf = Vec::new();
+        write_header(&mut buf);
+        write_trailer(&mut buf);
+
+        assert_eq!(&buf[..11], SIGNATURE);
+        assert_eq!(&buf[11..19], &[0u8; 8]);
+        assert_eq!(&buf[19..], &[0xff, 0xff]);
+    }
+
+    #[test]
+    fn test_encode_assignment_layout() {
+        let mut buf = Vec::new();
+        encode_assignment(&mut buf, &sample_assignment());
+
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&8i16.to_be_bytes());
+        for id in 1u128..=4 {
+            expected.extend_from_slice(&16i32.to_be_bytes());
+            expected.extend_from_slice(&id.to_be_bytes());
+        }
+        expected.extend_from_slice(&4i32.to_be_bytes());
+        expected.extend_from_slice(&2i32.to_be_bytes());
+        expected.extend_from_slice(&7i32.to_be_bytes());
+        expected.extend_from_slice(b"DAY_OFF");
+        expected.extend_from_slice(&8i32.to_be_bytes());
+        expected.extend_from_slice(&1_000_000i64.to_be_bytes());
+        expected.extend_from_slice(&8i32.to_be_bytes());
+        expected.extend_from_slice(&2_000_000i64.to_be_bytes());
+
+        assert_eq!(buf, expected);
+    }
+
+    #[test]
+    fn test_dates_before_postgres_epoch_are_negative() {
+        let mut buf = Vec::new();
+        write_date(&mut buf, NaiveDate::from_ymd_opt(1999, 12, 31).unwrap());
+
+        assert_eq!(&buf[4..], &(-1i32).to_be_bytes());
+    }
+}
@@ -0,0 +1,256 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use shared::{DomainError, DomainResult, PaginationParams};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::domain::entities::{Holiday, HolidayCalendar};
+use crate::domain::repositories::HolidayCalendarRepository;
+
+pub struct PostgresHolidayCalendarRepository {
+    pool: PgPool,
+}
+
+impl PostgresHolidayCalendarRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HolidayCalendarRepository for PostgresHolidayCalendarRepository {
+    async fn create(&self, calendar: HolidayCalendar) -> DomainResult<HolidayCalendar> {
+        let created = sqlx::query_as::<_, HolidayCalendar>(
+            r#"
+            INSERT INTO holiday_calendars (id, name, staff_group_id, coverage_percent, paid_leave, premium_weight, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING id, name, staff_group_id, coverage_percent, paid_leave, premium_weight, created_at, updated_at
+            "#,
+        )
+        .bind(calendar.id)
+        .bind(calendar.name)
+        .bind(calendar.staff_group_id)
+        .bind(calendar.coverage_percent)
+        .bind(calendar.paid_leave)
+        .bind(calendar.premium_weight)
+        .bind(calendar.created_at)
+        .bind(calendar.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<HolidayCalendar>> {
+        let calendar = sqlx::query_as::<_, HolidayCalendar>(
+            r#"
+            SELECT id, name, staff_group_id, coverage_percent, paid_leave, premium_weight, created_at, updated_at
+            FROM holiday_calendars
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(calendar)
+    }
+
+    async fn list(
+        &self,
+        staff_group_id: Option<Uuid>,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<HolidayCalendar>, u64)> {
+        let offset = (params.page - 1) * params.page_size;
+
+        let calendars = sqlx::query_as::<_, HolidayCalendar>(
+            r#"
+            SELECT id, name, staff_group_id, coverage_percent, paid_leave, premium_weight, created_at, updated_at
+            FROM holiday_calendars
+            WHERE $1::uuid IS NULL OR staff_group_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(staff_group_id)
+        .bind(params.page_size as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let total: (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM holiday_calendars WHERE $1::uuid IS NULL OR staff_group_id = $1",
+        )
+        .bind(staff_group_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok((calendars, total.0 as u64))
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM holiday_calendars WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Holiday calendar with id {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_for_group(&self, staff_group_id: Uuid) -> DomainResult<Vec<HolidayCalendar>> {
+        let calendars = sqlx::query_as::<_, HolidayCalendar>(
+            r#"
+            SELECT id, name, staff_group_id, coverage_percent, paid_leave, premium_weight, created_at, updated_at
+            FROM holiday_calendars
+            WHERE staff_group_id = $1 OR staff_group_id IS NULL
+            "#,
+        )
+        .bind(staff_group_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(calendars)
+    }
+
+    async fn upsert_holidays(&self, holidays: Vec<Holiday>) -> DomainResult<Vec<Holiday>> {
+        // ON CONFLICT cannot touch the same row twice in one statement, so
+        // collapse repeated dates first and keep the last entry
+        let holidays: BTreeMap<(Uuid, NaiveDate), Holiday> = holidays
+            .into_iter()
+            .map(|holiday| ((holiday.calendar_id, holiday.date), holiday))
+            .collect();
+        if holidays.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // PostgreSQL has a limit on the number of bind parameters, so we chunk the inserts
+        const BATCH_SIZE: usize = 1000;
+
+        let holidays: Vec<Holiday> = holidays.into_values().collect();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        let mut stored = Vec::with_capacity(holidays.len());
+
+        for chunk in holidays.chunks(BATCH_SIZE) {
+            let mut builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                "INSERT INTO holidays (id, calendar_id, date, name, source, external_uid, created_at) ",
+            );
+            builder.push_values(chunk, |mut row, holiday| {
+                row.push_bind(holiday.id)
+                    .push_bind(holiday.calendar_id)
+                    .push_bind(holiday.date)
+                    .push_bind(&holiday.name)
+                    .push_bind(holiday.source)
+                    .push_bind(&holiday.external_uid)
+                    .push_bind(holiday.created_at);
+            });
+            builder.push(
+                r#"
+                ON CONFLICT (calendar_id, date) DO UPDATE
+                SET name = EXCLUDED.name, source = EXCLUDED.source, external_uid = EXCLUDED.external_uid
+                RETURNING id, calendar_id, date, name, source, external_uid, created_at
+                "#,
+            );
+
+            stored.extend(
+                builder
+                    .build_query_as::<Holiday>()
+                    .fetch_all(&mut *tx)
+                    .await
+                    .map_err(|e| DomainError::DatabaseError(e.to_string()))?,
+            );
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+        stored.sort_by_key(|holiday| holiday.date);
+
+        Ok(stored)
+    }
+
+    async fn list_holidays(
+        &self,
+        calendar_id: Uuid,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> DomainResult<Vec<Holiday>> {
+        let holidays = sqlx::query_as::<_, Holiday>(
+            r#"
+            SELECT id, calendar_id, date, name, source, external_uid, created_at
+            FROM holidays
+            WHERE calendar_id = $1
+              AND ($2::date IS NULL OR date >= $2)
+              AND ($3::date IS NULL OR date <= $3)
+            ORDER BY date
+            "#,
+        )
+        .bind(calendar_id)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(holidays)
+    }
+
+    async fn delete_holiday(&self, calendar_id: Uuid, holiday_id: Uuid) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM holidays WHERE id = $1 AND calendar_id = $2")
+            .bind(holiday_id)
+            .bind(calendar_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Holiday with id {} not found in calendar {}",
+                holiday_id, calendar_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_holidays(
+        &self,
+        calendar_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<Holiday>> {
+        let holidays = sqlx::query_as::<_, Holiday>(
+            r#"
+            SELECT id, calendar_id, date, name, source, external_uid, created_at
+            FROM holidays
+            WHERE calendar_id = ANY($1) AND date >= $2 AND date < $3
+            ORDER BY date
+            "#,
+        )
+        .bind(calendar_ids)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(holidays)
+    }
+}
@@ -1,10 +1,11 @@
 use async_trait::async_trait;
-use shared::{DomainError, DomainResult, JobStatus};
-use sqlx::PgPool;
+use shared::{DomainError, DomainResult, JobStatus, PaginationParams};
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
 use crate::domain::entities::ScheduleJob;
-use crate::domain::repositories::ScheduleJobRepository;
+use crate::domain::repositories::{ExclusiveCreate, JobFilter, ScheduleJobRepository};
 
 pub struct PostgresScheduleJobRepository {
     pool: PgPool,
@@ -14,16 +15,14 @@ impl PostgresScheduleJobRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
-}
 
-#[async_trait]
-impl ScheduleJobRepository for PostgresScheduleJobRepository {
-    async fn create(&self, job: ScheduleJob) -> DomainResult<ScheduleJob> {
+    /// Insert a job on the given connection
+    async fn insert(conn: &mut PgConnection, job: ScheduleJob) -> DomainResult<ScheduleJob> {
         let created_job = sqlx::query_as::<_, ScheduleJob>(
             r#"
-            INSERT INTO schedule_jobs (id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at
+            INSERT INTO schedule_jobs (id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            RETURNING id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets
             "#,
         )
         .bind(job.id)
@@ -33,17 +32,61 @@ impl ScheduleJobRepository for PostgresScheduleJobRepository {
         .bind(job.error_message)
         .bind(job.created_at)
         .bind(job.updated_at)
-        .fetch_one(&self.pool)
+        .bind(job.idempotency_key)
+        .bind(job.request_hash)
+        .bind(job.seed)
+        .bind(job.additional_group_ids)
+        .bind(Json(job.coverage_targets))
+        .fetch_one(&mut *conn)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
         Ok(created_job)
     }
 
+    /// Append the WHERE clause for a job filter to the query
+    fn push_filter(query_builder: &mut QueryBuilder<'_, Postgres>, filter: &JobFilter) {
+        query_builder.push(" WHERE TRUE");
+
+        if let Some(staff_group_id) = filter.staff_group_id {
+            query_builder
+                .push(" AND staff_group_id = ")
+                .push_bind(staff_group_id);
+        }
+        if let Some(status) = &filter.status {
+            query_builder
+                .push(" AND status = ")
+                .push_bind(status.clone());
+        }
+        if let Some(period_from) = filter.period_from {
+            query_builder
+                .push(" AND period_begin_date >= ")
+                .push_bind(period_from);
+        }
+        if let Some(period_to) = filter.period_to {
+            query_builder
+                .push(" AND period_begin_date <= ")
+                .push_bind(period_to);
+        }
+    }
+}
+
+#[async_trait]
+impl ScheduleJobRepository for PostgresScheduleJobRepository {
+    async fn create(&self, job: ScheduleJob) -> DomainResult<ScheduleJob> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Self::insert(&mut conn, job).await
+    }
+
     async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<ScheduleJob>> {
         let job = sqlx::query_as::<_, ScheduleJob>(
             r#"
-            SELECT id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at
+            SELECT id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets
             FROM schedule_jobs
             WHERE id = $1
             "#,
@@ -56,52 +99,152 @@ impl ScheduleJobRepository for PostgresScheduleJobRepository {
         Ok(job)
     }
 
-    async fn update_status(
+    async fn find_by_idempotency_key(&self, key: &str) -> DomainResult<Option<ScheduleJob>> {
+        let job = sqlx::query_as::<_, ScheduleJob>(
+            r#"
+            SELECT id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets
+            FROM schedule_jobs
+            WHERE idempotency_key = $1
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(job)
+    }
+
+    async fn create_exclusive(
         &self,
-        id: Uuid,
-        status: JobStatus,
-        error_message: Option<String>,
-    ) -> DomainResult<()> {
+        job: ScheduleJob,
+        supersede: bool,
+    ) -> DomainResult<ExclusiveCreate> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        // Held until commit, so a concurrent submission for the same group and
+        // period sees this job as active
         sqlx::query(
+            "SELECT pg_advisory_xact_lock(hashtextextended($1::text || ':' || $2::text, 0))",
+        )
+        .bind(job.staff_group_id)
+        .bind(job.period_begin_date)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let active = sqlx::query_as::<_, ScheduleJob>(
+            r#"
+            SELECT id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets
+            FROM schedule_jobs
+            WHERE staff_group_id = $1
+              AND period_begin_date = $2
+              AND status IN ('PENDING', 'PROCESSING')
+            ORDER BY created_at
+            "#,
+        )
+        .bind(job.staff_group_id)
+        .bind(job.period_begin_date)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        if !supersede {
+            if let Some(active_job) = active.into_iter().next() {
+                return Ok(ExclusiveCreate::Conflict(active_job));
+            }
+        }
+
+        let superseded = sqlx::query_as::<_, ScheduleJob>(
             r#"
             UPDATE schedule_jobs
             SET status = $1, error_message = $2, updated_at = NOW()
-            WHERE id = $3
+            WHERE staff_group_id = $3
+              AND period_begin_date = $4
+              AND status IN ('PENDING', 'PROCESSING')
+            RETURNING id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets
             "#,
         )
-        .bind(status)
-        .bind(error_message)
-        .bind(id)
-        .execute(&self.pool)
+        .bind(JobStatus::Cancelled)
+        .bind(format!("Superseded by job {}", job.id))
+        .bind(job.staff_group_id)
+        .bind(job.period_begin_date)
+        .fetch_all(&mut *tx)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        Ok(())
+        let job = Self::insert(&mut tx, job).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(ExclusiveCreate::Created { job, superseded })
     }
 
-    async fn mark_completed(&self, id: Uuid) -> DomainResult<()> {
-        sqlx::query(
+    async fn list(
+        &self,
+        filter: &JobFilter,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<ScheduleJob>, u64)> {
+        let offset = (params.page - 1) * params.page_size;
+
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "SELECT id, staff_group_id, period_begin_date, status, error_message, created_at, updated_at, completed_at, idempotency_key, request_hash, seed, additional_group_ids, coverage_targets FROM schedule_jobs",
+        );
+        Self::push_filter(&mut query_builder, filter);
+        query_builder
+            .push(" ORDER BY created_at DESC, id LIMIT ")
+            .push_bind(params.page_size as i64)
+            .push(" OFFSET ")
+            .push_bind(offset as i64);
+
+        let jobs = query_builder
+            .build_query_as::<ScheduleJob>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let mut count_builder: QueryBuilder<Postgres> =
+            QueryBuilder::new("SELECT COUNT(*) FROM schedule_jobs");
+        Self::push_filter(&mut count_builder, filter);
+
+        let total: (i64,) = count_builder
+            .build_query_as()
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok((jobs, total.0 as u64))
+    }
+
+    async fn mark_processing(&self, id: Uuid) -> DomainResult<bool> {
+        let result = sqlx::query(
             r#"
             UPDATE schedule_jobs
-            SET status = $1, completed_at = NOW(), updated_at = NOW()
-            WHERE id = $2
+            SET status = $1, updated_at = NOW()
+            WHERE id = $2 AND status IN ('PENDING', 'PROCESSING')
             "#,
         )
-        .bind(JobStatus::Completed)
+        .bind(JobStatus::Processing)
         .bind(id)
         .execute(&self.pool)
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        Ok(())
+        Ok(result.rows_affected() == 1)
     }
 
-    async fn mark_failed(&self, id: Uuid, error_message: String) -> DomainResult<()> {
-        sqlx::query(
+    async fn mark_failed(&self, id: Uuid, error_message: String) -> DomainResult<bool> {
+        let result = sqlx::query(
             r#"
             UPDATE schedule_jobs
             SET status = $1, error_message = $2, updated_at = NOW()
-            WHERE id = $3
+            WHERE id = $3 AND status IN ('PENDING', 'PROCESSING')
             "#,
         )
         .bind(JobStatus::Failed)
@@ -111,6 +254,6 @@ impl ScheduleJobRepository for PostgresScheduleJobRepository {
         .await
         .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
 
-        Ok(())
+        Ok(result.rows_affected() == 1)
     }
 }
@@ -1,8 +1,10 @@
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use shared::{DomainError, DomainResult};
-use sqlx::{PgPool, Postgres, QueryBuilder};
+use sqlx::{PgConnection, PgPool, Postgres, QueryBuilder};
 use uuid::Uuid;
 
+use super::pg_copy;
 use crate::domain::entities::ShiftAssignment;
 use crate::domain::repositories::ShiftAssignmentRepository;
 
@@ -16,45 +18,121 @@ impl PostgresShiftAssignmentRepository {
     }
 }
 
-#[async_trait]
-impl ShiftAssignmentRepository for PostgresShiftAssignmentRepository {
-    async fn create_batch(&self, assignments: Vec<ShiftAssignment>) -> DomainResult<()> {
-        if assignments.is_empty() {
-            return Ok(());
-        }
+/// Batches at least this large are written with binary COPY
+pub const COPY_THRESHOLD: usize = 5_000;
+
+/// How a batch of assignments is written to the database
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertStrategy {
+    /// Multi-row `INSERT ... VALUES` statements
+    Values,
+    /// A single binary `COPY ... FROM STDIN` stream
+    Copy,
+}
 
-        // Use batch insert with QueryBuilder for better performance
-        // PostgreSQL has a limit on the number of bind parameters, so we chunk the inserts
-        const BATCH_SIZE: usize = 1000;
-
-        for chunk in assignments.chunks(BATCH_SIZE) {
-            let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
-                "INSERT INTO shift_assignments (id, schedule_job_id, staff_id, date, shift, created_at) "
-            );
-
-            query_builder.push_values(chunk, |mut b, assignment| {
-                b.push_bind(assignment.id)
-                    .push_bind(assignment.schedule_job_id)
-                    .push_bind(assignment.staff_id)
-                    .push_bind(assignment.date)
-                    .push_bind(assignment.shift)
-                    .push_bind(assignment.created_at);
-            });
-
-            query_builder
-                .build()
-                .execute(&self.pool)
-                .await
-                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+impl InsertStrategy {
+    /// COPY has a fixed setup cost but is far cheaper per row, so it only
+    /// pays off for large batches
+    pub fn for_batch_size(len: usize) -> Self {
+        if len >= COPY_THRESHOLD {
+            Self::Copy
+        } else {
+            Self::Values
         }
+    }
+}
+
+/// Insert assignments on an existing connection, inside the caller's transaction
+pub async fn insert_assignments(
+    conn: &mut PgConnection,
+    assignments: &[ShiftAssignment],
+    strategy: InsertStrategy,
+) -> DomainResult<()> {
+    if assignments.is_empty() {
+        return Ok(());
+    }
+
+    match strategy {
+        InsertStrategy::Values => insert_with_values(conn, assignments).await,
+        InsertStrategy::Copy => insert_with_copy(conn, assignments).await,
+    }
+}
+
+async fn insert_with_values(
+    conn: &mut PgConnection,
+    assignments: &[ShiftAssignment],
+) -> DomainResult<()> {
+    // PostgreSQL has a limit on the number of bind parameters, so we chunk the inserts
+    const BATCH_SIZE: usize = 1000;
+
+    for chunk in assignments.chunks(BATCH_SIZE) {
+        let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+            "INSERT INTO shift_assignments (id, schedule_job_id, staff_id, staff_group_id, date, shift, created_at, updated_at) ",
+        );
+
+        query_builder.push_values(chunk, |mut b, assignment| {
+            b.push_bind(assignment.id)
+                .push_bind(assignment.schedule_job_id)
+                .push_bind(assignment.staff_id)
+                .push_bind(assignment.staff_group_id)
+                .push_bind(assignment.date)
+                .push_bind(assignment.shift)
+                .push_bind(assignment.created_at)
+                .push_bind(assignment.updated_at);
+        });
 
-        Ok(())
+        query_builder
+            .build()
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
     }
 
+    Ok(())
+}
+
+async fn insert_with_copy(
+    conn: &mut PgConnection,
+    assignments: &[ShiftAssignment],
+) -> DomainResult<()> {
+    // Rows are encoded and sent in chunks so the whole batch is never buffered at once
+    const ROWS_PER_MESSAGE: usize = 10_000;
+
+    let mut copy = conn
+        .copy_in_raw(pg_copy::ASSIGNMENT_COPY_STATEMENT)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+    let mut buf = Vec::new();
+    pg_copy::write_header(&mut buf);
+
+    for chunk in assignments.chunks(ROWS_PER_MESSAGE) {
+        for assignment in chunk {
+            pg_copy::encode_assignment(&mut buf, assignment);
+        }
+        copy.send(std::mem::take(&mut buf))
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+    }
+
+    pg_copy::write_trailer(&mut buf);
+    copy.send(buf)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+    copy.finish()
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+    Ok(())
+}
+
+#[async_trait]
+impl ShiftAssignmentRepository for PostgresShiftAssignmentRepository {
     async fn find_by_job_id(&self, job_id: Uuid) -> DomainResult<Vec<ShiftAssignment>> {
         let assignments = sqlx::query_as::<_, ShiftAssignment>(
             r#"
-            SELECT id, schedule_job_id, staff_id, date, shift, created_at
+            SELECT id, schedule_job_id, staff_id, staff_group_id, date, shift, created_at, updated_at
             FROM shift_assignments
             WHERE schedule_job_id = $1
             ORDER BY date, staff_id
@@ -67,4 +145,53 @@ impl ShiftAssignmentRepository for PostgresShiftAssignmentRepository {
 
         Ok(assignments)
     }
+
+    async fn find_history(
+        &self,
+        staff_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<ShiftAssignment>> {
+        let assignments = sqlx::query_as::<_, ShiftAssignment>(
+            r#"
+            SELECT DISTINCT ON (a.staff_id, a.date)
+                a.id, a.schedule_job_id, a.staff_id, a.staff_group_id, a.date, a.shift, a.created_at, a.updated_at
+            FROM shift_assignments a
+            JOIN schedule_jobs j ON j.id = a.schedule_job_id
+            WHERE j.status = 'COMPLETED'
+              AND a.staff_id = ANY($1)
+              AND a.date >= $2
+              AND a.date < $3
+            ORDER BY a.staff_id, a.date, j.completed_at DESC
+            "#,
+        )
+        .bind(staff_ids)
+        .bind(from)
+        .bind(to)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(assignments)
+    }
+
+    async fn find_published_for_staff(&self, staff_id: Uuid) -> DomainResult<Vec<ShiftAssignment>> {
+        let assignments = sqlx::query_as::<_, ShiftAssignment>(
+            r#"
+            SELECT DISTINCT ON (a.date)
+                a.id, a.schedule_job_id, a.staff_id, a.staff_group_id, a.date, a.shift, a.created_at, a.updated_at
+            FROM shift_assignments a
+            JOIN schedule_jobs j ON j.id = a.schedule_job_id
+            WHERE j.status = 'COMPLETED'
+              AND a.staff_id = $1
+            ORDER BY a.date, j.completed_at DESC
+            "#,
+        )
+        .bind(staff_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(assignments)
+    }
 }
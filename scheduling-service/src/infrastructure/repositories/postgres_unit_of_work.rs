@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use shared::{DomainError, DomainResult, JobStatus};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::postgres_shift_assignment_repository::{insert_assignments, InsertStrategy};
+use crate::domain::entities::ShiftAssignment;
+use crate::domain::repositories::{Transaction, UnitOfWork};
+
+pub struct PostgresUnitOfWork {
+    pool: PgPool,
+}
+
+impl PostgresUnitOfWork {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for PostgresUnitOfWork {
+    async fn begin(&self) -> DomainResult<Box<dyn Transaction>> {
+        let tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(Box::new(PostgresTransaction { tx }))
+    }
+}
+
+/// Wraps a sqlx transaction, which rolls back when dropped uncommitted
+pub struct PostgresTransaction {
+    tx: sqlx::Transaction<'static, sqlx::Postgres>,
+}
+
+#[async_trait]
+impl Transaction for PostgresTransaction {
+    async fn delete_assignments_for_job(&mut self, job_id: Uuid) -> DomainResult<u64> {
+        let result = sqlx::query("DELETE FROM shift_assignments WHERE schedule_job_id = $1")
+            .bind(job_id)
+            .execute(&mut *self.tx)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn create_assignments(&mut self, assignments: &[ShiftAssignment]) -> DomainResult<()> {
+        let strategy = InsertStrategy::for_batch_size(assignments.len());
+        insert_assignments(&mut self.tx, assignments, strategy).await
+    }
+
+    async fn mark_completed(&mut self, job_id: Uuid) -> DomainResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE schedule_jobs
+            SET status = $1, completed_at = NOW(), updated_at = NOW()
+            WHERE id = $2 AND status = $3
+            "#,
+        )
+        .bind(JobStatus::Completed)
+        .bind(job_id)
+        .bind(JobStatus::Processing)
+        .execute(&mut *self.tx)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn commit(self: Box<Self>) -> DomainResult<()> {
+        self.tx
+            .commit()
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))
+    }
+}
@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use shared::{DomainError, DomainResult, PaginationParams};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::domain::entities::{WebhookDelivery, WebhookSubscription};
+use crate::domain::repositories::WebhookRepository;
+
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    async fn create(&self, subscription: WebhookSubscription) -> DomainResult<WebhookSubscription> {
+        let created = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            INSERT INTO webhook_subscriptions (id, url, secret, staff_group_id, active, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id, url, secret, staff_group_id, active, created_at, updated_at
+            "#,
+        )
+        .bind(subscription.id)
+        .bind(subscription.url)
+        .bind(subscription.secret)
+        .bind(subscription.staff_group_id)
+        .bind(subscription.active)
+        .bind(subscription.created_at)
+        .bind(subscription.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(created)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<WebhookSubscription>> {
+        let subscription = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, secret, staff_group_id, active, created_at, updated_at
+            FROM webhook_subscriptions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(subscription)
+    }
+
+    async fn list(
+        &self,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<WebhookSubscription>, u64)> {
+        let offset = (params.page - 1) * params.page_size;
+
+        let subscriptions = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, secret, staff_group_id, active, created_at, updated_at
+            FROM webhook_subscriptions
+            ORDER BY created_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(params.page_size as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let total: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM webhook_subscriptions")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok((subscriptions, total.0 as u64))
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        let result = sqlx::query("DELETE FROM webhook_subscriptions WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::NotFound(format!(
+                "Webhook subscription with id {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_active_for_group(
+        &self,
+        staff_group_id: Uuid,
+    ) -> DomainResult<Vec<WebhookSubscription>> {
+        let subscriptions = sqlx::query_as::<_, WebhookSubscription>(
+            r#"
+            SELECT id, url, secret, staff_group_id, active, created_at, updated_at
+            FROM webhook_subscriptions
+            WHERE active AND (staff_group_id = $1 OR staff_group_id IS NULL)
+            "#,
+        )
+        .bind(staff_group_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(subscriptions)
+    }
+
+    async fn record_delivery(&self, delivery: WebhookDelivery) -> DomainResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_deliveries (id, delivery_id, subscription_id, schedule_job_id, event_type, payload, attempt, status_code, success, error_message, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(delivery.id)
+        .bind(delivery.delivery_id)
+        .bind(delivery.subscription_id)
+        .bind(delivery.schedule_job_id)
+        .bind(delivery.event_type)
+        .bind(delivery.payload)
+        .bind(delivery.attempt)
+        .bind(delivery.status_code)
+        .bind(delivery.success)
+        .bind(delivery.error_message)
+        .bind(delivery.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: Uuid,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<WebhookDelivery>, u64)> {
+        let offset = (params.page - 1) * params.page_size;
+
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            r#"
+            SELECT id, delivery_id, subscription_id, schedule_job_id, event_type, payload, attempt, status_code, success, error_message, created_at
+            FROM webhook_deliveries
+            WHERE subscription_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(params.page_size as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        let total: (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM webhook_deliveries WHERE subscription_id = $1")
+                .bind(subscription_id)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(|e| DomainError::DatabaseError(e.to_string()))?;
+
+        Ok((deliveries, total.0 as u64))
+    }
+}
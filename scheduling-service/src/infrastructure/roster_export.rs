@@ -0,0 +1,195 @@
+//! Spreadsheet exports of a roster grid.
+//!
+//! Both formats share one layout: a header row, one row per staff member with
+//! their totals at the end, and a row of daily totals per shift type.
+
+use rust_xlsxwriter::{Format, Workbook, XlsxError};
+use shared::{DomainError, DomainResult};
+
+use crate::presentation::{RosterGrid, ShiftTotals};
+
+const TOTAL_HEADERS: [&str; 3] = ["Mornings", "Evenings", "Days off"];
+
+/// A spreadsheet cell; totals are kept numeric so XLSX can sum them
+enum Cell {
+    Text(String),
+    Count(usize),
+    Empty,
+}
+
+fn counts(totals: &ShiftTotals) -> [usize; 3] {
+    [totals.mornings, totals.evenings, totals.days_off]
+}
+
+fn header(grid: &RosterGrid) -> Vec<String> {
+    ["Staff", "Staff ID"]
+        .into_iter()
+        .map(str::to_string)
+        .chain(grid.dates.iter().map(|date| date.to_string()))
+        .chain(TOTAL_HEADERS.into_iter().map(str::to_string))
+        .collect()
+}
+
+/// Staff rows followed by the daily totals rows
+fn body(grid: &RosterGrid) -> Vec<Vec<Cell>> {
+    let mut rows: Vec<Vec<Cell>> = grid
+        .rows
+        .iter()
+        .map(|row| {
+            [
+                Cell::Text(row.staff_name.clone()),
+                Cell::Text(row.staff_id.to_string()),
+            ]
+            .into_iter()
+            .chain(row.shifts.iter().map(|shift| match shift {
+                Some(shift) => Cell::Text(RosterGrid::label(*shift).to_string()),
+                None => Cell::Empty,
+            }))
+            .chain(counts(&row.totals).into_iter().map(Cell::Count))
+            .collect()
+        })
+        .collect();
+
+    // One totals row per shift type, with the overall total in its own column
+    for (kind, label) in TOTAL_HEADERS.iter().enumerate() {
+        let overall = counts(&grid.totals)[kind];
+        let row = [
+            Cell::Text(format!("Total {}", label.to_lowercase())),
+            Cell::Empty,
+        ]
+        .into_iter()
+        .chain(
+            grid.day_totals
+                .iter()
+                .map(|totals| Cell::Count(counts(totals)[kind])),
+        )
+        .chain((0..TOTAL_HEADERS.len()).map(|column| {
+            if column == kind {
+                Cell::Count(overall)
+            } else {
+                Cell::Empty
+            }
+        }))
+        .collect();
+        rows.push(row);
+    }
+
+    rows
+}
+
+/// Render the grid as CSV
+pub fn write_csv(grid: &RosterGrid) -> DomainResult<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let to_error = |e: csv::Error| DomainError::InternalError(e.to_string());
+
+    writer.write_record(header(grid)).map_err(to_error)?;
+    for row in body(grid) {
+        let record = row.into_iter().map(|cell| match cell {
+            Cell::Text(text) => text,
+            Cell::Count(count) => count.to_string(),
+            Cell::Empty => String::new(),
+        });
+        writer.write_record(record).map_err(to_error)?;
+    }
+
+    writer
+        .into_inner()
+        .map_err(|e| DomainError::InternalError(e.to_string()))
+}
+
+fn build_xlsx(grid: &RosterGrid) -> Result<Vec<u8>, XlsxError> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Roster")?;
+
+    let bold = Format::new().set_bold();
+    for (column, title) in header(grid).iter().enumerate() {
+        worksheet.write_string_with_format(0, column as u16, title, &bold)?;
+    }
+
+    let first_total_row = grid.rows.len() + 1;
+    for (index, row) in body(grid).into_iter().enumerate() {
+        let row_number = (index + 1) as u32;
+        for (column, cell) in row.into_iter().enumerate() {
+            let column = column as u16;
+            match cell {
+                Cell::Text(text) if index + 1 >= first_total_row => {
+                    worksheet.write_string_with_format(row_number, column, &text, &bold)?;
+                }
+                Cell::Text(text) => {
+                    worksheet.write_string(row_number, column, &text)?;
+                }
+                Cell::Count(count) => {
+                    worksheet.write_number(row_number, column, count as f64)?;
+                }
+                Cell::Empty => {}
+            }
+        }
+    }
+
+    worksheet.set_column_width(0, 24)?;
+    worksheet.set_column_width(1, 38)?;
+    worksheet.set_freeze_panes(1, 2)?;
+
+    workbook.save_to_buffer()
+}
+
+/// Render the grid as an XLSX workbook with a single sheet
+pub fn write_xlsx(grid: &RosterGrid) -> DomainResult<Vec<u8>> {
+    build_xlsx(grid).map_err(|e| DomainError::InternalError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ShiftAssignment;
+    use chrono::{NaiveDate, Utc};
+    use shared::ShiftType;
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn grid() -> RosterGrid {
+        let staff_id = Uuid::from_u128(1);
+        let assignments: Vec<ShiftAssignment> = [(15, ShiftType::Morning), (16, ShiftType::DayOff)]
+            .into_iter()
+            .map(|(day, shift)| ShiftAssignment {
+                id: Uuid::new_v4(),
+                schedule_job_id: Uuid::nil(),
+                staff_id,
+                staff_group_id: Uuid::nil(),
+                date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+                shift,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .collect();
+        RosterGrid::new(
+            &assignments,
+            &HashMap::from([(staff_id, "Jane, Doe".to_string())]),
+        )
+    }
+
+    #[test]
+    fn test_csv_layout() {
+        let csv = String::from_utf8(write_csv(&grid()).unwrap()).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "Staff,Staff ID,2024-01-15,2024-01-16,Mornings,Evenings,Days off"
+        );
+        assert_eq!(
+            lines[1],
+            format!("\"Jane, Doe\",{},M,OFF,1,0,1", Uuid::from_u128(1))
+        );
+        assert_eq!(lines[2], "Total mornings,,1,0,1,,");
+        assert_eq!(lines[3], "Total evenings,,0,0,,0,");
+        assert_eq!(lines[4], "Total days off,,0,1,,,1");
+    }
+
+    #[test]
+    fn test_xlsx_is_a_zip_archive() {
+        let xlsx = write_xlsx(&grid()).unwrap();
+        assert!(xlsx.starts_with(b"PK"));
+    }
+}
@@ -0,0 +1,386 @@
+//! Printable rosters: a colour-coded staff × day grid for each week, as HTML
+//! or as a PDF drawn with the standard PDF fonts.
+
+use printpdf::{
+    path::PaintMode, BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Rect,
+    Rgb,
+};
+use shared::{DomainError, DomainResult, ShiftType};
+use std::ops::Range;
+
+use crate::presentation::RosterGrid;
+
+/// How each shift type is shown in the grid and the legend
+struct ShiftStyle {
+    shift: ShiftType,
+    name: &'static str,
+    css_class: &'static str,
+    rgb: (u8, u8, u8),
+}
+
+const SHIFT_STYLES: [ShiftStyle; 3] = [
+    ShiftStyle {
+        shift: ShiftType::Morning,
+        name: "Morning",
+        css_class: "morning",
+        rgb: (0xff, 0xe8, 0xa3),
+    },
+    ShiftStyle {
+        shift: ShiftType::Evening,
+        name: "Evening",
+        css_class: "evening",
+        rgb: (0xb9, 0xd3, 0xf5),
+    },
+    ShiftStyle {
+        shift: ShiftType::DayOff,
+        name: "Day off",
+        css_class: "day-off",
+        rgb: (0xe3, 0xe3, 0xe3),
+    },
+];
+
+fn style(shift: ShiftType) -> &'static ShiftStyle {
+    SHIFT_STYLES
+        .iter()
+        .find(|style| style.shift == shift)
+        .expect("every shift type has a style")
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render the grid as a standalone HTML page with one table per week
+pub fn write_html(title: &str, grid: &RosterGrid) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n<style>\n", escape_html(title)));
+    html.push_str(
+        "body { font-family: Helvetica, Arial, sans-serif; margin: 1.5em; \
+         -webkit-print-color-adjust: exact; print-color-adjust: exact; }\n\
+         table { border-collapse: collapse; margin-bottom: 1.5em; }\n\
+         th, td { border: 1px solid #999; padding: 4px 10px; text-align: center; }\n\
+         th.staff, td.staff { text-align: left; }\n\
+         .legend { list-style: none; padding: 0; }\n\
+         .legend li { display: inline-block; margin-right: 1.5em; }\n\
+         .swatch { display: inline-block; padding: 2px 8px; border: 1px solid #999; }\n\
+         @media print { section { page-break-after: always; } }\n",
+    );
+    for style in &SHIFT_STYLES {
+        let (r, g, b) = style.rgb;
+        html.push_str(&format!(
+            ".{} {{ background: #{:02x}{:02x}{:02x}; }}\n",
+            style.css_class, r, g, b
+        ));
+    }
+    html.push_str("</style>\n</head>\n<body>\n");
+    html.push_str(&format!(
+        "<h1>{}</h1>\n<ul class=\"legend\">\n",
+        escape_html(title)
+    ));
+    for style in &SHIFT_STYLES {
+        html.push_str(&format!(
+            "<li><span class=\"swatch {}\">{}</span> {}</li>\n",
+            style.css_class,
+            RosterGrid::label(style.shift),
+            style.name
+        ));
+    }
+    html.push_str("</ul>\n");
+
+    for week in grid.weeks() {
+        html.push_str(&format!(
+            "<section>\n<h2>Week of {}</h2>\n<table>\n<thead>\n<tr><th class=\"staff\">Staff</th>",
+            grid.dates[week.start].format("%-d %B %Y")
+        ));
+        for date in &grid.dates[week.clone()] {
+            html.push_str(&format!("<th>{}</th>", date.format("%a %-d %b")));
+        }
+        html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        for row in &grid.rows {
+            html.push_str(&format!(
+                "<tr><td class=\"staff\">{}</td>",
+                escape_html(&row.staff_name)
+            ));
+            for shift in &row.shifts[week.clone()] {
+                match shift {
+                    Some(shift) => html.push_str(&format!(
+                        "<td class=\"{}\">{}</td>",
+                        style(*shift).css_class,
+                        RosterGrid::label(*shift)
+                    )),
+                    None => html.push_str("<td></td>"),
+                }
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n</section>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+// A4 landscape, in millimetres
+const PAGE_WIDTH: f32 = 297.0;
+const PAGE_HEIGHT: f32 = 210.0;
+const MARGIN: f32 = 15.0;
+const NAME_COLUMN_WIDTH: f32 = 62.0;
+const ROW_HEIGHT: f32 = 7.0;
+/// Space above the table for the title, legend and column headers
+const TABLE_TOP: f32 = PAGE_HEIGHT - MARGIN - 26.0;
+/// Longest staff name printed before it is cut short
+const MAX_NAME_CHARS: usize = 30;
+
+fn rgb((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb(Rgb::new(
+        r as f32 / 255.0,
+        g as f32 / 255.0,
+        b as f32 / 255.0,
+        None,
+    ))
+}
+
+const BLACK: (u8, u8, u8) = (0, 0, 0);
+const GRID_LINE: (u8, u8, u8) = (0x99, 0x99, 0x99);
+
+/// The standard PDF fonts only cover Latin-1
+fn printable(text: &str, max_chars: usize) -> String {
+    let mut printable: String = text
+        .chars()
+        .map(|c| if (c as u32) < 0x100 { c } else { '?' })
+        .take(max_chars)
+        .collect();
+    if text.chars().count() > max_chars {
+        printable.pop();
+        printable.push('.');
+    }
+    printable
+}
+
+struct Fonts {
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+}
+
+fn cell(layer: &PdfLayerReference, x: f32, y: f32, width: f32, fill: Option<(u8, u8, u8)>) {
+    let rect = Rect::new(Mm(x), Mm(y), Mm(x + width), Mm(y + ROW_HEIGHT));
+    if let Some(fill) = fill {
+        layer.set_fill_color(rgb(fill));
+        layer.add_rect(rect.with_mode(PaintMode::Fill));
+    }
+    layer.set_outline_color(rgb(GRID_LINE));
+    layer.add_rect(rect.with_mode(PaintMode::Stroke));
+}
+
+fn text(layer: &PdfLayerReference, value: &str, size: f32, x: f32, y: f32, font: &IndirectFontRef) {
+    layer.set_fill_color(rgb(BLACK));
+    layer.use_text(value, size, Mm(x), Mm(y), font);
+}
+
+/// Draw one page: the title, the legend and the given staff rows of one week
+fn draw_page(
+    layer: &PdfLayerReference,
+    fonts: &Fonts,
+    title: &str,
+    grid: &RosterGrid,
+    week: Range<usize>,
+    rows: Range<usize>,
+) {
+    let top = PAGE_HEIGHT - MARGIN;
+    text(
+        layer,
+        &printable(
+            &format!(
+                "{} - week of {}",
+                title,
+                grid.dates[week.start].format("%-d %B %Y")
+            ),
+            90,
+        ),
+        14.0,
+        MARGIN,
+        top - 6.0,
+        &fonts.bold,
+    );
+
+    let mut x = MARGIN;
+    for style in &SHIFT_STYLES {
+        cell(layer, x, top - 16.0, 10.0, Some(style.rgb));
+        text(
+            layer,
+            RosterGrid::label(style.shift),
+            8.0,
+            x + 1.5,
+            top - 14.0,
+            &fonts.regular,
+        );
+        text(layer, style.name, 9.0, x + 12.0, top - 14.0, &fonts.regular);
+        x += 40.0;
+    }
+
+    layer.set_outline_thickness(0.3);
+    let day_width = (PAGE_WIDTH - 2.0 * MARGIN - NAME_COLUMN_WIDTH) / 7.0;
+    let mut y = TABLE_TOP;
+    cell(layer, MARGIN, y, NAME_COLUMN_WIDTH, None);
+    text(layer, "Staff", 9.0, MARGIN + 2.0, y + 2.2, &fonts.bold);
+    for (column, date) in grid.dates[week.clone()].iter().enumerate() {
+        let x = MARGIN + NAME_COLUMN_WIDTH + column as f32 * day_width;
+        cell(layer, x, y, day_width, None);
+        text(
+            layer,
+            &date.format("%a %-d %b").to_string(),
+            9.0,
+            x + 2.0,
+            y + 2.2,
+            &fonts.bold,
+        );
+    }
+
+    for row in &grid.rows[rows] {
+        y -= ROW_HEIGHT;
+        cell(layer, MARGIN, y, NAME_COLUMN_WIDTH, None);
+        text(
+            layer,
+            &printable(&row.staff_name, MAX_NAME_CHARS),
+            9.0,
+            MARGIN + 2.0,
+            y + 2.2,
+            &fonts.regular,
+        );
+        for (column, shift) in row.shifts[week.clone()].iter().enumerate() {
+            let x = MARGIN + NAME_COLUMN_WIDTH + column as f32 * day_width;
+            cell(layer, x, y, day_width, shift.map(|shift| style(shift).rgb));
+            if let Some(shift) = shift {
+                text(
+                    layer,
+                    RosterGrid::label(*shift),
+                    9.0,
+                    x + 2.0,
+                    y + 2.2,
+                    &fonts.regular,
+                );
+            }
+        }
+    }
+}
+
+/// Render the grid as a landscape A4 PDF with one page per week, continued
+/// onto further pages when there are more staff than fit on one
+pub fn write_pdf(title: &str, grid: &RosterGrid) -> DomainResult<Vec<u8>> {
+    let to_error = |e: printpdf::Error| DomainError::InternalError(e.to_string());
+    let rows_per_page = ((TABLE_TOP - MARGIN) / ROW_HEIGHT) as usize;
+
+    let mut pages: Vec<(Range<usize>, Range<usize>)> = Vec::new();
+    for week in grid.weeks() {
+        let mut start = 0;
+        loop {
+            let end = (start + rows_per_page).min(grid.rows.len());
+            pages.push((week.clone(), start..end));
+            start = end;
+            if start >= grid.rows.len() {
+                break;
+            }
+        }
+    }
+
+    let (document, first_page, first_layer) =
+        PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Roster");
+    let fonts = Fonts {
+        regular: document
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(to_error)?,
+        bold: document
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(to_error)?,
+    };
+
+    for (index, (week, rows)) in pages.into_iter().enumerate() {
+        let (page, layer) = if index == 0 {
+            (first_page, first_layer)
+        } else {
+            document.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Roster")
+        };
+        let layer = document.get_page(page).get_layer(layer);
+        draw_page(&layer, &fonts, title, grid, week, rows);
+    }
+
+    drop(fonts);
+    document.save_to_bytes().map_err(to_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entities::ShiftAssignment;
+    use chrono::{NaiveDate, Utc};
+    use std::collections::HashMap;
+    use uuid::Uuid;
+
+    fn grid(staff_count: u128) -> RosterGrid {
+        let assignments: Vec<ShiftAssignment> = (1..=staff_count)
+            .flat_map(|staff| {
+                (15..=28).map(move |day| ShiftAssignment {
+                    id: Uuid::new_v4(),
+                    schedule_job_id: Uuid::nil(),
+                    staff_id: Uuid::from_u128(staff),
+                    staff_group_id: Uuid::nil(),
+                    date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+                    shift: [ShiftType::Morning, ShiftType::Evening, ShiftType::DayOff]
+                        [(day as usize) % 3],
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+            })
+            .collect();
+        let names = HashMap::from([(Uuid::from_u128(1), "Tom & Jerry <Ops>".to_string())]);
+        RosterGrid::new(&assignments, &names)
+    }
+
+    #[test]
+    fn test_html_has_a_table_per_week_and_a_legend() {
+        let html = write_html("Roster from 2024-01-15", &grid(2));
+
+        assert_eq!(html.matches("<table>").count(), 2);
+        assert!(html.contains("<h2>Week of 22 January 2024</h2>"));
+        assert!(html.contains("<span class=\"swatch evening\">E</span> Evening"));
+        assert!(html.contains("<td class=\"staff\">Tom &amp; Jerry &lt;Ops&gt;</td>"));
+        assert!(html.contains("<th>Mon 15 Jan</th>"));
+        assert!(html.contains("<td class=\"day-off\">OFF</td>"));
+    }
+
+    #[test]
+    fn test_pdf_has_a_page_per_week_and_overflow_page() {
+        let pdf = write_pdf("Roster", &grid(2)).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        let pages = |pdf: &[u8]| {
+            printpdf::lopdf::Document::load_mem(pdf)
+                .unwrap()
+                .get_pages()
+                .len()
+        };
+        assert_eq!(pages(&pdf), 2);
+
+        // Too many staff for one page continue on another page of the same week
+        let pdf = write_pdf("Roster", &grid(40)).unwrap();
+        assert_eq!(pages(&pdf), 4);
+    }
+
+    #[test]
+    fn test_printable_names_fit_the_standard_fonts() {
+        assert_eq!(printable("Zoë Łukasz", 30), "Zoë ?ukasz");
+        assert_eq!(printable("abcdef", 4), "abc.");
+    }
+}
@@ -0,0 +1,196 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::domain::entities::{WebhookDelivery, WebhookSubscription};
+use crate::domain::events::{JobEvent, JobEventKind, JobEventPublisher};
+use crate::domain::repositories::WebhookRepository;
+use crate::infrastructure::config::WebhookSettings;
+
+pub const EVENT_HEADER: &str = "X-Webhook-Event";
+pub const DELIVERY_HEADER: &str = "X-Webhook-Delivery";
+pub const TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Sign a payload for a subscription.
+///
+/// The signature is the hex HMAC-SHA256 of `"{timestamp}.{body}"` keyed with the
+/// subscription secret, sent as `sha256=<hex>` so receivers can reject replays
+/// by checking the timestamp as well.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Delivers job events to matching webhook subscriptions.
+///
+/// Each delivery runs in its own task so slow receivers never hold up job
+/// processing. Failed attempts are retried with exponential backoff and every
+/// attempt is written to the delivery log.
+pub struct WebhookDispatcher {
+    webhook_repo: Arc<dyn WebhookRepository>,
+    client: reqwest::Client,
+    settings: WebhookSettings,
+}
+
+impl WebhookDispatcher {
+    pub fn new(webhook_repo: Arc<dyn WebhookRepository>, settings: WebhookSettings) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(settings.request_timeout_secs))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            webhook_repo,
+            client,
+            settings,
+        }
+    }
+}
+
+#[async_trait]
+impl JobEventPublisher for WebhookDispatcher {
+    async fn publish(&self, event: &JobEvent) {
+        // Progress updates are too chatty for webhooks; they are only streamed
+        if !matches!(event.kind, JobEventKind::StatusChanged { .. }) {
+            return;
+        }
+
+        let subscriptions = match self
+            .webhook_repo
+            .find_active_for_group(event.staff_group_id)
+            .await
+        {
+            Ok(subscriptions) => subscriptions,
+            Err(e) => {
+                tracing::error!("Failed to load webhook subscriptions: {:?}", e);
+                return;
+            }
+        };
+
+        if subscriptions.is_empty() {
+            return;
+        }
+
+        let payload = match serde_json::to_string(event) {
+            Ok(payload) => payload,
+            Err(e) => {
+                tracing::error!("Failed to serialize job event: {:?}", e);
+                return;
+            }
+        };
+
+        for subscription in subscriptions {
+            let delivery = Delivery {
+                webhook_repo: self.webhook_repo.clone(),
+                client: self.client.clone(),
+                settings: self.settings.clone(),
+                subscription,
+                delivery_id: Uuid::new_v4(),
+                schedule_job_id: event.schedule_id,
+                event_type: event.event_type(),
+                payload: payload.clone(),
+            };
+            tokio::spawn(delivery.run());
+        }
+    }
+}
+
+/// A single event on its way to a single subscription
+struct Delivery {
+    webhook_repo: Arc<dyn WebhookRepository>,
+    client: reqwest::Client,
+    settings: WebhookSettings,
+    subscription: WebhookSubscription,
+    delivery_id: Uuid,
+    schedule_job_id: Uuid,
+    event_type: &'static str,
+    payload: String,
+}
+
+impl Delivery {
+    async fn run(self) {
+        let max_attempts = self.settings.max_attempts.max(1);
+        let mut backoff = Duration::from_millis(self.settings.initial_backoff_ms);
+
+        for attempt in 1..=max_attempts {
+            let (status_code, error_message) = self.attempt().await;
+            let success = error_message.is_none();
+
+            let record = WebhookDelivery {
+                id: Uuid::new_v4(),
+                delivery_id: self.delivery_id,
+                subscription_id: self.subscription.id,
+                schedule_job_id: self.schedule_job_id,
+                event_type: self.event_type.to_string(),
+                payload: self.payload.clone(),
+                attempt: attempt as i32,
+                status_code,
+                success,
+                error_message: error_message.clone(),
+                created_at: Utc::now(),
+            };
+            if let Err(e) = self.webhook_repo.record_delivery(record).await {
+                tracing::error!("Failed to record webhook delivery: {:?}", e);
+            }
+
+            if success {
+                return;
+            }
+
+            tracing::warn!(
+                "Webhook delivery {} to {} failed (attempt {}/{}): {}",
+                self.delivery_id,
+                self.subscription.url,
+                attempt,
+                max_attempts,
+                error_message.unwrap_or_default()
+            );
+
+            if attempt < max_attempts {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+    }
+
+    /// Send the payload once, returning the response status and an error if
+    /// the attempt did not succeed
+    async fn attempt(&self) -> (Option<i32>, Option<String>) {
+        let timestamp = Utc::now().timestamp();
+        let signature = sign(&self.subscription.secret, timestamp, &self.payload);
+
+        let result = self
+            .client
+            .post(&self.subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, self.event_type)
+            .header(DELIVERY_HEADER, self.delivery_id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, signature)
+            .body(self.payload.clone())
+            .send()
+            .await;
+
+        match result {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    (Some(status.as_u16() as i32), None)
+                } else {
+                    (
+                        Some(status.as_u16() as i32),
+                        Some(format!("Receiver responded with status {}", status)),
+                    )
+                }
+            }
+            Err(e) => (None, Some(e.to_string())),
+        }
+    }
+}
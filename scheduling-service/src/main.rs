@@ -8,6 +8,7 @@ use std::sync::Arc;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use api::AppState;
+use domain::events::{CompositeJobEventPublisher, JobEventPublisher};
 use domain::rules::{
     MaxDaysOffRule, MinDaysOffRule, NoMorningAfterEveningRule, Rule, ShiftBalanceRule,
 };
@@ -17,8 +18,11 @@ use infrastructure::{
     database,
     http_client::DataServiceClient,
     redis,
-    repositories::{PostgresScheduleJobRepository, PostgresShiftAssignmentRepository},
-    JobProcessor,
+    repositories::{
+        PostgresHolidayCalendarRepository, PostgresScheduleJobRepository,
+        PostgresShiftAssignmentRepository, PostgresUnitOfWork, PostgresWebhookRepository,
+    },
+    JobProcessor, RedisJobEventPublisher, RedisJobEventSubscriber, WebhookDispatcher,
 };
 
 #[tokio::main]
@@ -44,12 +48,27 @@ async fn main() -> Result<()> {
     tracing::info!("Database migrations completed");
 
     let redis_pool = redis::create_redis_pool(&settings.redis.url).await?;
+    let redis_client = ::redis::Client::open(settings.redis.url.as_str())?;
     tracing::info!("Redis connection established");
 
     let job_repo = Arc::new(PostgresScheduleJobRepository::new(db_pool.clone()));
     let assignment_repo = Arc::new(PostgresShiftAssignmentRepository::new(db_pool.clone()));
+    let webhook_repo = Arc::new(PostgresWebhookRepository::new(db_pool.clone()));
+    let holiday_repo = Arc::new(PostgresHolidayCalendarRepository::new(db_pool.clone()));
+    let unit_of_work = Arc::new(PostgresUnitOfWork::new(db_pool.clone()));
     tracing::info!("Repositories initialized");
 
+    let event_publisher: Arc<dyn JobEventPublisher> =
+        Arc::new(CompositeJobEventPublisher::new(vec![
+            Arc::new(WebhookDispatcher::new(
+                webhook_repo.clone(),
+                settings.webhooks.clone(),
+            )),
+            Arc::new(RedisJobEventPublisher::new(redis_pool.clone())),
+        ]));
+    let event_subscriber = Arc::new(RedisJobEventSubscriber::new(redis_client));
+    tracing::info!("Job event publishers initialized");
+
     let data_service_url = format!(
         "http://{}:{}",
         settings.data_service.host, settings.data_service.port
@@ -71,19 +90,41 @@ async fn main() -> Result<()> {
     ];
     tracing::info!("Scheduling rules configured");
 
-    let scheduler = Arc::new(ScheduleGenerator::new(rules));
+    let scheduler = Arc::new(
+        ScheduleGenerator::new(rules)
+            .with_fairness_tolerance(settings.scheduling.fairness_tolerance),
+    );
 
-    let processor = Arc::new(JobProcessor::new(
-        job_repo.clone(),
-        assignment_repo.clone(),
-        data_service_client,
-        scheduler,
-    ));
+    let processor = Arc::new(
+        JobProcessor::new(
+            job_repo.clone(),
+            unit_of_work,
+            data_service_client.clone(),
+            scheduler,
+        )
+        .with_event_publisher(event_publisher.clone())
+        .with_assignment_history(
+            assignment_repo.clone(),
+            settings.scheduling.fairness_lookback_days,
+        )
+        .with_holiday_calendars(holiday_repo.clone()),
+    );
 
     let (schedule_sender, processor_handle) = processor.start();
     tracing::info!("Background schedule processor started");
 
-    let app_state = AppState::new(job_repo, assignment_repo, schedule_sender, redis_pool);
+    let app_state = AppState::new(
+        job_repo,
+        assignment_repo,
+        webhook_repo,
+        holiday_repo,
+        data_service_client,
+        event_publisher,
+        event_subscriber,
+        schedule_sender,
+        redis_pool,
+    )
+    .with_calendar_settings(settings.calendar.clone());
 
     let app = api::create_router(app_state);
 
@@ -0,0 +1,67 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::{Holiday, HolidayCalendar, HolidaySource};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HolidayCalendarSerializer {
+    pub id: Uuid,
+    pub name: String,
+    pub staff_group_id: Option<Uuid>,
+    pub coverage_percent: i32,
+    pub paid_leave: bool,
+    pub premium_weight: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<HolidayCalendar> for HolidayCalendarSerializer {
+    fn from(calendar: HolidayCalendar) -> Self {
+        Self {
+            id: calendar.id,
+            name: calendar.name,
+            staff_group_id: calendar.staff_group_id,
+            coverage_percent: calendar.coverage_percent,
+            paid_leave: calendar.paid_leave,
+            premium_weight: calendar.premium_weight,
+            created_at: calendar.created_at,
+            updated_at: calendar.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HolidaySerializer {
+    pub id: Uuid,
+    pub calendar_id: Uuid,
+    pub date: NaiveDate,
+    pub name: String,
+    pub source: HolidaySource,
+    pub external_uid: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Holiday> for HolidaySerializer {
+    fn from(holiday: Holiday) -> Self {
+        Self {
+            id: holiday.id,
+            calendar_id: holiday.calendar_id,
+            date: holiday.date,
+            name: holiday.name,
+            source: holiday.source,
+            external_uid: holiday.external_uid,
+            created_at: holiday.created_at,
+        }
+    }
+}
+
+/// Outcome of importing an iCalendar file
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HolidayImportSerializer {
+    /// Holidays created or updated, ordered by date
+    pub imported: Vec<HolidaySerializer>,
+    /// Events that were ignored, with the reason
+    pub skipped: Vec<String>,
+}
@@ -1,6 +1,14 @@
+pub mod holiday_serializer;
+pub mod roster_grid;
 pub mod schedule_serializer;
+pub mod webhook_serializer;
 
+pub use holiday_serializer::{
+    HolidayCalendarSerializer, HolidayImportSerializer, HolidaySerializer,
+};
+pub use roster_grid::{RosterGrid, ShiftTotals};
 pub use schedule_serializer::{
     ScheduleJobSerializer, ScheduleResultSerializer, ScheduleStatusSerializer,
     ShiftAssignmentSerializer,
 };
+pub use webhook_serializer::{WebhookDeliverySerializer, WebhookSubscriptionSerializer};
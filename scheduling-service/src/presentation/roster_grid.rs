@@ -0,0 +1,186 @@
+use chrono::{Datelike, NaiveDate};
+use shared::ShiftType;
+use std::collections::HashMap;
+use std::ops::Range;
+use uuid::Uuid;
+
+use crate::domain::entities::ShiftAssignment;
+
+/// Shifts of one kind counted over a row or a column of the grid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShiftTotals {
+    pub mornings: usize,
+    pub evenings: usize,
+    pub days_off: usize,
+}
+
+impl ShiftTotals {
+    fn add(&mut self, shift: ShiftType) {
+        match shift {
+            ShiftType::Morning => self.mornings += 1,
+            ShiftType::Evening => self.evenings += 1,
+            ShiftType::DayOff => self.days_off += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RosterRow {
+    pub staff_id: Uuid,
+    pub staff_name: String,
+    /// Shift on each date of the grid, `None` where the staff member has none
+    pub shifts: Vec<Option<ShiftType>>,
+    pub totals: ShiftTotals,
+}
+
+/// A completed schedule laid out as a staff × date grid
+#[derive(Debug, Clone)]
+pub struct RosterGrid {
+    pub dates: Vec<NaiveDate>,
+    /// One row per staff member, sorted by name
+    pub rows: Vec<RosterRow>,
+    /// Totals of each date, in the order of `dates`
+    pub day_totals: Vec<ShiftTotals>,
+    pub totals: ShiftTotals,
+}
+
+impl RosterGrid {
+    /// Build the grid from a schedule's assignments. Staff missing from
+    /// `names`, for example because they have since left their group, are
+    /// listed under their id.
+    pub fn new(assignments: &[ShiftAssignment], names: &HashMap<Uuid, String>) -> Self {
+        let mut dates: Vec<NaiveDate> = assignments.iter().map(|a| a.date).collect();
+        dates.sort_unstable();
+        dates.dedup();
+        let date_index: HashMap<NaiveDate, usize> = dates
+            .iter()
+            .enumerate()
+            .map(|(index, date)| (*date, index))
+            .collect();
+
+        let mut rows: Vec<RosterRow> = Vec::new();
+        let mut row_index: HashMap<Uuid, usize> = HashMap::new();
+        let mut day_totals = vec![ShiftTotals::default(); dates.len()];
+        let mut totals = ShiftTotals::default();
+
+        for assignment in assignments {
+            let row = *row_index.entry(assignment.staff_id).or_insert_with(|| {
+                rows.push(RosterRow {
+                    staff_id: assignment.staff_id,
+                    staff_name: names
+                        .get(&assignment.staff_id)
+                        .cloned()
+                        .unwrap_or_else(|| assignment.staff_id.to_string()),
+                    shifts: vec![None; dates.len()],
+                    totals: ShiftTotals::default(),
+                });
+                rows.len() - 1
+            });
+            let day = date_index[&assignment.date];
+
+            rows[row].shifts[day] = Some(assignment.shift);
+            rows[row].totals.add(assignment.shift);
+            day_totals[day].add(assignment.shift);
+            totals.add(assignment.shift);
+        }
+
+        rows.sort_by(|a, b| {
+            a.staff_name
+                .cmp(&b.staff_name)
+                .then(a.staff_id.cmp(&b.staff_id))
+        });
+
+        Self {
+            dates,
+            rows,
+            day_totals,
+            totals,
+        }
+    }
+
+    /// Ranges of `dates` that fall in the same Monday-to-Sunday week
+    pub fn weeks(&self) -> Vec<Range<usize>> {
+        let mut weeks: Vec<Range<usize>> = Vec::new();
+        for (index, date) in self.dates.iter().enumerate() {
+            let week = date.iso_week();
+            match weeks.last_mut() {
+                Some(range) if self.dates[range.start].iso_week() == week => range.end = index + 1,
+                _ => weeks.push(index..index + 1),
+            }
+        }
+        weeks
+    }
+
+    /// Short label of a shift as shown in grid cells
+    pub fn label(shift: ShiftType) -> &'static str {
+        match shift {
+            ShiftType::Morning => "M",
+            ShiftType::Evening => "E",
+            ShiftType::DayOff => "OFF",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn assignment(staff_id: Uuid, day: u32, shift: ShiftType) -> ShiftAssignment {
+        ShiftAssignment {
+            id: Uuid::new_v4(),
+            schedule_job_id: Uuid::nil(),
+            staff_id,
+            staff_group_id: Uuid::nil(),
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            shift,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_grid_rows_are_sorted_by_name_with_totals() {
+        let (zoe, adam, gone) = (Uuid::from_u128(1), Uuid::from_u128(2), Uuid::from_u128(3));
+        let names = HashMap::from([(zoe, "Zoe".to_string()), (adam, "Adam".to_string())]);
+        let assignments = vec![
+            assignment(zoe, 16, ShiftType::Evening),
+            assignment(zoe, 15, ShiftType::Morning),
+            assignment(adam, 15, ShiftType::Evening),
+            assignment(gone, 16, ShiftType::DayOff),
+        ];
+
+        let grid = RosterGrid::new(&assignments, &names);
+
+        assert_eq!(grid.dates.len(), 2);
+        let names: Vec<&str> = grid.rows.iter().map(|r| r.staff_name.as_str()).collect();
+        assert_eq!(names, vec![gone.to_string().as_str(), "Adam", "Zoe"]);
+        assert_eq!(
+            grid.rows[2].shifts,
+            vec![Some(ShiftType::Morning), Some(ShiftType::Evening)]
+        );
+        assert_eq!(grid.rows[1].shifts, vec![Some(ShiftType::Evening), None]);
+        assert_eq!(
+            grid.day_totals[1],
+            ShiftTotals {
+                mornings: 0,
+                evenings: 1,
+                days_off: 1,
+            }
+        );
+        assert_eq!(grid.totals.evenings, 2);
+    }
+
+    #[test]
+    fn test_weeks_split_on_mondays() {
+        let staff_id = Uuid::from_u128(1);
+        // Saturday 13th to Tuesday 23rd January
+        let assignments: Vec<ShiftAssignment> = (13..=23)
+            .map(|day| assignment(staff_id, day, ShiftType::Morning))
+            .collect();
+
+        let grid = RosterGrid::new(&assignments, &HashMap::new());
+
+        assert_eq!(grid.weeks(), vec![0..2, 2..9, 9..11]);
+    }
+}
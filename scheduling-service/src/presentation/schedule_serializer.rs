@@ -4,7 +4,7 @@ use shared::JobStatus;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::domain::entities::{ScheduleJob, ShiftAssignment};
+use crate::domain::entities::{CoverageTarget, ScheduleJob, ShiftAssignment};
 
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ScheduleJobSerializer {
@@ -31,6 +31,11 @@ pub struct ScheduleStatusSerializer {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
+    pub seed: i64,
+    /// Groups scheduled together with `staff_group_id`
+    pub additional_group_ids: Vec<Uuid>,
+    /// Minimum daily staffing requested for subgroups
+    pub coverage_targets: Vec<CoverageTarget>,
 }
 
 impl From<ScheduleJob> for ScheduleStatusSerializer {
@@ -44,6 +49,9 @@ impl From<ScheduleJob> for ScheduleStatusSerializer {
             created_at: job.created_at,
             updated_at: job.updated_at,
             completed_at: job.completed_at,
+            seed: job.seed,
+            additional_group_ids: job.additional_group_ids,
+            coverage_targets: job.coverage_targets,
         }
     }
 }
@@ -51,6 +59,8 @@ impl From<ScheduleJob> for ScheduleStatusSerializer {
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ShiftAssignmentSerializer {
     pub staff_id: Uuid,
+    /// Group the shift is worked for
+    pub staff_group_id: Uuid,
     pub date: NaiveDate,
     pub shift: shared::ShiftType,
 }
@@ -59,6 +69,7 @@ impl From<ShiftAssignment> for ShiftAssignmentSerializer {
     fn from(assignment: ShiftAssignment) -> Self {
         Self {
             staff_id: assignment.staff_id,
+            staff_group_id: assignment.staff_group_id,
             date: assignment.date,
             shift: assignment.shift,
         }
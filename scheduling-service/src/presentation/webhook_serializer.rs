@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::domain::entities::{WebhookDelivery, WebhookSubscription};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSubscriptionSerializer {
+    pub id: Uuid,
+    pub url: String,
+    pub staff_group_id: Option<Uuid>,
+    pub active: bool,
+    /// Only returned when the subscription is created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl WebhookSubscriptionSerializer {
+    /// Serializer for the creation response, which is the only place the secret is shown
+    pub fn with_secret(subscription: WebhookSubscription) -> Self {
+        let secret = subscription.secret.clone();
+        Self {
+            secret: Some(secret),
+            ..Self::from(subscription)
+        }
+    }
+}
+
+impl From<WebhookSubscription> for WebhookSubscriptionSerializer {
+    fn from(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id,
+            url: subscription.url,
+            staff_group_id: subscription.staff_group_id,
+            active: subscription.active,
+            secret: None,
+            created_at: subscription.created_at,
+            updated_at: subscription.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliverySerializer {
+    pub id: Uuid,
+    pub delivery_id: Uuid,
+    pub schedule_id: Uuid,
+    pub event_type: String,
+    pub attempt: i32,
+    pub status_code: Option<i32>,
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliverySerializer {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id,
+            delivery_id: delivery.delivery_id,
+            schedule_id: delivery.schedule_job_id,
+            event_type: delivery.event_type,
+            attempt: delivery.attempt,
+            status_code: delivery.status_code,
+            success: delivery.success,
+            error_message: delivery.error_message,
+            created_at: delivery.created_at,
+        }
+    }
+}
@@ -3,13 +3,26 @@ use chrono::{NaiveDate, Utc};
 use mockall::mock;
 use scheduling_service::api::requests::schedule_request::ScheduleJobRequest;
 use scheduling_service::api::AppState;
-use scheduling_service::domain::entities::{ScheduleJob, ShiftAssignment};
-use scheduling_service::domain::repositories::{ScheduleJobRepository, ShiftAssignmentRepository};
-use scheduling_service::infrastructure::http_client::{DataServiceClientTrait, StaffResponse};
-use shared::{create_redis_pool, DomainError, DomainResult, JobStatus, ShiftType, StaffStatus};
+use scheduling_service::domain::entities::{
+    Holiday, HolidayCalendar, ScheduleJob, ShiftAssignment, WebhookDelivery, WebhookSubscription,
+};
+use scheduling_service::domain::events::{
+    JobEvent, JobEventPublisher, JobEventStream, JobEventSubscriber,
+};
+use scheduling_service::domain::repositories::{
+    ExclusiveCreate, HolidayCalendarRepository, JobFilter, ScheduleJobRepository,
+    ShiftAssignmentRepository, Transaction, UnitOfWork, WebhookRepository,
+};
+use scheduling_service::infrastructure::http_client::{
+    DataServiceClientTrait, ResolvedGroupResponse, StaffResponse,
+};
+use shared::{
+    create_redis_pool, DomainError, DomainResult, JobStatus, PaginationParams, ShiftType,
+    StaffStatus,
+};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use uuid::Uuid;
 
 // Generate mock for DataServiceClientTrait using mockall (for HTTP calls to data-service)
@@ -18,7 +31,8 @@ mock! {
 
     #[async_trait]
     impl DataServiceClientTrait for DataServiceClient {
-        async fn get_group_members(&self, group_id: Uuid) -> DomainResult<Vec<StaffResponse>>;
+        async fn get_group_members(&self, group_id: Uuid, from: NaiveDate, to: NaiveDate) -> DomainResult<Vec<StaffResponse>>;
+        async fn get_resolved_groups(&self, group_id: Uuid, from: NaiveDate, to: NaiveDate) -> DomainResult<Vec<ResolvedGroupResponse>>;
     }
 }
 
@@ -43,6 +57,28 @@ impl MockScheduleJobRepository {
         }
         repo
     }
+
+    /// Mark a job completed; used by `MockUnitOfWork` on commit
+    pub fn complete(&self, id: Uuid) -> DomainResult<()> {
+        let mut jobs = self.jobs.write().unwrap();
+        if let Some(job) = jobs.get_mut(&id) {
+            job.status = JobStatus::Completed;
+            job.completed_at = Some(Utc::now());
+            job.updated_at = Utc::now();
+            Ok(())
+        } else {
+            Err(DomainError::NotFound(format!("Job {} not found", id)))
+        }
+    }
+
+    /// Cancel a job the way superseding does; usable from synchronous mock
+    /// callbacks to cancel a job while it is being processed
+    pub fn cancel(&self, id: Uuid) {
+        if let Some(job) = self.jobs.write().unwrap().get_mut(&id) {
+            job.status = JobStatus::Cancelled;
+            job.updated_at = Utc::now();
+        }
+    }
 }
 
 #[async_trait]
@@ -56,44 +92,100 @@ impl ScheduleJobRepository for MockScheduleJobRepository {
         Ok(self.jobs.read().unwrap().get(&id).cloned())
     }
 
-    async fn update_status(
+    async fn find_by_idempotency_key(&self, key: &str) -> DomainResult<Option<ScheduleJob>> {
+        Ok(self
+            .jobs
+            .read()
+            .unwrap()
+            .values()
+            .find(|j| j.idempotency_key.as_deref() == Some(key))
+            .cloned())
+    }
+
+    async fn create_exclusive(
         &self,
-        id: Uuid,
-        status: JobStatus,
-        error_message: Option<String>,
-    ) -> DomainResult<()> {
+        job: ScheduleJob,
+        supersede: bool,
+    ) -> DomainResult<ExclusiveCreate> {
         let mut jobs = self.jobs.write().unwrap();
-        if let Some(job) = jobs.get_mut(&id) {
-            job.status = status;
-            job.error_message = error_message;
-            job.updated_at = Utc::now();
-            Ok(())
-        } else {
-            Err(DomainError::NotFound(format!("Job {} not found", id)))
+        let mut active: Vec<&mut ScheduleJob> = jobs
+            .values_mut()
+            .filter(|j| {
+                j.staff_group_id == job.staff_group_id
+                    && j.period_begin_date == job.period_begin_date
+                    && matches!(j.status, JobStatus::Pending | JobStatus::Processing)
+            })
+            .collect();
+        active.sort_by_key(|j| j.created_at);
+
+        if !supersede {
+            if let Some(active_job) = active.first() {
+                return Ok(ExclusiveCreate::Conflict((*active_job).clone()));
+            }
         }
+
+        let superseded = active
+            .into_iter()
+            .map(|active_job| {
+                active_job.status = JobStatus::Cancelled;
+                active_job.error_message = Some(format!("Superseded by job {}", job.id));
+                active_job.updated_at = Utc::now();
+                active_job.clone()
+            })
+            .collect();
+        jobs.insert(job.id, job.clone());
+
+        Ok(ExclusiveCreate::Created { job, superseded })
+    }
+
+    async fn list(
+        &self,
+        filter: &JobFilter,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<ScheduleJob>, u64)> {
+        let jobs = self.jobs.read().unwrap();
+        let mut filtered: Vec<ScheduleJob> = jobs
+            .values()
+            .filter(|j| filter.staff_group_id.is_none_or(|g| j.staff_group_id == g))
+            .filter(|j| filter.status.as_ref().is_none_or(|s| &j.status == s))
+            .filter(|j| filter.period_from.is_none_or(|d| j.period_begin_date >= d))
+            .filter(|j| filter.period_to.is_none_or(|d| j.period_begin_date <= d))
+            .cloned()
+            .collect();
+        filtered.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+
+        let total = filtered.len() as u64;
+        let offset = (params.page - 1) * params.page_size;
+        let paginated: Vec<ScheduleJob> = filtered
+            .into_iter()
+            .skip(offset as usize)
+            .take(params.page_size as usize)
+            .collect();
+        Ok((paginated, total))
     }
 
-    async fn mark_completed(&self, id: Uuid) -> DomainResult<()> {
+    async fn mark_processing(&self, id: Uuid) -> DomainResult<bool> {
         let mut jobs = self.jobs.write().unwrap();
-        if let Some(job) = jobs.get_mut(&id) {
-            job.status = JobStatus::Completed;
-            job.completed_at = Some(Utc::now());
-            job.updated_at = Utc::now();
-            Ok(())
-        } else {
-            Err(DomainError::NotFound(format!("Job {} not found", id)))
+        match jobs.get_mut(&id) {
+            Some(job) if matches!(job.status, JobStatus::Pending | JobStatus::Processing) => {
+                job.status = JobStatus::Processing;
+                job.updated_at = Utc::now();
+                Ok(true)
+            }
+            _ => Ok(false),
         }
     }
 
-    async fn mark_failed(&self, id: Uuid, error_message: String) -> DomainResult<()> {
+    async fn mark_failed(&self, id: Uuid, error_message: String) -> DomainResult<bool> {
         let mut jobs = self.jobs.write().unwrap();
-        if let Some(job) = jobs.get_mut(&id) {
-            job.status = JobStatus::Failed;
-            job.error_message = Some(error_message);
-            job.updated_at = Utc::now();
-            Ok(())
-        } else {
-            Err(DomainError::NotFound(format!("Job {} not found", id)))
+        match jobs.get_mut(&id) {
+            Some(job) if matches!(job.status, JobStatus::Pending | JobStatus::Processing) => {
+                job.status = JobStatus::Failed;
+                job.error_message = Some(error_message);
+                job.updated_at = Utc::now();
+                Ok(true)
+            }
+            _ => Ok(false),
         }
     }
 }
@@ -117,16 +209,118 @@ impl MockShiftAssignmentRepository {
         }
         repo
     }
+
+    /// Replace the assignments of a job; used by `MockUnitOfWork` on commit
+    pub fn replace_for_job(&self, job_id: Uuid, new_assignments: Vec<ShiftAssignment>) {
+        let mut assignments = self.assignments.write().unwrap();
+        assignments.retain(|a| a.schedule_job_id != job_id);
+        assignments.extend(new_assignments);
+    }
+}
+
+/// Manual mock implementation for UnitOfWork. Writes are buffered in the
+/// transaction and only applied to the mock repositories on commit.
+pub struct MockUnitOfWork {
+    job_repo: Arc<MockScheduleJobRepository>,
+    assignment_repo: Arc<MockShiftAssignmentRepository>,
+    fail_on_mark_completed: bool,
+}
+
+impl MockUnitOfWork {
+    pub fn new(
+        job_repo: Arc<MockScheduleJobRepository>,
+        assignment_repo: Arc<MockShiftAssignmentRepository>,
+    ) -> Self {
+        Self {
+            job_repo,
+            assignment_repo,
+            fail_on_mark_completed: false,
+        }
+    }
+
+    /// Make every transaction fail after the assignments were written
+    pub fn failing_on_mark_completed(mut self) -> Self {
+        self.fail_on_mark_completed = true;
+        self
+    }
 }
 
 #[async_trait]
-impl ShiftAssignmentRepository for MockShiftAssignmentRepository {
-    async fn create_batch(&self, assignments: Vec<ShiftAssignment>) -> DomainResult<()> {
-        let mut current = self.assignments.write().unwrap();
-        current.extend(assignments);
+impl UnitOfWork for MockUnitOfWork {
+    async fn begin(&self) -> DomainResult<Box<dyn Transaction>> {
+        Ok(Box::new(MockTransaction {
+            job_repo: self.job_repo.clone(),
+            assignment_repo: self.assignment_repo.clone(),
+            fail_on_mark_completed: self.fail_on_mark_completed,
+            replaced_job: None,
+            assignments: Vec::new(),
+            completed_job: None,
+        }))
+    }
+}
+
+pub struct MockTransaction {
+    job_repo: Arc<MockScheduleJobRepository>,
+    assignment_repo: Arc<MockShiftAssignmentRepository>,
+    fail_on_mark_completed: bool,
+    replaced_job: Option<Uuid>,
+    assignments: Vec<ShiftAssignment>,
+    completed_job: Option<Uuid>,
+}
+
+#[async_trait]
+impl Transaction for MockTransaction {
+    async fn delete_assignments_for_job(&mut self, job_id: Uuid) -> DomainResult<u64> {
+        self.replaced_job = Some(job_id);
+        let existing = self.assignment_repo.find_by_job_id(job_id).await?;
+        Ok(existing.len() as u64)
+    }
+
+    async fn create_assignments(&mut self, assignments: &[ShiftAssignment]) -> DomainResult<()> {
+        self.assignments.extend_from_slice(assignments);
         Ok(())
     }
 
+    async fn mark_completed(&mut self, job_id: Uuid) -> DomainResult<bool> {
+        if self.fail_on_mark_completed {
+            return Err(DomainError::DatabaseError(
+                "simulated failure while completing job".to_string(),
+            ));
+        }
+        let processing = self
+            .job_repo
+            .find_by_id(job_id)
+            .await?
+            .is_some_and(|job| job.status == JobStatus::Processing);
+        if processing {
+            self.completed_job = Some(job_id);
+        }
+        Ok(processing)
+    }
+
+    async fn commit(self: Box<Self>) -> DomainResult<()> {
+        let mut by_job: HashMap<Uuid, Vec<ShiftAssignment>> = HashMap::new();
+        if let Some(job_id) = self.replaced_job {
+            by_job.entry(job_id).or_default();
+        }
+        for assignment in self.assignments {
+            by_job
+                .entry(assignment.schedule_job_id)
+                .or_default()
+                .push(assignment);
+        }
+        for (job_id, assignments) in by_job {
+            self.assignment_repo.replace_for_job(job_id, assignments);
+        }
+        if let Some(job_id) = self.completed_job {
+            self.job_repo.complete(job_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ShiftAssignmentRepository for MockShiftAssignmentRepository {
     async fn find_by_job_id(&self, job_id: Uuid) -> DomainResult<Vec<ShiftAssignment>> {
         let assignments = self.assignments.read().unwrap();
         Ok(assignments
@@ -135,6 +329,382 @@ impl ShiftAssignmentRepository for MockShiftAssignmentRepository {
             .cloned()
             .collect())
     }
+
+    async fn find_history(
+        &self,
+        staff_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<ShiftAssignment>> {
+        // Later assignments for the same staff member and day replace earlier ones
+        let assignments = self.assignments.read().unwrap();
+        let latest: HashMap<(Uuid, NaiveDate), ShiftAssignment> = assignments
+            .iter()
+            .filter(|a| staff_ids.contains(&a.staff_id) && a.date >= from && a.date < to)
+            .map(|a| ((a.staff_id, a.date), a.clone()))
+            .collect();
+        Ok(latest.into_values().collect())
+    }
+
+    async fn find_published_for_staff(&self, staff_id: Uuid) -> DomainResult<Vec<ShiftAssignment>> {
+        // Later assignments for the same day replace earlier ones
+        let assignments = self.assignments.read().unwrap();
+        let latest: HashMap<NaiveDate, ShiftAssignment> = assignments
+            .iter()
+            .filter(|a| a.staff_id == staff_id)
+            .map(|a| (a.date, a.clone()))
+            .collect();
+        let mut published: Vec<ShiftAssignment> = latest.into_values().collect();
+        published.sort_by_key(|a| a.date);
+        Ok(published)
+    }
+}
+
+/// Manual mock implementation for WebhookRepository
+#[derive(Default)]
+pub struct MockWebhookRepository {
+    subscriptions: RwLock<HashMap<Uuid, WebhookSubscription>>,
+    deliveries: RwLock<Vec<WebhookDelivery>>,
+}
+
+impl MockWebhookRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_subscriptions(subscription_list: Vec<WebhookSubscription>) -> Self {
+        let repo = Self::new();
+        {
+            let mut subscriptions = repo.subscriptions.write().unwrap();
+            for subscription in subscription_list {
+                subscriptions.insert(subscription.id, subscription);
+            }
+        }
+        repo
+    }
+
+    /// All recorded delivery attempts, oldest first
+    pub fn deliveries(&self) -> Vec<WebhookDelivery> {
+        self.deliveries.read().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for MockWebhookRepository {
+    async fn create(&self, subscription: WebhookSubscription) -> DomainResult<WebhookSubscription> {
+        self.subscriptions
+            .write()
+            .unwrap()
+            .insert(subscription.id, subscription.clone());
+        Ok(subscription)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<WebhookSubscription>> {
+        Ok(self.subscriptions.read().unwrap().get(&id).cloned())
+    }
+
+    async fn list(
+        &self,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<WebhookSubscription>, u64)> {
+        let mut subscriptions: Vec<WebhookSubscription> = self
+            .subscriptions
+            .read()
+            .unwrap()
+            .values()
+            .cloned()
+            .collect();
+        subscriptions.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+
+        let total = subscriptions.len() as u64;
+        let page = subscriptions
+            .into_iter()
+            .skip(((params.page - 1) * params.page_size) as usize)
+            .take(params.page_size as usize)
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        self.subscriptions
+            .write()
+            .unwrap()
+            .remove(&id)
+            .map(|_| ())
+            .ok_or_else(|| {
+                DomainError::NotFound(format!("Webhook subscription with id {} not found", id))
+            })
+    }
+
+    async fn find_active_for_group(
+        &self,
+        staff_group_id: Uuid,
+    ) -> DomainResult<Vec<WebhookSubscription>> {
+        Ok(self
+            .subscriptions
+            .read()
+            .unwrap()
+            .values()
+            .filter(|s| s.active && s.staff_group_id.is_none_or(|id| id == staff_group_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn record_delivery(&self, delivery: WebhookDelivery) -> DomainResult<()> {
+        self.deliveries.write().unwrap().push(delivery);
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: Uuid,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<WebhookDelivery>, u64)> {
+        let deliveries: Vec<WebhookDelivery> = self
+            .deliveries
+            .read()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|d| d.subscription_id == subscription_id)
+            .cloned()
+            .collect();
+
+        let total = deliveries.len() as u64;
+        let page = deliveries
+            .into_iter()
+            .skip(((params.page - 1) * params.page_size) as usize)
+            .take(params.page_size as usize)
+            .collect();
+        Ok((page, total))
+    }
+}
+
+/// Manual mock implementation for HolidayCalendarRepository
+#[derive(Default)]
+pub struct MockHolidayCalendarRepository {
+    calendars: RwLock<HashMap<Uuid, HolidayCalendar>>,
+    holidays: RwLock<Vec<Holiday>>,
+}
+
+impl MockHolidayCalendarRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_calendar(calendar: HolidayCalendar, holiday_list: Vec<Holiday>) -> Self {
+        let repo = Self::new();
+        repo.calendars
+            .write()
+            .unwrap()
+            .insert(calendar.id, calendar);
+        *repo.holidays.write().unwrap() = holiday_list;
+        repo
+    }
+}
+
+#[async_trait]
+impl HolidayCalendarRepository for MockHolidayCalendarRepository {
+    async fn create(&self, calendar: HolidayCalendar) -> DomainResult<HolidayCalendar> {
+        self.calendars
+            .write()
+            .unwrap()
+            .insert(calendar.id, calendar.clone());
+        Ok(calendar)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> DomainResult<Option<HolidayCalendar>> {
+        Ok(self.calendars.read().unwrap().get(&id).cloned())
+    }
+
+    async fn list(
+        &self,
+        staff_group_id: Option<Uuid>,
+        params: PaginationParams,
+    ) -> DomainResult<(Vec<HolidayCalendar>, u64)> {
+        let mut calendars: Vec<HolidayCalendar> = self
+            .calendars
+            .read()
+            .unwrap()
+            .values()
+            .filter(|c| staff_group_id.is_none() || c.staff_group_id == staff_group_id)
+            .cloned()
+            .collect();
+        calendars.sort_by_key(|c| std::cmp::Reverse(c.created_at));
+
+        let total = calendars.len() as u64;
+        let page = calendars
+            .into_iter()
+            .skip(((params.page - 1) * params.page_size) as usize)
+            .take(params.page_size as usize)
+            .collect();
+        Ok((page, total))
+    }
+
+    async fn delete(&self, id: Uuid) -> DomainResult<()> {
+        self.calendars.write().unwrap().remove(&id).ok_or_else(|| {
+            DomainError::NotFound(format!("Holiday calendar with id {} not found", id))
+        })?;
+        self.holidays
+            .write()
+            .unwrap()
+            .retain(|h| h.calendar_id != id);
+        Ok(())
+    }
+
+    async fn find_for_group(&self, staff_group_id: Uuid) -> DomainResult<Vec<HolidayCalendar>> {
+        Ok(self
+            .calendars
+            .read()
+            .unwrap()
+            .values()
+            .filter(|c| c.staff_group_id.is_none() || c.staff_group_id == Some(staff_group_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn upsert_holidays(&self, new_holidays: Vec<Holiday>) -> DomainResult<Vec<Holiday>> {
+        let mut holidays = self.holidays.write().unwrap();
+        let mut stored: Vec<Holiday> = Vec::new();
+        for holiday in new_holidays {
+            let existing = holidays
+                .iter_mut()
+                .find(|h| h.calendar_id == holiday.calendar_id && h.date == holiday.date);
+            let result = match existing {
+                Some(existing) => {
+                    existing.name = holiday.name;
+                    existing.source = holiday.source;
+                    existing.external_uid = holiday.external_uid;
+                    existing.clone()
+                }
+                None => {
+                    holidays.push(holiday.clone());
+                    holiday
+                }
+            };
+            stored.retain(|h| h.id != result.id);
+            stored.push(result);
+        }
+        stored.sort_by_key(|h| h.date);
+        Ok(stored)
+    }
+
+    async fn list_holidays(
+        &self,
+        calendar_id: Uuid,
+        from: Option<NaiveDate>,
+        to: Option<NaiveDate>,
+    ) -> DomainResult<Vec<Holiday>> {
+        let mut holidays: Vec<Holiday> = self
+            .holidays
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|h| h.calendar_id == calendar_id)
+            .filter(|h| from.is_none_or(|from| h.date >= from))
+            .filter(|h| to.is_none_or(|to| h.date <= to))
+            .cloned()
+            .collect();
+        holidays.sort_by_key(|h| h.date);
+        Ok(holidays)
+    }
+
+    async fn delete_holiday(&self, calendar_id: Uuid, holiday_id: Uuid) -> DomainResult<()> {
+        let mut holidays = self.holidays.write().unwrap();
+        let before = holidays.len();
+        holidays.retain(|h| !(h.id == holiday_id && h.calendar_id == calendar_id));
+        if holidays.len() == before {
+            return Err(DomainError::NotFound(format!(
+                "Holiday with id {} not found in calendar {}",
+                holiday_id, calendar_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn find_holidays(
+        &self,
+        calendar_ids: &[Uuid],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> DomainResult<Vec<Holiday>> {
+        Ok(self
+            .holidays
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|h| calendar_ids.contains(&h.calendar_id) && h.date >= from && h.date < to)
+            .cloned()
+            .collect())
+    }
+}
+
+/// Event publisher that keeps every published event for assertions
+#[derive(Default)]
+pub struct RecordingEventPublisher {
+    events: RwLock<Vec<JobEvent>>,
+}
+
+impl RecordingEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn events(&self) -> Vec<JobEvent> {
+        self.events.read().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl JobEventPublisher for RecordingEventPublisher {
+    async fn publish(&self, event: &JobEvent) {
+        self.events.write().unwrap().push(event.clone());
+    }
+}
+
+/// In-process stand-in for Redis pub/sub: everything published is streamed to
+/// the subscribers of the same job
+pub struct InMemoryEventBus {
+    sender: broadcast::Sender<JobEvent>,
+}
+
+impl InMemoryEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(100);
+        Self { sender }
+    }
+}
+
+impl Default for InMemoryEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl JobEventPublisher for InMemoryEventBus {
+    async fn publish(&self, event: &JobEvent) {
+        let _ = self.sender.send(event.clone());
+    }
+}
+
+#[async_trait]
+impl JobEventSubscriber for InMemoryEventBus {
+    async fn subscribe(&self, schedule_id: Uuid) -> DomainResult<JobEventStream> {
+        let receiver = self.sender.subscribe();
+        let stream = futures::stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.schedule_id == schedule_id => {
+                        return Some((event, receiver))
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        Ok(Box::pin(stream))
+    }
 }
 
 /// Create a sample staff response for testing
@@ -153,6 +723,8 @@ pub fn create_sample_staff_response(
         status: StaffStatus::Active,
         created_at: now,
         updated_at: now,
+        valid_from: None,
+        valid_to: None,
     }
 }
 
@@ -182,7 +754,19 @@ pub async fn create_test_app_state(
         .await
         .expect("Failed to create Redis pool for tests - ensure Redis is running");
 
-    let state = AppState::new(job_repo, assignment_repo, sender, redis_pool);
+    let event_bus = Arc::new(InMemoryEventBus::new());
+
+    let state = AppState::new(
+        job_repo,
+        assignment_repo,
+        Arc::new(MockWebhookRepository::new()),
+        Arc::new(MockHolidayCalendarRepository::new()),
+        Arc::new(MockDataServiceClient::new()),
+        event_bus.clone(),
+        event_bus,
+        sender,
+        redis_pool,
+    );
     (state, receiver)
 }
 
@@ -210,6 +794,11 @@ pub fn create_sample_job(
         created_at: now,
         updated_at: now,
         completed_at: None,
+        idempotency_key: None,
+        request_hash: None,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
     }
 }
 
@@ -229,12 +818,18 @@ pub fn create_completed_job(
         created_at: now,
         updated_at: now,
         completed_at: Some(now),
+        idempotency_key: None,
+        request_hash: None,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
     }
 }
 
 /// Create sample shift assignments for testing
 pub fn create_sample_assignments(
     job_id: Uuid,
+    staff_group_id: Uuid,
     staff_ids: Vec<Uuid>,
     start_date: NaiveDate,
 ) -> Vec<ShiftAssignment> {
@@ -250,9 +845,11 @@ pub fn create_sample_assignments(
                 id: Uuid::new_v4(),
                 schedule_job_id: job_id,
                 staff_id: *staff_id,
+                staff_group_id,
                 date,
                 shift,
                 created_at: now,
+                updated_at: now,
             });
         }
     }
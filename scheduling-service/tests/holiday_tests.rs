@@ -0,0 +1,297 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use chrono::{NaiveDate, Utc};
+use common::{
+    create_sample_job, create_sample_staff_list, create_test_app_state, get_test_monday,
+    MockDataServiceClient, MockHolidayCalendarRepository, MockScheduleJobRepository,
+    MockShiftAssignmentRepository, MockUnitOfWork,
+};
+use scheduling_service::api::create_router;
+use scheduling_service::api::requests::schedule_request::ScheduleJobRequest;
+use scheduling_service::domain::entities::{Holiday, HolidayCalendar, HolidaySource};
+use scheduling_service::domain::repositories::{
+    HolidayCalendarRepository, ShiftAssignmentRepository,
+};
+use scheduling_service::domain::rules::MaxDaysOffRule;
+use scheduling_service::domain::schedule_generator::ScheduleGenerator;
+use scheduling_service::infrastructure::JobProcessor;
+use serde_json::json;
+use shared::{JobStatus, ShiftType};
+use std::sync::Arc;
+use uuid::Uuid;
+
+const CALENDAR_FILE: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Example//Holidays//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:new-year@example.com\r\n\
+DTSTART;VALUE=DATE:20250101\r\n\
+RRULE:FREQ=YEARLY;COUNT=2\r\n\
+SUMMARY:New Year's Day\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:payday@example.com\r\n\
+DTSTART;VALUE=DATE:20250131\r\n\
+RRULE:FREQ=MONTHLY\r\n\
+SUMMARY:Payday\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+async fn setup_test_server(holiday_repo: Arc<MockHolidayCalendarRepository>) -> TestServer {
+    let job_repo = Arc::new(MockScheduleJobRepository::new());
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let (mut state, _receiver) = create_test_app_state(job_repo, assignment_repo).await;
+    state.holiday_repo = holiday_repo;
+
+    TestServer::new(create_router(state)).unwrap()
+}
+
+fn create_calendar(staff_group_id: Option<Uuid>, coverage_percent: i32) -> HolidayCalendar {
+    let now = Utc::now();
+    HolidayCalendar {
+        id: Uuid::new_v4(),
+        name: "Public holidays".to_string(),
+        staff_group_id,
+        coverage_percent,
+        paid_leave: true,
+        premium_weight: 1,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn create_holiday(calendar_id: Uuid, date: NaiveDate) -> Holiday {
+    Holiday {
+        id: Uuid::new_v4(),
+        calendar_id,
+        date,
+        name: "Holiday".to_string(),
+        source: HolidaySource::Manual,
+        external_uid: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_holiday_calendar_crud() {
+    let server = setup_test_server(Arc::new(MockHolidayCalendarRepository::new())).await;
+    let group_id = Uuid::new_v4();
+
+    let response = server
+        .post("/api/v1/holiday-calendars")
+        .json(&json!({
+            "name": "Site holidays",
+            "staff_group_id": group_id,
+            "coverage_percent": 50,
+            "paid_leave": true
+        }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    let calendar_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["coverage_percent"], 50);
+    assert_eq!(body["data"]["premium_weight"], 1);
+
+    let response = server
+        .post(&format!(
+            "/api/v1/holiday-calendars/{}/holidays",
+            calendar_id
+        ))
+        .json(&json!({ "date": "2025-12-25", "name": "Christmas" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    let holiday_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["source"], "MANUAL");
+
+    // A second entry on the same date replaces the first
+    let response = server
+        .post(&format!(
+            "/api/v1/holiday-calendars/{}/holidays",
+            calendar_id
+        ))
+        .json(&json!({ "date": "2025-12-25", "name": "Christmas Day" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["id"], holiday_id);
+    assert_eq!(body["data"]["name"], "Christmas Day");
+
+    let response = server
+        .get(&format!(
+            "/api/v1/holiday-calendars?staff_group_id={}",
+            group_id
+        ))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["total"], 1);
+
+    let response = server
+        .delete(&format!(
+            "/api/v1/holiday-calendars/{}/holidays/{}",
+            calendar_id, holiday_id
+        ))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = server
+        .delete(&format!("/api/v1/holiday-calendars/{}", calendar_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = server
+        .get(&format!("/api/v1/holiday-calendars/{}", calendar_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_holiday_calendar_validates_policy() {
+    let server = setup_test_server(Arc::new(MockHolidayCalendarRepository::new())).await;
+
+    for payload in [
+        json!({ "name": " " }),
+        json!({ "name": "Holidays", "coverage_percent": 101 }),
+        json!({ "name": "Holidays", "premium_weight": 0 }),
+    ] {
+        let response = server
+            .post("/api/v1/holiday-calendars")
+            .json(&payload)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_import_ics_reports_skipped_events() {
+    let calendar = create_calendar(None, 100);
+    let calendar_id = calendar.id;
+    let holiday_repo = Arc::new(MockHolidayCalendarRepository::with_calendar(
+        calendar,
+        vec![],
+    ));
+    let server = setup_test_server(holiday_repo).await;
+
+    let response = server
+        .post(&format!("/api/v1/holiday-calendars/{}/import", calendar_id))
+        .text(CALENDAR_FILE)
+        .content_type("text/calendar")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: serde_json::Value = response.json();
+    let imported = body["data"]["imported"].as_array().unwrap();
+    assert_eq!(imported.len(), 2);
+    assert_eq!(imported[0]["date"], "2025-01-01");
+    assert_eq!(imported[1]["date"], "2026-01-01");
+    assert_eq!(imported[0]["source"], "ICS");
+    assert_eq!(imported[0]["external_uid"], "new-year@example.com");
+    assert_eq!(body["data"]["skipped"].as_array().unwrap().len(), 1);
+
+    let response = server
+        .get(&format!(
+            "/api/v1/holiday-calendars/{}/holidays?from=2025-06-01",
+            calendar_id
+        ))
+        .await;
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"].as_array().unwrap().len(), 1);
+
+    let response = server
+        .post(&format!("/api/v1/holiday-calendars/{}/import", calendar_id))
+        .text("not a calendar")
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post(&format!(
+            "/api/v1/holiday-calendars/{}/import",
+            Uuid::new_v4()
+        ))
+        .text(CALENDAR_FILE)
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+/// Holidays of the group's calendars shape the generated schedule; calendars of
+/// other groups are ignored
+#[tokio::test]
+async fn test_job_processor_applies_group_holiday_calendars() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let holiday = monday + chrono::Duration::days(2);
+    let other_groups_holiday = monday + chrono::Duration::days(3);
+
+    let group_calendar = create_calendar(Some(group_id), 0);
+    let other_calendar = create_calendar(Some(Uuid::new_v4()), 0);
+    let holiday_repo = Arc::new(MockHolidayCalendarRepository::with_calendar(
+        group_calendar.clone(),
+        vec![create_holiday(group_calendar.id, holiday)],
+    ));
+    holiday_repo.create(other_calendar.clone()).await.unwrap();
+    holiday_repo
+        .upsert_holidays(vec![create_holiday(
+            other_calendar.id,
+            other_groups_holiday,
+        )])
+        .await
+        .unwrap();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let staff_list = create_sample_staff_list(6);
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| Ok(staff_list.clone()));
+
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
+        job_repo.clone(),
+        assignment_repo.clone(),
+    ));
+    let processor = Arc::new(
+        JobProcessor::new(
+            job_repo.clone(),
+            unit_of_work,
+            Arc::new(mock_client),
+            Arc::new(ScheduleGenerator::new(vec![Arc::new(MaxDaysOffRule::new(
+                2,
+            ))])),
+        )
+        .with_holiday_calendars(holiday_repo),
+    );
+
+    let (sender, _handle) = processor.start();
+    sender
+        .send(ScheduleJobRequest {
+            job_id,
+            staff_group_id: group_id,
+            period_begin_date: monday,
+            seed: 0,
+            additional_group_ids: vec![],
+            coverage_targets: vec![],
+        })
+        .await
+        .unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert_eq!(assignments.len(), 6 * 28);
+
+    let working_on = |date: NaiveDate| {
+        assignments
+            .iter()
+            .filter(|a| a.date == date && a.shift != ShiftType::DayOff)
+            .count()
+    };
+    assert_eq!(working_on(holiday), 0);
+    assert!(working_on(other_groups_holiday) > 0);
+}
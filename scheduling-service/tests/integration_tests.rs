@@ -1,2 +1,6 @@
 mod common;
+mod holiday_tests;
 mod schedule_api_tests;
+mod schedule_events_tests;
+mod schedule_generator_tests;
+mod webhook_tests;
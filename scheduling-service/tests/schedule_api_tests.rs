@@ -4,9 +4,9 @@ mod common;
 use axum::http::StatusCode;
 use axum_test::{TestResponse, TestServer};
 use common::{
-    create_completed_job, create_sample_assignments, create_sample_job, create_test_app_state,
-    get_test_monday, MockScheduleJobRepository, MockShiftAssignmentRepository,
-    TestServerWithReceiver,
+    create_completed_job, create_sample_assignments, create_sample_job, create_sample_staff_list,
+    create_test_app_state, get_test_monday, MockDataServiceClient, MockScheduleJobRepository,
+    MockShiftAssignmentRepository, TestServerWithReceiver,
 };
 use scheduling_service::api::create_router;
 use scheduling_service::domain::entities::{ScheduleJob, ShiftAssignment};
@@ -97,6 +97,142 @@ async fn test_submit_schedule_invalid_date_not_monday() {
     response.assert_status(StatusCode::BAD_REQUEST);
 }
 
+#[tokio::test]
+async fn test_submit_schedule_with_seed_passes_it_to_processor() {
+    let mut test_server = setup_test_server().await;
+
+    let response: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .json(&json!({
+            "staff_group_id": Uuid::new_v4().to_string(),
+            "period_begin_date": get_test_monday().to_string(),
+            "seed": 1234
+        }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+
+    let request = test_server.receiver.try_recv().unwrap();
+    assert_eq!(request.seed, 1234);
+
+    let body: serde_json::Value = response.json();
+    let status: TestResponse = test_server
+        .server
+        .get(&format!(
+            "/api/v1/schedules/{}/status",
+            body["data"]["schedule_id"].as_str().unwrap()
+        ))
+        .await;
+    let status_body: serde_json::Value = status.json();
+    assert_eq!(status_body["data"]["seed"], 1234);
+}
+
+#[tokio::test]
+async fn test_submit_schedule_without_seed_picks_one() {
+    let mut test_server = setup_test_server().await;
+
+    let response: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .json(&json!({
+            "staff_group_id": Uuid::new_v4().to_string(),
+            "period_begin_date": get_test_monday().to_string()
+        }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+
+    let request = test_server.receiver.try_recv().unwrap();
+    assert!((0..1i64 << 53).contains(&request.seed));
+}
+
+#[tokio::test]
+async fn test_submit_schedule_with_additional_groups() {
+    let mut test_server = setup_test_server().await;
+    let group_id = Uuid::new_v4();
+    let other_group_id = Uuid::new_v4();
+
+    let response: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .json(&json!({
+            "staff_group_id": group_id,
+            "period_begin_date": get_test_monday().to_string(),
+            "additional_group_ids": [other_group_id]
+        }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+
+    let request = test_server.receiver.try_recv().unwrap();
+    assert_eq!(request.group_ids(), vec![group_id, other_group_id]);
+
+    let body: serde_json::Value = response.json();
+    let status: TestResponse = test_server
+        .server
+        .get(&format!(
+            "/api/v1/schedules/{}/status",
+            body["data"]["schedule_id"].as_str().unwrap()
+        ))
+        .await;
+    let status_body: serde_json::Value = status.json();
+    assert_eq!(
+        status_body["data"]["additional_group_ids"],
+        json!([other_group_id])
+    );
+
+    for additional_group_ids in [json!([group_id]), json!([other_group_id, other_group_id])] {
+        let response: TestResponse = test_server
+            .server
+            .post("/api/v1/schedules")
+            .json(&json!({
+                "staff_group_id": group_id,
+                "period_begin_date": get_test_monday().to_string(),
+                "additional_group_ids": additional_group_ids
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_submit_schedule_with_coverage_targets() {
+    let mut test_server = setup_test_server().await;
+    let group_id = Uuid::new_v4();
+    let backend_id = Uuid::new_v4();
+    let target = json!({ "group_id": backend_id, "shift": "EVENING", "min_staff": 1 });
+
+    let response: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .json(&json!({
+            "staff_group_id": group_id,
+            "period_begin_date": get_test_monday().to_string(),
+            "coverage_targets": [target]
+        }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+
+    let request = test_server.receiver.try_recv().unwrap();
+    assert_eq!(request.coverage_targets.len(), 1);
+    assert_eq!(request.coverage_targets[0].group_id, backend_id);
+
+    for coverage_targets in [
+        json!([{ "group_id": backend_id, "shift": "DAY_OFF", "min_staff": 1 }]),
+        json!([{ "group_id": backend_id, "shift": "EVENING", "min_staff": 0 }]),
+        json!([target, target]),
+    ] {
+        let response: TestResponse = test_server
+            .server
+            .post("/api/v1/schedules")
+            .json(&json!({
+                "staff_group_id": group_id,
+                "period_begin_date": get_test_monday().to_string(),
+                "coverage_targets": coverage_targets
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+}
+
 #[tokio::test]
 async fn test_get_schedule_status_pending() {
     let job_id = Uuid::new_v4();
@@ -176,7 +312,7 @@ async fn test_get_schedule_result_success() {
     let job = create_completed_job(job_id, group_id, monday);
 
     let staff_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
-    let assignments = create_sample_assignments(job_id, staff_ids, monday);
+    let assignments = create_sample_assignments(job_id, group_id, staff_ids, monday);
 
     let test_server = setup_test_server_with_jobs(vec![job], assignments).await;
 
@@ -240,6 +376,146 @@ async fn test_get_schedule_result_not_found() {
     response.assert_status(StatusCode::NOT_FOUND);
 }
 
+#[tokio::test]
+async fn test_get_schedule_calendar() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let job = create_completed_job(job_id, group_id, monday);
+    let pending_id = Uuid::new_v4();
+    let pending = create_sample_job(pending_id, group_id, monday, JobStatus::Pending);
+
+    let staff_ids = vec![Uuid::new_v4(), Uuid::new_v4()];
+    let assignments = create_sample_assignments(job_id, group_id, staff_ids, monday);
+
+    let test_server = setup_test_server_with_jobs(vec![job, pending], assignments).await;
+
+    let response: TestResponse = test_server
+        .server
+        .get(&format!("/api/v1/schedules/{}/calendar.ics", job_id))
+        .await;
+
+    response.assert_status_ok();
+    assert_eq!(
+        response.header("content-type"),
+        "text/calendar; charset=utf-8"
+    );
+    let body = response.text();
+    assert!(body.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(body.matches("BEGIN:VEVENT").count(), 2 * 7);
+
+    let response: TestResponse = test_server
+        .server
+        .get(&format!("/api/v1/schedules/{}/calendar.ics", pending_id))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_get_staff_calendar_across_schedules() {
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let next_monday = monday + chrono::Duration::days(7);
+    let (first_id, second_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let staff_id = Uuid::new_v4();
+
+    let mut assignments =
+        create_sample_assignments(first_id, group_id, vec![staff_id, Uuid::new_v4()], monday);
+    assignments.extend(create_sample_assignments(
+        second_id,
+        group_id,
+        vec![staff_id],
+        next_monday,
+    ));
+    let jobs = vec![
+        create_completed_job(first_id, group_id, monday),
+        create_completed_job(second_id, group_id, next_monday),
+    ];
+
+    let test_server = setup_test_server_with_jobs(jobs, assignments).await;
+
+    let response: TestResponse = test_server
+        .server
+        .get(&format!("/api/v1/staff/{}/calendar.ics", staff_id))
+        .await;
+
+    response.assert_status_ok();
+    let body = response.text();
+    assert_eq!(body.matches("BEGIN:VEVENT").count(), 14);
+    assert!(body.contains(&format!(
+        "UID:{}-{}@shift-scheduler",
+        next_monday.format("%Y%m%d"),
+        staff_id
+    )));
+}
+
+#[tokio::test]
+async fn test_export_schedule_in_each_format() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let staff_list = create_sample_staff_list(2);
+    let staff_ids: Vec<Uuid> = staff_list.iter().map(|s| s.id).collect();
+    let assignments = create_sample_assignments(job_id, group_id, staff_ids, monday);
+
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![
+        create_completed_job(job_id, group_id, monday),
+    ]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::with_assignments(assignments));
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| Ok(staff_list.clone()));
+
+    let (mut state, _receiver) = create_test_app_state(job_repo, assignment_repo).await;
+    state.data_service_client = Arc::new(mock_client);
+    let server = TestServer::new(create_router(state)).unwrap();
+
+    let response: TestResponse = server
+        .get(&format!("/api/v1/schedules/{}/export?format=csv", job_id))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "text/csv; charset=utf-8");
+    let csv = response.text();
+    let lines: Vec<&str> = csv.lines().collect();
+    // Header, one row per staff member and one totals row per shift type
+    assert_eq!(lines.len(), 1 + 2 + 3);
+    assert!(lines[0].ends_with("Mornings,Evenings,Days off"));
+    assert!(lines[1].starts_with("Staff 1,"));
+    assert!(lines[4].starts_with("Total evenings,"));
+
+    let response: TestResponse = server
+        .get(&format!("/api/v1/schedules/{}/export?format=xlsx", job_id))
+        .await;
+    response.assert_status_ok();
+    assert!(response.as_bytes().starts_with(b"PK"));
+
+    let response: TestResponse = server
+        .get(&format!("/api/v1/schedules/{}/export?format=html", job_id))
+        .await;
+    response.assert_status_ok();
+    assert!(response
+        .header("content-disposition")
+        .to_str()
+        .unwrap()
+        .starts_with("inline;"));
+    let html = response.text();
+    assert!(html.contains("<td class=\"staff\">Staff 1</td>"));
+    assert!(html.contains("class=\"legend\""));
+
+    let response: TestResponse = server
+        .get(&format!("/api/v1/schedules/{}/export?format=pdf", job_id))
+        .await;
+    response.assert_status_ok();
+    assert_eq!(response.header("content-type"), "application/pdf");
+    assert!(response.as_bytes().starts_with(b"%PDF"));
+
+    let response: TestResponse = server
+        .get(&format!("/api/v1/schedules/{}/export?format=pages", job_id))
+        .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
 #[tokio::test]
 async fn test_submit_multiple_schedules() {
     let test_server: TestServerWithReceiver = setup_test_server().await;
@@ -289,7 +565,7 @@ async fn test_schedule_result_contains_expected_fields() {
     let job = create_completed_job(job_id, group_id, monday);
 
     let staff_id = Uuid::new_v4();
-    let assignments = create_sample_assignments(job_id, vec![staff_id], monday);
+    let assignments = create_sample_assignments(job_id, group_id, vec![staff_id], monday);
 
     let test_server = setup_test_server_with_jobs(vec![job], assignments).await;
 
@@ -314,11 +590,205 @@ async fn test_schedule_result_contains_expected_fields() {
     }
 }
 
+#[tokio::test]
+async fn test_list_schedules_filters_by_group_and_status() {
+    let group_id = Uuid::new_v4();
+    let other_group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let jobs = vec![
+        create_completed_job(Uuid::new_v4(), group_id, monday),
+        create_sample_job(Uuid::new_v4(), group_id, monday, JobStatus::Failed),
+        create_completed_job(Uuid::new_v4(), other_group_id, monday),
+    ];
+
+    let test_server = setup_test_server_with_jobs(jobs, vec![]).await;
+
+    let response: TestResponse = test_server
+        .server
+        .get(&format!(
+            "/api/v1/schedules?staff_group_id={}&status=COMPLETED",
+            group_id
+        ))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["message"], "Schedule list retrieved successfully");
+    assert_eq!(body["data"]["total"], 1);
+    let data = body["data"]["data"].as_array().unwrap();
+    assert_eq!(data.len(), 1);
+    assert_eq!(data[0]["staff_group_id"], group_id.to_string());
+    assert_eq!(data[0]["status"], "COMPLETED");
+}
+
+#[tokio::test]
+async fn test_list_schedules_pagination_and_period_filter() {
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let jobs: Vec<ScheduleJob> = (0..5)
+        .map(|week| {
+            create_completed_job(
+                Uuid::new_v4(),
+                group_id,
+                monday + chrono::Duration::weeks(week),
+            )
+        })
+        .collect();
+
+    let test_server = setup_test_server_with_jobs(jobs, vec![]).await;
+
+    let response: TestResponse = test_server
+        .server
+        .get(&format!(
+            "/api/v1/schedules?period_from={}&page=2&page_size=2",
+            monday + chrono::Duration::weeks(1)
+        ))
+        .await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["total"], 4);
+    assert_eq!(body["data"]["page"], 2);
+    assert_eq!(body["data"]["total_pages"], 2);
+    assert_eq!(body["data"]["data"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_list_schedules_invalid_period_range() {
+    let test_server = setup_test_server().await;
+    let monday = get_test_monday();
+
+    let response: TestResponse = test_server
+        .server
+        .get(&format!(
+            "/api/v1/schedules?period_from={}&period_to={}",
+            monday + chrono::Duration::weeks(1),
+            monday
+        ))
+        .await;
+
+    response.assert_status(StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_submit_schedule_idempotency_key_replays_original_job() {
+    let mut test_server = setup_test_server().await;
+    let request_body = json!({
+        "staff_group_id": Uuid::new_v4().to_string(),
+        "period_begin_date": get_test_monday().to_string()
+    });
+
+    let first: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .add_header("Idempotency-Key", "retry-key-1")
+        .json(&request_body)
+        .await;
+    first.assert_status(StatusCode::ACCEPTED);
+
+    let second: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .add_header("Idempotency-Key", "retry-key-1")
+        .json(&request_body)
+        .await;
+    second.assert_status(StatusCode::ACCEPTED);
+    assert_eq!(second.header("Idempotent-Replayed"), "true");
+
+    let body1: serde_json::Value = first.json();
+    let body2: serde_json::Value = second.json();
+    assert_eq!(body1["data"]["schedule_id"], body2["data"]["schedule_id"]);
+
+    // Only the original submission reaches the processor
+    assert!(test_server.receiver.try_recv().is_ok());
+    assert!(test_server.receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_submit_schedule_idempotency_key_with_different_payload() {
+    let test_server = setup_test_server().await;
+    let monday = get_test_monday();
+
+    let first: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .add_header("Idempotency-Key", "retry-key-2")
+        .json(&json!({
+            "staff_group_id": Uuid::new_v4().to_string(),
+            "period_begin_date": monday.to_string()
+        }))
+        .await;
+    first.assert_status(StatusCode::ACCEPTED);
+
+    let second: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .add_header("Idempotency-Key", "retry-key-2")
+        .json(&json!({
+            "staff_group_id": Uuid::new_v4().to_string(),
+            "period_begin_date": monday.to_string()
+        }))
+        .await;
+    second.assert_status(StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_submit_schedule_reject_policy_with_active_job() {
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let active_job = create_sample_job(Uuid::new_v4(), group_id, monday, JobStatus::Processing);
+
+    let test_server = setup_test_server_with_jobs(vec![active_job], vec![]).await;
+
+    let response: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .json(&json!({
+            "staff_group_id": group_id.to_string(),
+            "period_begin_date": monday.to_string(),
+            "duplicate_policy": "REJECT"
+        }))
+        .await;
+
+    response.assert_status(StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn test_submit_schedule_supersede_policy_cancels_active_job() {
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let active_job_id = Uuid::new_v4();
+    let active_job = create_sample_job(active_job_id, group_id, monday, JobStatus::Pending);
+
+    let test_server = setup_test_server_with_jobs(vec![active_job], vec![]).await;
+
+    let response: TestResponse = test_server
+        .server
+        .post("/api/v1/schedules")
+        .json(&json!({
+            "staff_group_id": group_id.to_string(),
+            "period_begin_date": monday.to_string(),
+            "duplicate_policy": "SUPERSEDE"
+        }))
+        .await;
+    response.assert_status(StatusCode::ACCEPTED);
+
+    let status: TestResponse = test_server
+        .server
+        .get(&format!("/api/v1/schedules/{}/status", active_job_id))
+        .await;
+    let body: serde_json::Value = status.json();
+    assert_eq!(body["data"]["status"], "CANCELLED");
+}
+
 // ============================================================================
 // Job Processing Tests with Mocked Data Service
 // ============================================================================
 
-use common::{create_sample_staff_list, MockDataServiceClient};
+use common::{MockUnitOfWork, RecordingEventPublisher};
+use scheduling_service::domain::events::JobEventKind;
 use scheduling_service::domain::schedule_generator::ScheduleGenerator;
 use scheduling_service::infrastructure::JobProcessor;
 
@@ -352,14 +822,22 @@ async fn test_job_processor_success_with_mock_data_service() {
     let mut mock_client = MockDataServiceClient::new();
     mock_client
         .expect_get_group_members()
-        .with(mockall::predicate::eq(group_id))
+        .with(
+            mockall::predicate::eq(group_id),
+            mockall::predicate::eq(monday),
+            mockall::predicate::eq(monday + chrono::Duration::days(27)),
+        )
         .times(1)
-        .returning(move |_| Ok(staff_list.clone()));
+        .returning(move |_, _, _| Ok(staff_list.clone()));
 
     let scheduler = Arc::new(create_test_scheduler());
-    let processor = Arc::new(JobProcessor::new(
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
         job_repo.clone(),
         assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
         Arc::new(mock_client),
         scheduler,
     ));
@@ -372,6 +850,9 @@ async fn test_job_processor_success_with_mock_data_service() {
         job_id,
         staff_group_id: group_id,
         period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
     };
     sender.send(request).await.unwrap();
 
@@ -406,14 +887,22 @@ async fn test_job_processor_empty_group_with_mock_data_service() {
     let mut mock_client = MockDataServiceClient::new();
     mock_client
         .expect_get_group_members()
-        .with(mockall::predicate::eq(group_id))
+        .with(
+            mockall::predicate::eq(group_id),
+            mockall::predicate::always(),
+            mockall::predicate::always(),
+        )
         .times(1)
-        .returning(|_| Ok(vec![]));
+        .returning(|_, _, _| Ok(vec![]));
 
     let scheduler = Arc::new(create_test_scheduler());
-    let processor = Arc::new(JobProcessor::new(
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
         job_repo.clone(),
         assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
         Arc::new(mock_client),
         scheduler,
     ));
@@ -426,6 +915,9 @@ async fn test_job_processor_empty_group_with_mock_data_service() {
         job_id,
         staff_group_id: group_id,
         period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
     };
     sender.send(request).await.unwrap();
 
@@ -456,18 +948,26 @@ async fn test_job_processor_data_service_error_with_mock() {
     let mut mock_client = MockDataServiceClient::new();
     mock_client
         .expect_get_group_members()
-        .with(mockall::predicate::eq(group_id))
+        .with(
+            mockall::predicate::eq(group_id),
+            mockall::predicate::always(),
+            mockall::predicate::always(),
+        )
         .times(1)
-        .returning(|_| {
+        .returning(|_, _, _| {
             Err(shared::DomainError::ExternalServiceError(
                 "Data service unavailable".to_string(),
             ))
         });
 
     let scheduler = Arc::new(create_test_scheduler());
-    let processor = Arc::new(JobProcessor::new(
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
         job_repo.clone(),
         assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
         Arc::new(mock_client),
         scheduler,
     ));
@@ -480,6 +980,9 @@ async fn test_job_processor_data_service_error_with_mock() {
         job_id,
         staff_group_id: group_id,
         period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
     };
     sender.send(request).await.unwrap();
 
@@ -510,9 +1013,13 @@ async fn test_job_processor_group_not_found_with_mock_data_service() {
     let mut mock_client = MockDataServiceClient::new();
     mock_client
         .expect_get_group_members()
-        .with(mockall::predicate::eq(group_id))
+        .with(
+            mockall::predicate::eq(group_id),
+            mockall::predicate::always(),
+            mockall::predicate::always(),
+        )
         .times(1)
-        .returning(|id| {
+        .returning(|id, _, _| {
             Err(shared::DomainError::NotFound(format!(
                 "Group {} not found",
                 id
@@ -520,9 +1027,13 @@ async fn test_job_processor_group_not_found_with_mock_data_service() {
         });
 
     let scheduler = Arc::new(create_test_scheduler());
-    let processor = Arc::new(JobProcessor::new(
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
         job_repo.clone(),
         assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
         Arc::new(mock_client),
         scheduler,
     ));
@@ -535,6 +1046,9 @@ async fn test_job_processor_group_not_found_with_mock_data_service() {
         job_id,
         staff_group_id: group_id,
         period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
     };
     sender.send(request).await.unwrap();
 
@@ -546,3 +1060,598 @@ async fn test_job_processor_group_not_found_with_mock_data_service() {
     assert!(updated_job.is_some());
     assert_eq!(updated_job.unwrap().status, JobStatus::Failed);
 }
+
+/// Test that a failure while completing the job rolls back the assignments
+#[tokio::test]
+async fn test_job_processor_rolls_back_assignments_when_completion_fails() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let staff_list = create_sample_staff_list(3);
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| Ok(staff_list.clone()));
+
+    let unit_of_work = Arc::new(
+        MockUnitOfWork::new(job_repo.clone(), assignment_repo.clone()).failing_on_mark_completed(),
+    );
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
+        Arc::new(mock_client),
+        Arc::new(create_test_scheduler()),
+    ));
+
+    let (sender, _handle) = processor.start();
+    let request = scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+        job_id,
+        staff_group_id: group_id,
+        period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
+    };
+    sender.send(request).await.unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let updated_job = job_repo.find_by_id(job_id).await.unwrap().unwrap();
+    assert_eq!(updated_job.status, JobStatus::Failed);
+    assert!(updated_job.completed_at.is_none());
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert!(assignments.is_empty());
+}
+
+/// Test that a job superseded while it runs stays cancelled and saves nothing
+#[tokio::test]
+async fn test_job_processor_discards_job_cancelled_while_running() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    // Supersede the job once the processor has started fetching staff
+    let staff_list = create_sample_staff_list(3);
+    let superseding_repo = job_repo.clone();
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| {
+            superseding_repo.cancel(job_id);
+            Ok(staff_list.clone())
+        });
+
+    let publisher = Arc::new(RecordingEventPublisher::new());
+    let processor = Arc::new(
+        JobProcessor::new(
+            job_repo.clone(),
+            Arc::new(MockUnitOfWork::new(
+                job_repo.clone(),
+                assignment_repo.clone(),
+            )),
+            Arc::new(mock_client),
+            Arc::new(create_test_scheduler()),
+        )
+        .with_event_publisher(publisher.clone()),
+    );
+
+    let (sender, _handle) = processor.start();
+    let request = scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+        job_id,
+        staff_group_id: group_id,
+        period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
+    };
+    sender.send(request).await.unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let updated_job = job_repo.find_by_id(job_id).await.unwrap().unwrap();
+    assert_eq!(updated_job.status, JobStatus::Cancelled);
+    assert!(updated_job.completed_at.is_none());
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert!(assignments.is_empty());
+
+    let statuses: Vec<JobStatus> = publisher
+        .events()
+        .into_iter()
+        .filter_map(|event| match event.kind {
+            JobEventKind::StatusChanged { status, .. } => Some(status),
+            JobEventKind::Progress { .. } => None,
+        })
+        .collect();
+    assert_eq!(statuses, vec![JobStatus::Processing]);
+}
+
+/// Test that a job superseded while it runs is not marked failed afterwards
+#[tokio::test]
+async fn test_job_processor_keeps_job_cancelled_while_failing() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let superseding_repo = job_repo.clone();
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| {
+            superseding_repo.cancel(job_id);
+            Err(shared::DomainError::ExternalServiceError(
+                "Data service unavailable".to_string(),
+            ))
+        });
+
+    let publisher = Arc::new(RecordingEventPublisher::new());
+    let processor = Arc::new(
+        JobProcessor::new(
+            job_repo.clone(),
+            Arc::new(MockUnitOfWork::new(
+                job_repo.clone(),
+                assignment_repo.clone(),
+            )),
+            Arc::new(mock_client),
+            Arc::new(create_test_scheduler()),
+        )
+        .with_event_publisher(publisher.clone()),
+    );
+
+    let (sender, _handle) = processor.start();
+    let request = scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+        job_id,
+        staff_group_id: group_id,
+        period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
+    };
+    sender.send(request).await.unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let updated_job = job_repo.find_by_id(job_id).await.unwrap().unwrap();
+    assert_eq!(updated_job.status, JobStatus::Cancelled);
+
+    let statuses: Vec<JobStatus> = publisher
+        .events()
+        .into_iter()
+        .filter_map(|event| match event.kind {
+            JobEventKind::StatusChanged { status, .. } => Some(status),
+            JobEventKind::Progress { .. } => None,
+        })
+        .collect();
+    assert_eq!(statuses, vec![JobStatus::Processing]);
+}
+
+/// Test that a retried job replaces assignments left by an earlier attempt
+#[tokio::test]
+async fn test_job_processor_replaces_assignments_from_earlier_attempt() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let staff_list = create_sample_staff_list(3);
+    let stale = create_sample_assignments(job_id, group_id, vec![staff_list[0].id], monday);
+    let stale_ids: Vec<Uuid> = stale.iter().map(|a| a.id).collect();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::with_assignments(stale));
+
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| Ok(staff_list.clone()));
+
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
+        job_repo.clone(),
+        assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
+        Arc::new(mock_client),
+        Arc::new(create_test_scheduler()),
+    ));
+
+    let (sender, _handle) = processor.start();
+    let request = scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+        job_id,
+        staff_group_id: group_id,
+        period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
+    };
+    sender.send(request).await.unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let updated_job = job_repo.find_by_id(job_id).await.unwrap().unwrap();
+    assert_eq!(updated_job.status, JobStatus::Completed);
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert_eq!(assignments.len(), 3 * 28);
+    assert!(assignments.iter().all(|a| !stale_ids.contains(&a.id)));
+}
+
+/// Test that evenings worked in earlier periods push new evenings to other staff
+#[tokio::test]
+async fn test_job_processor_rotates_evenings_using_assignment_history() {
+    let job_id = Uuid::new_v4();
+    let previous_job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let staff_list = create_sample_staff_list(6);
+    let staff_ids: Vec<Uuid> = staff_list.iter().map(|s| s.id).collect();
+
+    // Two staff members worked every evening of the previous period
+    let history: Vec<ShiftAssignment> = (1..=28)
+        .flat_map(|days_before| {
+            let date = monday - chrono::Duration::days(days_before);
+            staff_ids[..2].iter().map(move |staff_id| ShiftAssignment {
+                id: Uuid::new_v4(),
+                schedule_job_id: previous_job_id,
+                staff_id: *staff_id,
+                staff_group_id: group_id,
+                date,
+                shift: shared::ShiftType::Evening,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            })
+        })
+        .collect();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::with_assignments(history));
+
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| Ok(staff_list.clone()));
+
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
+        job_repo.clone(),
+        assignment_repo.clone(),
+    ));
+    let processor = Arc::new(
+        JobProcessor::new(
+            job_repo.clone(),
+            unit_of_work,
+            Arc::new(mock_client),
+            Arc::new(create_test_scheduler()),
+        )
+        .with_assignment_history(assignment_repo.clone(), 28),
+    );
+
+    let (sender, _handle) = processor.start();
+    let request = scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+        job_id,
+        staff_group_id: group_id,
+        period_begin_date: monday,
+        seed: 0,
+        additional_group_ids: vec![],
+        coverage_targets: vec![],
+    };
+    sender.send(request).await.unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert_eq!(assignments.len(), 6 * 28);
+
+    let evenings = |staff_id: &Uuid| {
+        assignments
+            .iter()
+            .filter(|a| a.staff_id == *staff_id && a.shift == shared::ShiftType::Evening)
+            .count()
+    };
+    let most_after_history = staff_ids[..2].iter().map(evenings).max().unwrap();
+    let fewest_without_history = staff_ids[2..].iter().map(evenings).min().unwrap();
+    assert!(most_after_history < fewest_without_history);
+}
+
+/// Staff shared between the scheduled groups get one shift a day, each worked
+/// for one of their groups
+#[tokio::test]
+async fn test_job_processor_schedules_groups_from_shared_pool() {
+    let job_id = Uuid::new_v4();
+    let backend_id = Uuid::new_v4();
+    let frontend_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let staff_list = create_sample_staff_list(6);
+    let backend = staff_list[..4].to_vec();
+    let frontend = staff_list[2..].to_vec();
+    let shared_ids: Vec<Uuid> = staff_list[2..4].iter().map(|s| s.id).collect();
+
+    let job = create_sample_job(job_id, backend_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |group_id, _, _| {
+            Ok(if group_id == backend_id {
+                backend.clone()
+            } else {
+                frontend.clone()
+            })
+        });
+
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
+        job_repo.clone(),
+        assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
+        Arc::new(mock_client),
+        Arc::new(create_test_scheduler()),
+    ));
+
+    let (sender, _handle) = processor.start();
+    sender
+        .send(
+            scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+                job_id,
+                staff_group_id: backend_id,
+                period_begin_date: monday,
+                seed: 0,
+                additional_group_ids: vec![frontend_id],
+                coverage_targets: vec![],
+            },
+        )
+        .await
+        .unwrap();
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert_eq!(assignments.len(), 6 * 28);
+
+    for assignment in &assignments {
+        let position = staff_list
+            .iter()
+            .position(|s| s.id == assignment.staff_id)
+            .unwrap();
+        let expected: &[Uuid] = match position {
+            0 | 1 => &[backend_id],
+            2 | 3 => &[backend_id, frontend_id],
+            _ => &[frontend_id],
+        };
+        assert!(expected.contains(&assignment.staff_group_id));
+    }
+
+    // Shared staff end up working for both groups over the period
+    let shared_groups: std::collections::HashSet<Uuid> = assignments
+        .iter()
+        .filter(|a| shared_ids.contains(&a.staff_id) && a.shift != shared::ShiftType::DayOff)
+        .map(|a| a.staff_group_id)
+        .collect();
+    assert_eq!(shared_groups.len(), 2);
+}
+
+#[tokio::test]
+async fn test_job_processor_fills_subgroup_coverage_from_its_members() {
+    use scheduling_service::domain::entities::CoverageTarget;
+    use scheduling_service::infrastructure::http_client::ResolvedGroupResponse;
+
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let backend_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let staff_list = create_sample_staff_list(9);
+    let backend_ids: Vec<Uuid> = staff_list[..3].iter().map(|s| s.id).collect();
+    let resolved = vec![
+        ResolvedGroupResponse {
+            group_id,
+            group_name: "Engineering".to_string(),
+            path: vec![group_id],
+            members: staff_list[3..].to_vec(),
+        },
+        ResolvedGroupResponse {
+            group_id: backend_id,
+            group_name: "Backend Team".to_string(),
+            path: vec![group_id, backend_id],
+            members: staff_list[..3].to_vec(),
+        },
+    ];
+
+    let unknown_job_id = Uuid::new_v4();
+    let jobs = vec![
+        create_sample_job(job_id, group_id, monday, JobStatus::Pending),
+        create_sample_job(unknown_job_id, group_id, monday, JobStatus::Pending),
+    ];
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(jobs));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_resolved_groups()
+        .returning(move |_, _, _| Ok(resolved.clone()));
+
+    let unit_of_work = Arc::new(MockUnitOfWork::new(
+        job_repo.clone(),
+        assignment_repo.clone(),
+    ));
+    let processor = Arc::new(JobProcessor::new(
+        job_repo.clone(),
+        unit_of_work,
+        Arc::new(mock_client),
+        Arc::new(create_test_scheduler()),
+    ));
+
+    let (sender, _handle) = processor.start();
+    for (job_id, target_group_id) in [(job_id, backend_id), (unknown_job_id, Uuid::new_v4())] {
+        sender
+            .send(
+                scheduling_service::api::requests::schedule_request::ScheduleJobRequest {
+                    job_id,
+                    staff_group_id: group_id,
+                    period_begin_date: monday,
+                    seed: 0,
+                    additional_group_ids: vec![],
+                    coverage_targets: vec![CoverageTarget {
+                        group_id: target_group_id,
+                        shift: shared::ShiftType::Evening,
+                        min_staff: 1,
+                    }],
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+    let assignments = assignment_repo.find_by_job_id(job_id).await.unwrap();
+    assert_eq!(assignments.len(), 9 * 28);
+    for day in 0..28 {
+        let date = monday + chrono::Duration::days(day);
+        assert!(assignments.iter().any(|a| a.date == date
+            && a.shift == shared::ShiftType::Evening
+            && backend_ids.contains(&a.staff_id)));
+    }
+
+    // Targets must name a group of the resolved hierarchy
+    let failed = job_repo.find_by_id(unknown_job_id).await.unwrap().unwrap();
+    assert_eq!(failed.status, JobStatus::Failed);
+}
+
+/// Members of several subgroups are returned once by the data service client
+#[tokio::test]
+async fn test_data_service_client_deduplicates_subgroup_members() {
+    use scheduling_service::infrastructure::http_client::{
+        DataServiceClient, DataServiceClientTrait,
+    };
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let group_id = Uuid::new_v4();
+    let (backend_id, frontend_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let staff_list = create_sample_staff_list(3);
+    let stub = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!(
+            "/api/v1/groups/{}/resolved-members",
+            group_id
+        )))
+        .and(query_param("from", "2025-01-06"))
+        .and(query_param("to", "2025-02-02"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Resolved members retrieved successfully",
+            "data": [
+                {
+                    "group_id": backend_id,
+                    "group_name": "Backend",
+                    "path": [group_id, backend_id],
+                    "members": [staff_list[0], staff_list[1]]
+                },
+                {
+                    "group_id": frontend_id,
+                    "group_name": "Frontend",
+                    "path": [group_id, frontend_id],
+                    "members": [staff_list[1], staff_list[2]]
+                }
+            ]
+        })))
+        .mount(&stub)
+        .await;
+
+    let from = chrono::NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+    let to = chrono::NaiveDate::from_ymd_opt(2025, 2, 2).unwrap();
+    let members = DataServiceClient::new(stub.uri())
+        .get_group_members(group_id, from, to)
+        .await
+        .unwrap();
+
+    let ids: Vec<Uuid> = members.iter().map(|s| s.id).collect();
+    assert_eq!(ids, staff_list.iter().map(|s| s.id).collect::<Vec<_>>());
+}
+
+/// A member transferred between subgroups is returned once per membership
+/// period, so the scheduler knows the days they belong to the group
+#[tokio::test]
+async fn test_data_service_client_keeps_membership_periods() {
+    use scheduling_service::domain::staff_pool::MembershipPeriod;
+    use scheduling_service::infrastructure::http_client::{
+        DataServiceClient, DataServiceClientTrait,
+    };
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    let group_id = Uuid::new_v4();
+    let (backend_id, frontend_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let jane = &create_sample_staff_list(1)[0];
+    let mut before = serde_json::to_value(jane).unwrap();
+    before["valid_to"] = json!("2025-01-19");
+    let mut after = serde_json::to_value(jane).unwrap();
+    after["valid_from"] = json!("2025-01-20");
+
+    let stub = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path(format!(
+            "/api/v1/groups/{}/resolved-members",
+            group_id
+        )))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "success": true,
+            "message": "Resolved members retrieved successfully",
+            "data": [
+                {
+                    "group_id": backend_id,
+                    "group_name": "Backend",
+                    "path": [group_id, backend_id],
+                    "members": [before]
+                },
+                {
+                    "group_id": frontend_id,
+                    "group_name": "Frontend",
+                    "path": [group_id, frontend_id],
+                    "members": [after]
+                }
+            ]
+        })))
+        .mount(&stub)
+        .await;
+
+    let day = |d| chrono::NaiveDate::from_ymd_opt(2025, 1, d).unwrap();
+    let members = DataServiceClient::new(stub.uri())
+        .get_group_members(group_id, day(6), day(31))
+        .await
+        .unwrap();
+
+    let periods: Vec<(Uuid, MembershipPeriod)> = members
+        .iter()
+        .map(|s| (s.id, s.membership_period()))
+        .collect();
+    assert_eq!(
+        periods,
+        vec![
+            (jane.id, MembershipPeriod::new(None, Some(day(19)))),
+            (jane.id, MembershipPeriod::new(Some(day(20)), None)),
+        ]
+    );
+}
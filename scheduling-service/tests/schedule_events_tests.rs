@@ -0,0 +1,176 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use common::{
+    create_completed_job, create_sample_job, create_test_app_state, get_test_monday,
+    MockScheduleJobRepository, MockShiftAssignmentRepository,
+};
+use futures::StreamExt;
+use scheduling_service::api::create_router;
+use scheduling_service::domain::entities::ScheduleJob;
+use scheduling_service::domain::events::{JobEvent, JobEventPublisher, JobEventSubscriber};
+use scheduling_service::infrastructure::redis::create_redis_pool;
+use scheduling_service::infrastructure::{RedisJobEventPublisher, RedisJobEventSubscriber};
+use shared::JobStatus;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Setup a test server and return the publisher feeding its event streams
+async fn setup_test_server(job_list: Vec<ScheduleJob>) -> (TestServer, Arc<dyn JobEventPublisher>) {
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(job_list));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let (state, _receiver) = create_test_app_state(job_repo, assignment_repo).await;
+    let publisher = state.event_publisher.clone();
+
+    (TestServer::new(create_router(state)).unwrap(), publisher)
+}
+
+/// Split an SSE body into (event name, JSON data) pairs
+fn parse_sse(body: &str) -> Vec<(String, serde_json::Value)> {
+    body.split("\n\n")
+        .filter_map(|frame| {
+            let mut name = None;
+            let mut data = None;
+            for line in frame.lines() {
+                if let Some(value) = line.strip_prefix("event: ") {
+                    name = Some(value.to_string());
+                } else if let Some(value) = line.strip_prefix("data: ") {
+                    data = Some(serde_json::from_str(value).unwrap());
+                }
+            }
+            Some((name?, data?))
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_events_for_finished_job_close_after_current_status() {
+    let job_id = Uuid::new_v4();
+    let job = create_completed_job(job_id, Uuid::new_v4(), get_test_monday());
+    let (server, _publisher) = setup_test_server(vec![job]).await;
+
+    let response = server
+        .get(&format!("/api/v1/schedules/{}/events", job_id))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+    assert!(response
+        .header("content-type")
+        .to_str()
+        .unwrap()
+        .starts_with("text/event-stream"));
+
+    let events = parse_sse(&response.text());
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].0, "status_changed");
+    assert_eq!(events[0].1["status"], "COMPLETED");
+    assert_eq!(events[0].1["schedule_id"], job_id.to_string());
+}
+
+#[tokio::test]
+async fn test_events_stream_progress_until_terminal_status() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let (server, publisher) = setup_test_server(vec![job]).await;
+
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let events = [
+            JobEvent::status_changed(job_id, group_id, monday, JobStatus::Processing, None),
+            // Events for other jobs must not leak into the stream
+            JobEvent::progress(Uuid::new_v4(), group_id, monday, "fetching_staff", 10),
+            JobEvent::progress(job_id, group_id, monday, "generating_schedule", 40),
+            JobEvent::status_changed(job_id, group_id, monday, JobStatus::Completed, None),
+            JobEvent::progress(job_id, group_id, monday, "after_completion", 100),
+        ];
+        for event in &events {
+            publisher.publish(event).await;
+        }
+    });
+
+    let response = server
+        .get(&format!("/api/v1/schedules/{}/events", job_id))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::OK);
+
+    let events = parse_sse(&response.text());
+    let summary: Vec<(&str, &str)> = events
+        .iter()
+        .map(|(name, data)| {
+            let detail = data["status"].as_str().or(data["stage"].as_str());
+            (name.as_str(), detail.unwrap())
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("status_changed", "PENDING"),
+            ("status_changed", "PROCESSING"),
+            ("progress", "generating_schedule"),
+            ("status_changed", "COMPLETED"),
+        ]
+    );
+    assert_eq!(events[2].1["percent"], 40);
+}
+
+#[tokio::test]
+async fn test_events_for_unknown_job() {
+    let (server, _publisher) = setup_test_server(vec![]).await;
+
+    let response = server
+        .get(&format!("/api/v1/schedules/{}/events", Uuid::new_v4()))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_redis_event_publisher_reaches_subscriber() {
+    let redis_pool = create_redis_pool("redis://localhost:6379")
+        .await
+        .expect("Failed to create Redis pool for tests - ensure Redis is running");
+    let client = redis::Client::open("redis://localhost:6379").unwrap();
+
+    let publisher = RedisJobEventPublisher::new(redis_pool);
+    let subscriber = RedisJobEventSubscriber::new(client);
+
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+    let mut stream = subscriber.subscribe(job_id).await.unwrap();
+
+    publisher
+        .publish(&JobEvent::progress(
+            Uuid::new_v4(),
+            group_id,
+            monday,
+            "fetching_staff",
+            10,
+        ))
+        .await;
+    publisher
+        .publish(&JobEvent::status_changed(
+            job_id,
+            group_id,
+            monday,
+            JobStatus::Failed,
+            Some("No active staff members found in the group".to_string()),
+        ))
+        .await;
+
+    let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for job event")
+        .expect("event stream ended");
+
+    assert_eq!(event.schedule_id, job_id);
+    assert!(event.is_terminal());
+}
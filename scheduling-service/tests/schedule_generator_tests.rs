@@ -0,0 +1,452 @@
+use chrono::NaiveDate;
+use scheduling_service::domain::entities::ShiftAssignment;
+use scheduling_service::domain::fairness::{Burden, FairnessLedger};
+use scheduling_service::domain::holiday_schedule::{HolidayPolicy, HolidaySchedule};
+use scheduling_service::domain::rules::{
+    MaxDaysOffRule, MinDaysOffRule, NoMorningAfterEveningRule, Rule, ShiftBalanceRule,
+};
+use scheduling_service::domain::schedule_generator::ScheduleGenerator;
+use scheduling_service::domain::staff_pool::{MembershipPeriod, StaffPool};
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Regenerate golden files with `UPDATE_GOLDEN=1 cargo test`
+const UPDATE_GOLDEN_ENV: &str = "UPDATE_GOLDEN";
+
+fn create_generator() -> ScheduleGenerator {
+    let rules: Vec<Arc<dyn Rule>> = vec![
+        Arc::new(NoMorningAfterEveningRule::new()),
+        Arc::new(MinDaysOffRule::new(1)),
+        Arc::new(MaxDaysOffRule::new(2)),
+        Arc::new(ShiftBalanceRule::new(1)),
+    ];
+    ScheduleGenerator::new(rules)
+}
+
+/// Members of a group on every day
+fn always(staff_ids: &[Uuid]) -> Vec<(Uuid, MembershipPeriod)> {
+    staff_ids
+        .iter()
+        .map(|id| (*id, MembershipPeriod::default()))
+        .collect()
+}
+
+fn monday() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+}
+
+fn staff(count: u128) -> Vec<Uuid> {
+    (1..=count).map(Uuid::from_u128).collect()
+}
+
+fn pool(staff_ids: Vec<Uuid>) -> StaffPool {
+    StaffPool::new().with_group(Uuid::from_u128(1000), always(&staff_ids))
+}
+
+/// The roster without ids or `created_at`, one assignment per line
+fn render(assignments: &[ShiftAssignment]) -> String {
+    assignments
+        .iter()
+        .map(|a| format!("{} {} {:?}\n", a.date, a.staff_id, a.shift))
+        .collect()
+}
+
+fn assert_golden(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+
+    if std::env::var_os(UPDATE_GOLDEN_ENV).is_some() {
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Missing golden file {}: {}", path.display(), e));
+    assert!(
+        expected == actual,
+        "Output differs from {}; rerun with {}=1 if the change is intended",
+        path.display(),
+        UPDATE_GOLDEN_ENV
+    );
+}
+
+#[test]
+fn test_generation_matches_golden_file() {
+    let assignments = create_generator()
+        .generate_schedule(
+            &pool(staff(6)),
+            monday(),
+            Uuid::from_u128(99),
+            42,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    assert_golden("schedule_seed_42.txt", &render(&assignments));
+}
+
+#[test]
+fn test_generation_ignores_staff_order_and_duplicates() {
+    let generator = create_generator();
+    let job_id = Uuid::from_u128(99);
+
+    let sorted = generator
+        .generate_schedule(
+            &pool(staff(10)),
+            monday(),
+            job_id,
+            7,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    let mut shuffled = staff(10);
+    shuffled.reverse();
+    shuffled.push(Uuid::from_u128(3));
+    let from_shuffled = generator
+        .generate_schedule(
+            &pool(shuffled),
+            monday(),
+            job_id,
+            7,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    assert_eq!(sorted.len(), 10 * 28);
+    assert_eq!(render(&sorted), render(&from_shuffled));
+}
+
+#[test]
+fn test_shared_staff_work_one_group_per_shift() {
+    let (backend, frontend) = (Uuid::from_u128(1000), Uuid::from_u128(2000));
+    let staff_ids = staff(10);
+    let pool = StaffPool::new()
+        .with_group(backend, always(&staff_ids[..6]))
+        .with_group(frontend, always(&staff_ids[4..]));
+
+    let assignments = create_generator()
+        .generate_schedule(
+            &pool,
+            monday(),
+            Uuid::from_u128(99),
+            7,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    // Shared staff are scheduled once per day, not once per group
+    assert_eq!(assignments.len(), 10 * 28);
+    for assignment in &assignments {
+        assert!(pool
+            .groups_of(assignment.staff_id)
+            .contains(&assignment.staff_group_id));
+    }
+}
+
+#[test]
+fn test_mid_period_transfer_only_works_for_the_group_of_the_day() {
+    use shared::ShiftType;
+
+    let (backend, frontend) = (Uuid::from_u128(1000), Uuid::from_u128(2000));
+    let staff_ids = staff(10);
+    let (jane, bob, alice) = (staff_ids[0], staff_ids[1], staff_ids[2]);
+    let day = |offset| monday() + chrono::Duration::days(offset);
+
+    // Jane moves to frontend in the third week, Bob leaves after the first
+    // week and Alice joins frontend in the second
+    let pool = StaffPool::new()
+        .with_group(
+            backend,
+            always(&staff_ids[3..6]).into_iter().chain([
+                (jane, MembershipPeriod::new(None, Some(day(13)))),
+                (bob, MembershipPeriod::new(None, Some(day(6)))),
+            ]),
+        )
+        .with_group(
+            frontend,
+            always(&staff_ids[6..]).into_iter().chain([
+                (jane, MembershipPeriod::new(Some(day(14)), None)),
+                (alice, MembershipPeriod::new(Some(day(7)), None)),
+            ]),
+        );
+
+    let assignments = create_generator()
+        .generate_schedule(
+            &pool,
+            monday(),
+            Uuid::from_u128(99),
+            7,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+    assert_eq!(assignments.len(), 10 * 28);
+
+    for assignment in &assignments {
+        let offset = (assignment.date - monday()).num_days();
+        if assignment.staff_id == jane {
+            let group = if offset < 14 { backend } else { frontend };
+            assert_eq!(assignment.staff_group_id, group, "Jane on day {}", offset);
+        }
+        if assignment.staff_id == bob && offset > 6 {
+            assert_eq!(assignment.shift, ShiftType::DayOff, "Bob on day {}", offset);
+        }
+        if assignment.staff_id == alice && offset < 7 {
+            assert_eq!(
+                assignment.shift,
+                ShiftType::DayOff,
+                "Alice on day {}",
+                offset
+            );
+        }
+        if assignment.shift != ShiftType::DayOff {
+            assert!(pool
+                .groups_on(assignment.staff_id, assignment.date)
+                .contains(&assignment.staff_group_id));
+        }
+    }
+}
+
+#[test]
+fn test_different_seeds_give_different_rosters() {
+    let generator = create_generator();
+    let job_id = Uuid::from_u128(99);
+
+    let first = generator
+        .generate_schedule(
+            &pool(staff(10)),
+            monday(),
+            job_id,
+            1,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+    let second = generator
+        .generate_schedule(
+            &pool(staff(10)),
+            monday(),
+            job_id,
+            2,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    let shifts = |assignments: &[ShiftAssignment]| -> Vec<_> {
+        assignments
+            .iter()
+            .map(|a| (a.date, a.staff_id, a.shift))
+            .collect()
+    };
+    assert_ne!(shifts(&first), shifts(&second));
+}
+
+#[test]
+fn test_resubmitted_seed_reproduces_roster_with_fresh_ids() {
+    let generator = create_generator();
+    let generate = |job_id| {
+        generator
+            .generate_schedule(
+                &pool(staff(6)),
+                monday(),
+                job_id,
+                42,
+                &FairnessLedger::new(),
+                &HolidaySchedule::new(),
+            )
+            .unwrap()
+    };
+
+    let first = generate(Uuid::from_u128(99));
+    let resubmitted = generate(Uuid::from_u128(100));
+
+    assert_eq!(render(&first), render(&resubmitted));
+    let first_ids: HashSet<Uuid> = first.iter().map(|a| a.id).collect();
+    assert!(resubmitted.iter().all(|a| !first_ids.contains(&a.id)));
+}
+
+/// Spread between the most and least burdened staff member
+fn spread(ledger: &FairnessLedger, staff_ids: &[Uuid], burden: Burden) -> u32 {
+    let counts: Vec<u32> = staff_ids
+        .iter()
+        .map(|id| ledger.count(*id, burden))
+        .collect();
+    counts.iter().max().unwrap() - counts.iter().min().unwrap()
+}
+
+#[test]
+fn test_undesirable_shifts_stay_within_tolerance() {
+    // Without rules nothing stops the generator from sharing shifts out evenly
+    let staff_ids = staff(12);
+    let assignments = ScheduleGenerator::new(vec![])
+        .with_fairness_tolerance(1)
+        .generate_schedule(
+            &pool(staff_ids.clone()),
+            monday(),
+            Uuid::from_u128(99),
+            3,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    let ledger = FairnessLedger::from_assignments(HolidaySchedule::new(), &assignments);
+    assert!(spread(&ledger, &staff_ids, Burden::Evening) <= 1);
+    assert!(spread(&ledger, &staff_ids, Burden::Weekend) <= 1);
+}
+
+#[test]
+fn test_burden_from_earlier_periods_is_rotated() {
+    let generator = create_generator();
+    let staff_ids = staff(12);
+    let previous_monday = monday() - chrono::Duration::days(28);
+
+    // In the previous period the first three staff worked every evening
+    let previous: Vec<ShiftAssignment> = (0..28)
+        .flat_map(|day| {
+            let date = previous_monday + chrono::Duration::days(day);
+            staff_ids[..3].iter().map(move |staff_id| ShiftAssignment {
+                id: Uuid::new_v4(),
+                schedule_job_id: Uuid::from_u128(98),
+                staff_id: *staff_id,
+                staff_group_id: Uuid::from_u128(1000),
+                date,
+                shift: shared::ShiftType::Evening,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            })
+        })
+        .collect();
+    let history = FairnessLedger::from_assignments(HolidaySchedule::new(), &previous);
+
+    let assignments = generator
+        .generate_schedule(
+            &pool(staff_ids.clone()),
+            monday(),
+            Uuid::from_u128(99),
+            5,
+            &history,
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+    let current = FairnessLedger::from_assignments(HolidaySchedule::new(), &assignments);
+
+    let max_burdened = staff_ids[..3]
+        .iter()
+        .map(|id| current.count(*id, Burden::Evening))
+        .max()
+        .unwrap();
+    let min_rested = staff_ids[3..]
+        .iter()
+        .map(|id| current.count(*id, Burden::Evening))
+        .min()
+        .unwrap();
+    assert!(
+        max_burdened < min_rested,
+        "staff with past evenings got {} evenings, others at least {}",
+        max_burdened,
+        min_rested
+    );
+}
+
+#[test]
+fn test_holidays_lower_coverage_and_grant_paid_leave() {
+    // Only the weekly limit, so the holiday's effect on it is visible directly
+    let generator = ScheduleGenerator::new(vec![Arc::new(MaxDaysOffRule::new(2))]);
+    let staff_ids = staff(12);
+    let holiday = monday() + chrono::Duration::days(2);
+    let mut holidays = HolidaySchedule::new();
+    holidays.add(
+        holiday,
+        HolidayPolicy {
+            coverage_percent: 50,
+            paid_leave: true,
+            premium_weight: 2,
+        },
+    );
+
+    let assignments = generator
+        .generate_schedule(
+            &pool(staff_ids.clone()),
+            monday(),
+            Uuid::from_u128(99),
+            7,
+            &FairnessLedger::new(),
+            &holidays,
+        )
+        .unwrap();
+
+    let working_on = |date: NaiveDate| {
+        assignments
+            .iter()
+            .filter(|a| a.date == date && a.shift != shared::ShiftType::DayOff)
+            .count()
+    };
+    assert_eq!(working_on(holiday), 4);
+    assert_eq!(working_on(holiday.pred_opt().unwrap()), 8);
+
+    // The holiday day off comes on top of the usual weekly days off
+    let week_end = monday() + chrono::Duration::days(6);
+    let days_off = |staff_id: Uuid, include_holiday: bool| {
+        assignments
+            .iter()
+            .filter(|a| a.staff_id == staff_id && a.date <= week_end)
+            .filter(|a| a.shift == shared::ShiftType::DayOff)
+            .filter(|a| include_holiday || a.date != holiday)
+            .count()
+    };
+    assert!(staff_ids.iter().all(|id| days_off(*id, false) <= 2));
+    assert!(staff_ids.iter().any(|id| days_off(*id, true) == 3));
+}
+
+#[test]
+fn test_subgroup_coverage_is_filled_from_its_members() {
+    use scheduling_service::domain::entities::CoverageTarget;
+    use shared::ShiftType;
+
+    let (engineering, backend) = (Uuid::from_u128(1000), Uuid::from_u128(1001));
+    let staff_ids = staff(9);
+    let backend_staff = staff_ids[..3].to_vec();
+    let pool = StaffPool::new()
+        .with_group(engineering, always(&staff_ids))
+        .with_subgroup_members(&[engineering], always(&staff_ids[3..]))
+        .with_subgroup_members(&[engineering, backend], always(&backend_staff))
+        .with_coverage_targets(vec![CoverageTarget {
+            group_id: backend,
+            shift: ShiftType::Evening,
+            min_staff: 1,
+        }]);
+
+    let assignments = create_generator()
+        .generate_schedule(
+            &pool,
+            monday(),
+            Uuid::from_u128(99),
+            42,
+            &FairnessLedger::new(),
+            &HolidaySchedule::new(),
+        )
+        .unwrap();
+
+    for day in 0..28 {
+        let date = monday() + chrono::Duration::days(day);
+        let backend_evenings = assignments
+            .iter()
+            .filter(|a| {
+                a.date == date
+                    && a.shift == ShiftType::Evening
+                    && backend_staff.contains(&a.staff_id)
+            })
+            .count();
+        assert!(backend_evenings >= 1, "no backend evening on {}", date);
+    }
+}
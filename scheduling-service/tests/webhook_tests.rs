@@ -0,0 +1,396 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use axum::http::StatusCode;
+use axum_test::TestServer;
+use chrono::Utc;
+use common::{
+    create_sample_job, create_sample_staff_list, create_test_app_state, get_test_monday,
+    MockDataServiceClient, MockScheduleJobRepository, MockShiftAssignmentRepository,
+    MockUnitOfWork, MockWebhookRepository, RecordingEventPublisher,
+};
+use scheduling_service::api::create_router;
+use scheduling_service::api::requests::schedule_request::ScheduleJobRequest;
+use scheduling_service::domain::entities::{WebhookDelivery, WebhookSubscription};
+use scheduling_service::domain::events::{JobEvent, JobEventKind, JobEventPublisher};
+use scheduling_service::domain::rules::{MaxDaysOffRule, MinDaysOffRule, Rule};
+use scheduling_service::domain::schedule_generator::ScheduleGenerator;
+use scheduling_service::infrastructure::config::WebhookSettings;
+use scheduling_service::infrastructure::webhook_dispatcher::{
+    sign, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER,
+};
+use scheduling_service::infrastructure::{JobProcessor, WebhookDispatcher};
+use serde_json::json;
+use shared::JobStatus;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const TEST_SECRET: &str = "test-webhook-secret-0123456789";
+
+async fn setup_test_server(webhook_repo: Arc<MockWebhookRepository>) -> TestServer {
+    let job_repo = Arc::new(MockScheduleJobRepository::new());
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let (mut state, _receiver) = create_test_app_state(job_repo, assignment_repo).await;
+    state.webhook_repo = webhook_repo;
+
+    TestServer::new(create_router(state)).unwrap()
+}
+
+fn create_subscription(url: String, staff_group_id: Option<Uuid>) -> WebhookSubscription {
+    let now = Utc::now();
+    WebhookSubscription {
+        id: Uuid::new_v4(),
+        url,
+        secret: TEST_SECRET.to_string(),
+        staff_group_id,
+        active: true,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+fn fast_retry_settings(max_attempts: u32) -> WebhookSettings {
+    WebhookSettings {
+        max_attempts,
+        initial_backoff_ms: 10,
+        request_timeout_secs: 5,
+    }
+}
+
+/// Wait until the dispatcher's background tasks have recorded `count` attempts
+async fn wait_for_deliveries(repo: &MockWebhookRepository, count: usize) -> Vec<WebhookDelivery> {
+    for _ in 0..100 {
+        let deliveries = repo.deliveries();
+        if deliveries.len() >= count {
+            return deliveries;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    repo.deliveries()
+}
+
+#[tokio::test]
+async fn test_webhook_crud_only_reveals_secret_on_create() {
+    let server = setup_test_server(Arc::new(MockWebhookRepository::new())).await;
+    let group_id = Uuid::new_v4();
+
+    let response = server
+        .post("/api/v1/webhooks")
+        .json(&json!({
+            "url": "https://example.com/hooks/schedules",
+            "staff_group_id": group_id
+        }))
+        .await;
+
+    assert_eq!(response.status_code(), StatusCode::CREATED);
+    let body: serde_json::Value = response.json();
+    let webhook_id = body["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["data"]["staff_group_id"], group_id.to_string());
+    assert_eq!(body["data"]["secret"].as_str().unwrap().len(), 32);
+
+    let response = server
+        .get(&format!("/api/v1/webhooks/{}", webhook_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: serde_json::Value = response.json();
+    assert!(body["data"].get("secret").is_none());
+
+    let response = server.get("/api/v1/webhooks").await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["total"], 1);
+
+    let response = server
+        .delete(&format!("/api/v1/webhooks/{}", webhook_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+
+    let response = server
+        .get(&format!("/api/v1/webhooks/{}", webhook_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_create_webhook_rejects_invalid_url_and_short_secret() {
+    let server = setup_test_server(Arc::new(MockWebhookRepository::new())).await;
+
+    let response = server
+        .post("/api/v1/webhooks")
+        .json(&json!({ "url": "not a url" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post("/api/v1/webhooks")
+        .json(&json!({ "url": "ftp://example.com/hook" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+    let response = server
+        .post("/api/v1/webhooks")
+        .json(&json!({ "url": "https://example.com/hook", "secret": "short" }))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_dispatcher_retries_and_signs_deliveries() {
+    let stub = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(500))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&stub)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&stub)
+        .await;
+
+    let group_id = Uuid::new_v4();
+    let subscription = create_subscription(format!("{}/hook", stub.uri()), Some(group_id));
+    let subscription_id = subscription.id;
+    let webhook_repo = Arc::new(MockWebhookRepository::with_subscriptions(vec![
+        subscription,
+    ]));
+    let dispatcher = WebhookDispatcher::new(webhook_repo.clone(), fast_retry_settings(3));
+
+    let job_id = Uuid::new_v4();
+    let event = JobEvent::status_changed(
+        job_id,
+        group_id,
+        get_test_monday(),
+        JobStatus::Completed,
+        None,
+    );
+    dispatcher.publish(&event).await;
+
+    let deliveries = wait_for_deliveries(&webhook_repo, 2).await;
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[0].attempt, 1);
+    assert_eq!(deliveries[0].status_code, Some(500));
+    assert!(!deliveries[0].success);
+    assert_eq!(deliveries[1].attempt, 2);
+    assert_eq!(deliveries[1].status_code, Some(200));
+    assert!(deliveries[1].success);
+    assert_eq!(deliveries[0].delivery_id, deliveries[1].delivery_id);
+    assert!(deliveries
+        .iter()
+        .all(|d| d.subscription_id == subscription_id && d.schedule_job_id == job_id));
+
+    let requests = stub.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 2);
+    for request in &requests {
+        let header = |name: &str| request.headers.get(name).unwrap().to_str().unwrap();
+        let body = String::from_utf8(request.body.clone()).unwrap();
+        let timestamp: i64 = header(TIMESTAMP_HEADER).parse().unwrap();
+
+        assert_eq!(header(EVENT_HEADER), "schedule.status_changed");
+        assert_eq!(
+            header(DELIVERY_HEADER),
+            deliveries[0].delivery_id.to_string()
+        );
+        assert_eq!(
+            header(SIGNATURE_HEADER),
+            sign(TEST_SECRET, timestamp, &body)
+        );
+
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["event"], "status_changed");
+        assert_eq!(payload["status"], "COMPLETED");
+        assert_eq!(payload["schedule_id"], job_id.to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_dispatcher_gives_up_after_max_attempts() {
+    let stub = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&stub)
+        .await;
+
+    let group_id = Uuid::new_v4();
+    let webhook_repo = Arc::new(MockWebhookRepository::with_subscriptions(vec![
+        create_subscription(stub.uri(), None),
+    ]));
+    let dispatcher = WebhookDispatcher::new(webhook_repo.clone(), fast_retry_settings(2));
+
+    let event = JobEvent::status_changed(
+        Uuid::new_v4(),
+        group_id,
+        get_test_monday(),
+        JobStatus::Failed,
+        Some("Scheduling failed".to_string()),
+    );
+    dispatcher.publish(&event).await;
+
+    let deliveries = wait_for_deliveries(&webhook_repo, 2).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(webhook_repo.deliveries().len(), 2);
+    assert!(deliveries.iter().all(|d| !d.success));
+}
+
+#[tokio::test]
+async fn test_dispatcher_only_notifies_matching_groups() {
+    let stub = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&stub)
+        .await;
+
+    let group_id = Uuid::new_v4();
+    let other_group = create_subscription(stub.uri(), Some(Uuid::new_v4()));
+    let same_group = create_subscription(stub.uri(), Some(group_id));
+    let global = create_subscription(stub.uri(), None);
+    let expected = [same_group.id, global.id];
+    let webhook_repo = Arc::new(MockWebhookRepository::with_subscriptions(vec![
+        other_group,
+        same_group,
+        global,
+    ]));
+    let dispatcher = WebhookDispatcher::new(webhook_repo.clone(), fast_retry_settings(1));
+
+    let event = JobEvent::status_changed(
+        Uuid::new_v4(),
+        group_id,
+        get_test_monday(),
+        JobStatus::Processing,
+        None,
+    );
+    dispatcher.publish(&event).await;
+
+    let deliveries = wait_for_deliveries(&webhook_repo, 2).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert_eq!(webhook_repo.deliveries().len(), 2);
+    assert!(deliveries
+        .iter()
+        .all(|d| expected.contains(&d.subscription_id)));
+}
+
+#[tokio::test]
+async fn test_webhook_deliveries_endpoint() {
+    let stub = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&stub)
+        .await;
+
+    let subscription = create_subscription(stub.uri(), None);
+    let subscription_id = subscription.id;
+    let webhook_repo = Arc::new(MockWebhookRepository::with_subscriptions(vec![
+        subscription,
+    ]));
+    let dispatcher = WebhookDispatcher::new(webhook_repo.clone(), fast_retry_settings(1));
+
+    dispatcher
+        .publish(&JobEvent::status_changed(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            get_test_monday(),
+            JobStatus::Completed,
+            None,
+        ))
+        .await;
+    wait_for_deliveries(&webhook_repo, 1).await;
+
+    let server = setup_test_server(webhook_repo).await;
+
+    let response = server
+        .get(&format!("/api/v1/webhooks/{}/deliveries", subscription_id))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::OK);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["data"]["total"], 1);
+    assert_eq!(body["data"]["data"][0]["success"], true);
+    assert_eq!(body["data"]["data"][0]["status_code"], 200);
+
+    let response = server
+        .get(&format!("/api/v1/webhooks/{}/deliveries", Uuid::new_v4()))
+        .await;
+    assert_eq!(response.status_code(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_job_processor_publishes_status_transitions() {
+    let job_id = Uuid::new_v4();
+    let group_id = Uuid::new_v4();
+    let monday = get_test_monday();
+
+    let job = create_sample_job(job_id, group_id, monday, JobStatus::Pending);
+    let job_repo = Arc::new(MockScheduleJobRepository::with_jobs(vec![job]));
+    let assignment_repo = Arc::new(MockShiftAssignmentRepository::new());
+
+    let staff_list = create_sample_staff_list(3);
+    let mut mock_client = MockDataServiceClient::new();
+    mock_client
+        .expect_get_group_members()
+        .returning(move |_, _, _| Ok(staff_list.clone()));
+
+    let rules: Vec<Arc<dyn Rule>> = vec![
+        Arc::new(MinDaysOffRule::new(1)),
+        Arc::new(MaxDaysOffRule::new(2)),
+    ];
+    let publisher = Arc::new(RecordingEventPublisher::new());
+    let processor = Arc::new(
+        JobProcessor::new(
+            job_repo.clone(),
+            Arc::new(MockUnitOfWork::new(job_repo, assignment_repo)),
+            Arc::new(mock_client),
+            Arc::new(ScheduleGenerator::new(rules)),
+        )
+        .with_event_publisher(publisher.clone()),
+    );
+
+    let (sender, _handle) = processor.start();
+    sender
+        .send(ScheduleJobRequest {
+            job_id,
+            staff_group_id: group_id,
+            period_begin_date: monday,
+            seed: 0,
+            additional_group_ids: vec![],
+            coverage_targets: vec![],
+        })
+        .await
+        .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let events = publisher.events();
+    assert!(events.iter().all(|event| event.schedule_id == job_id));
+
+    let statuses: Vec<JobStatus> = events
+        .iter()
+        .filter_map(|event| match &event.kind {
+            JobEventKind::StatusChanged { status, .. } => Some(status.clone()),
+            JobEventKind::Progress { .. } => None,
+        })
+        .collect();
+    assert_eq!(statuses, vec![JobStatus::Processing, JobStatus::Completed]);
+
+    let stages: Vec<&str> = events
+        .iter()
+        .filter_map(|event| match &event.kind {
+            JobEventKind::Progress { stage, .. } => Some(stage.as_str()),
+            JobEventKind::StatusChanged { .. } => None,
+        })
+        .collect();
+    assert_eq!(
+        stages,
+        vec![
+            "fetching_staff",
+            "generating_schedule",
+            "saving_assignments"
+        ]
+    );
+}
@@ -44,6 +44,13 @@ pub async fn invalidate_cache(redis_conn: &mut ConnectionManager, key: &str) {
     let _: Result<(), _> = redis_conn.del(key).await;
 }
 
+/// Invalidate several cache keys at once
+pub async fn invalidate_cache_keys(redis_conn: &mut ConnectionManager, keys: &[String]) {
+    if !keys.is_empty() {
+        let _: Result<(), _> = redis::cmd("DEL").arg(keys).query_async(redis_conn).await;
+    }
+}
+
 /// Invalidate multiple cache keys by pattern
 pub async fn invalidate_cache_pattern(redis_conn: &mut ConnectionManager, pattern: &str) {
     let keys: Result<Vec<String>, _> = redis_conn.keys(pattern).await;
@@ -78,3 +85,38 @@ pub async fn set_cached<T: serde::Serialize>(
         let _: Result<(), _> = redis_conn.set_ex(key, json, ttl_seconds).await;
     }
 }
+
+/// Get a cached value stored in a field of a hash
+pub async fn get_cached_field<T: serde::de::DeserializeOwned>(
+    redis_conn: &mut ConnectionManager,
+    key: &str,
+    field: &str,
+) -> Option<T> {
+    let cached: Result<String, _> = redis_conn.hget(key, field).await;
+    if let Ok(cached_data) = cached {
+        serde_json::from_str(&cached_data).ok()
+    } else {
+        None
+    }
+}
+
+/// Set a cached value in a field of a hash. The TTL applies to the whole
+/// hash, so deleting the key drops every field at once.
+pub async fn set_cached_field<T: serde::Serialize>(
+    redis_conn: &mut ConnectionManager,
+    key: &str,
+    field: &str,
+    value: &T,
+    ttl_seconds: u64,
+) {
+    if let Ok(json) = serde_json::to_string(value) {
+        let _: Result<(), _> = redis::pipe()
+            .atomic()
+            .hset(key, field, json)
+            .ignore()
+            .expire(key, ttl_seconds as i64)
+            .ignore()
+            .query_async(redis_conn)
+            .await;
+    }
+}
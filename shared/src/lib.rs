@@ -6,10 +6,10 @@ pub mod types;
 
 // Re-export commonly used items
 pub use cache::{
-    cache_keys, cache_ttl, create_redis_pool, get_cached, invalidate_cache,
-    invalidate_cache_pattern, set_cached, RedisPool,
+    cache_keys, cache_ttl, create_redis_pool, get_cached, get_cached_field, invalidate_cache,
+    invalidate_cache_keys, invalidate_cache_pattern, set_cached, set_cached_field, RedisPool,
 };
 pub use error::{DomainError, DomainResult};
 pub use pagination::{PaginatedResponse, PaginationParams};
-pub use response::ApiResponse;
-pub use types::{Identifiable, JobStatus, ShiftType, StaffStatus, Timestamped};
+pub use response::{ApiErrorResponse, ApiResponse};
+pub use types::{Identifiable, JobStatus, MembershipRole, ShiftType, StaffStatus, Timestamped};
@@ -7,6 +7,9 @@ pub struct ApiResponse<T> {
     pub data: T,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total: Option<u64>,
+    /// Cursor for the next page of a cursor-paginated listing
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
@@ -15,6 +18,7 @@ impl<T> ApiResponse<T> {
             message: message.into(),
             data,
             total: None,
+            next_cursor: None,
         }
     }
 
@@ -23,6 +27,29 @@ impl<T> ApiResponse<T> {
             message: message.into(),
             data,
             total: Some(total),
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_next_cursor(mut self, next_cursor: Option<String>) -> Self {
+        self.next_cursor = next_cursor;
+        self
+    }
+}
+
+/// Body of a failed request whose `details` say what went wrong, such as the
+/// rows that made an import roll back
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ApiErrorResponse<T> {
+    pub message: String,
+    pub details: T,
+}
+
+impl<T> ApiErrorResponse<T> {
+    pub fn new(message: impl Into<String>, details: T) -> Self {
+        Self {
+            message: message.into(),
+            details,
         }
     }
 }
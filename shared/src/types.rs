@@ -11,6 +11,20 @@ pub enum StaffStatus {
     Inactive,
 }
 
+/// What a staff member does within a group
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq, Hash, sqlx::Type,
+)]
+#[sqlx(type_name = "membership_role", rename_all = "SCREAMING_SNAKE_CASE")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MembershipRole {
+    Lead,
+    #[default]
+    Member,
+    Trainee,
+    Backup,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, Hash, sqlx::Type)]
 #[sqlx(type_name = "shift_type", rename_all = "SCREAMING_SNAKE_CASE")]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
@@ -28,6 +42,7 @@ pub enum JobStatus {
     Processing,
     Completed,
     Failed,
+    Cancelled,
 }
 
 pub trait Timestamped {